@@ -0,0 +1,17 @@
+-- Matched transactions written by Store actions (STORAGE_BACKEND=postgres)
+CREATE TABLE IF NOT EXISTS matched_transactions (
+    id BIGSERIAL PRIMARY KEY,
+    collection TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    matched_filters TEXT[] NOT NULL,
+    slot_leader TEXT,
+    stored_at TIMESTAMPTZ NOT NULL,
+    -- The full StoredTransaction as JSON
+    record JSONB NOT NULL,
+    UNIQUE (collection, signature)
+);
+
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_collection_slot ON matched_transactions(collection, slot);
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_slot ON matched_transactions(slot);
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_signature ON matched_transactions(signature);
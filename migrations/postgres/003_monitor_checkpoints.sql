@@ -0,0 +1,9 @@
+-- Slot checkpoints of monitor instances (CHECKPOINT_BACKEND=postgres), one row per CHECKPOINT_KEY
+CREATE TABLE IF NOT EXISTS monitor_checkpoints (
+    name TEXT PRIMARY KEY,
+    last_processed_slot BIGINT NOT NULL,
+    -- Unix time of the save, in seconds
+    saved_at BIGINT NOT NULL,
+    total_slots_processed BIGINT NOT NULL,
+    total_matches_found BIGINT NOT NULL
+);
@@ -0,0 +1,17 @@
+-- Matched transactions written by Store actions (storage backend "sqlite")
+CREATE TABLE IF NOT EXISTS matched_transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    collection TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    matched_filters TEXT NOT NULL, -- JSON array as string
+    slot_leader TEXT,
+    stored_at DATETIME NOT NULL,
+    record TEXT NOT NULL, -- The full StoredTransaction as JSON
+    UNIQUE (collection, signature)
+);
+
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_collection_slot ON matched_transactions(collection, slot);
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_slot ON matched_transactions(slot);
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_signature ON matched_transactions(signature);
+CREATE INDEX IF NOT EXISTS idx_matched_transactions_stored_at ON matched_transactions(stored_at);
@@ -65,7 +65,13 @@ if [ ! -f "$ENV_FILE" ]; then
 fi
 
 # Create necessary directories
-mkdir -p config tx_data logs
+mkdir -p config checkpoint tx_data logs
+
+# Checkpoints used to be mounted as ./slot_checkpoint.json; carry an old one over
+if [ -f slot_checkpoint.json ] && [ ! -e checkpoint/slot_checkpoint.json ]; then
+    echo "Moving slot_checkpoint.json to checkpoint/slot_checkpoint.json"
+    mv slot_checkpoint.json checkpoint/slot_checkpoint.json
+fi
 
 # Build command based on mode
 case $MODE in
@@ -97,7 +103,8 @@ docker run \
     --name "$CONTAINER_NAME" \
     --env-file "$ENV_FILE" \
     -v "$(pwd)/config:/app/config" \
-    -v "$(pwd)/slot_checkpoint.json:/app/slot_checkpoint.json" \
+    -v "$(pwd)/checkpoint:/app/checkpoint" \
+    -e CHECKPOINT_FILE=/app/checkpoint/slot_checkpoint.json \
     -v "$(pwd)/tx_data:/app/tx_data" \
     -v "$(pwd)/logs:/app/logs" \
     "$IMAGE_NAME" \
@@ -0,0 +1,86 @@
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::rpc_client_with_failover::RpcClientWithFailover;
+
+/// Maximum accounts per `getMultipleAccounts` call
+const LOOKUP_BATCH_SIZE: usize = 100;
+/// Oldest entries are dropped beyond this many cached owners (~150 bytes each)
+const MAX_CACHED_OWNERS: usize = 100_000;
+
+#[derive(Default)]
+struct OwnerMap {
+    owners: HashMap<String, String>,
+    order: VecDeque<String>,
+}
+
+/// Account → owner program, fetched with batched `getMultipleAccounts` calls and cached.
+///
+/// Owners are read at lookup time, not at the transaction's slot: they almost never change,
+/// but an account closed since then has no owner and one reassigned since then reports the
+/// new owner. Missing accounts are not cached, so they are retried on the next lookup.
+pub struct AccountOwnerCache {
+    rpc_client: RpcClientWithFailover,
+    owners: Mutex<OwnerMap>,
+}
+
+impl AccountOwnerCache {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_client: RpcClientWithFailover::new(rpc_url),
+            owners: Mutex::new(OwnerMap::default()),
+        }
+    }
+
+    /// Owners of the given accounts, fetching the ones not cached yet. Accounts that don't
+    /// exist or whose batch failed are left out; failures are logged and never fail the slot.
+    pub async fn owners_of(&self, accounts: &[String]) -> HashMap<String, String> {
+        let missing: Vec<Pubkey> = {
+            let cache = self.owners.lock().await;
+            let mut queued = HashSet::new();
+            accounts.iter()
+                .filter(|account| !cache.owners.contains_key(*account) && queued.insert(*account))
+                .filter_map(|account| Pubkey::from_str(account).ok())
+                .collect()
+        };
+
+        if !missing.is_empty() {
+            debug!("Resolving owners of {} accounts", missing.len());
+        }
+
+        // The lock is not held across RPC calls, so concurrent slots don't wait on each other
+        for batch in missing.chunks(LOOKUP_BATCH_SIZE) {
+            match self.rpc_client.get_multiple_account_owners(batch).await {
+                Ok(owners) => {
+                    let mut cache = self.owners.lock().await;
+                    for (account, owner) in batch.iter().zip(owners) {
+                        if let Some(owner) = owner {
+                            cache.insert(account.to_string(), owner.to_string());
+                        }
+                    }
+                },
+                Err(e) => warn!("Could not resolve owners of {} accounts: {:#}", batch.len(), e),
+            }
+        }
+
+        let cache = self.owners.lock().await;
+        accounts.iter()
+            .filter_map(|account| cache.owners.get(account).map(|owner| (account.clone(), owner.clone())))
+            .collect()
+    }
+}
+
+impl OwnerMap {
+    fn insert(&mut self, account: String, owner: String) {
+        if self.owners.insert(account.clone(), owner).is_none() {
+            self.order.push_back(account);
+        }
+        while self.owners.len() > MAX_CACHED_OWNERS {
+            let Some(oldest) = self.order.pop_front() else { break };
+            self.owners.remove(&oldest);
+        }
+    }
+}
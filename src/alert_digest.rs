@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::filter_engine::{AlertSeverity, MatchedFilter};
+use crate::filter_summary::{format_amount, mint_label, short_address};
+use crate::transaction_extractor::ExtractedTransaction;
+
+/// Transactions listed in a digest, largest volume first
+const DIGEST_TOP_TRANSACTIONS: usize = 5;
+
+/// One Low/Medium match waiting for the next digest
+#[derive(Debug, Clone)]
+struct DigestEntry {
+    filter_name: String,
+    severity: AlertSeverity,
+    signature: String,
+    /// Amount moved per mint: the larger of summed increases and summed decreases
+    volume: HashMap<String, f64>,
+    /// Held-back channels the alert targeted; only their digests list it
+    channels: Vec<String>,
+}
+
+/// Collects Low and Medium severity Telegram/Slack alerts and sends them as one summary every
+/// `interval`, so routine matches don't drown out the High and Critical ones, which still go
+/// out immediately.
+#[derive(Debug)]
+pub struct AlertDigest {
+    interval: Duration,
+    entries: Mutex<Vec<DigestEntry>>,
+}
+
+impl AlertDigest {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, entries: Mutex::new(Vec::new()) }
+    }
+
+    /// `DIGEST_INTERVAL_MINS`; unset or 0 disables digest mode
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(value) = std::env::var("DIGEST_INTERVAL_MINS").ok().filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let mins = value.trim().parse::<u64>()
+            .with_context(|| format!("Invalid DIGEST_INTERVAL_MINS value '{}': expected a number of minutes", value))?;
+        Ok((mins > 0).then(|| Self::new(Duration::from_secs(mins * 60))))
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Whether alerts of `severity` on `channel` wait for the digest instead of going out now
+    pub fn holds(&self, channel: &str, severity: &AlertSeverity) -> bool {
+        matches!(channel, "telegram" | "slack") && matches!(severity, AlertSeverity::Low | AlertSeverity::Medium)
+    }
+
+    /// Hold one alert for the digests of `channels`, each of which `holds` the severity
+    pub fn add(
+        &self,
+        transaction: &ExtractedTransaction,
+        matched_filter: &MatchedFilter,
+        severity: &AlertSeverity,
+        channels: Vec<String>,
+    ) {
+        let mut volume: HashMap<String, (f64, f64)> = HashMap::new();
+        for change in &transaction.token_balance_changes {
+            let (increase, decrease) = volume.entry(change.mint.clone()).or_default();
+            if change.change > 0.0 { *increase += change.change } else { *decrease -= change.change }
+        }
+
+        self.entries.lock().unwrap().push(DigestEntry {
+            filter_name: matched_filter.filter_name.clone(),
+            severity: severity.clone(),
+            signature: transaction.signature.clone(),
+            volume: volume.into_iter().map(|(mint, (inc, dec))| (mint, inc.max(dec))).collect(),
+            channels,
+        });
+    }
+
+    /// One summary per channel of everything collected for it since the last call, by channel
+    /// name; empty when nothing matched
+    pub fn take_summaries(&self) -> BTreeMap<String, String> {
+        let entries = std::mem::take(&mut *self.entries.lock().unwrap());
+        let mut per_channel: BTreeMap<String, Vec<&DigestEntry>> = BTreeMap::new();
+        for entry in &entries {
+            for channel in &entry.channels {
+                per_channel.entry(channel.clone()).or_default().push(entry);
+            }
+        }
+        per_channel.into_iter()
+            .map(|(channel, entries)| (channel, self.summary(&entries)))
+            .collect()
+    }
+
+    fn summary(&self, entries: &[&DigestEntry]) -> String {
+        let mut summary = format!(
+            "📬 Alert digest: {} match(es) in the last {} min\n",
+            entries.len(),
+            self.interval.as_secs() / 60
+        );
+
+        let mut per_filter: BTreeMap<(&str, String), usize> = BTreeMap::new();
+        for entry in entries {
+            *per_filter.entry((entry.filter_name.as_str(), format!("{:?}", entry.severity))).or_default() += 1;
+        }
+        summary.push_str("\nBy filter:\n");
+        for ((filter_name, severity), count) in &per_filter {
+            summary.push_str(&format!("• {} ({}): {}\n", filter_name, severity, count));
+        }
+
+        let mut total_volume: BTreeMap<String, f64> = BTreeMap::new();
+        for (mint, amount) in entries.iter().flat_map(|entry| &entry.volume) {
+            *total_volume.entry(mint_label(mint)).or_default() += amount;
+        }
+        if !total_volume.is_empty() {
+            summary.push_str("\nTotal volume:\n");
+            for (mint, amount) in &total_volume {
+                summary.push_str(&format!("• {} {}\n", format_amount(*amount), mint));
+            }
+        }
+
+        let largest = |entry: &DigestEntry| entry.volume.iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(mint, amount)| (mint.clone(), *amount));
+        let mut top: Vec<(&DigestEntry, Option<(String, f64)>)> = entries.iter().map(|entry| (*entry, largest(*entry))).collect();
+        top.sort_by(|a, b| {
+            let amount = |item: &(&DigestEntry, Option<(String, f64)>)| item.1.as_ref().map_or(0.0, |(_, amount)| *amount);
+            amount(b).total_cmp(&amount(a))
+        });
+        summary.push_str("\nTop transactions:\n");
+        for (entry, largest) in top.iter().take(DIGEST_TOP_TRANSACTIONS) {
+            let amount = largest.as_ref()
+                .map(|(mint, amount)| format!(" — {} {}", format_amount(*amount), mint_label(mint)))
+                .unwrap_or_default();
+            summary.push_str(&format!("• {} ({}){}\n", short_address(&entry.signature), entry.filter_name, amount));
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_engine::tests::{test_transaction, token_change};
+
+    fn transfer(signature: &str, amount: f64) -> ExtractedTransaction {
+        let mut transaction = test_transaction();
+        transaction.signature = signature.to_string();
+        transaction.slot = 7;
+        transaction.token_balance_changes = vec![token_change("from", "MINT", -amount), token_change("to", "MINT", amount)];
+        transaction
+    }
+
+    fn matched(filter_name: &str) -> MatchedFilter {
+        MatchedFilter {
+            filter_id: filter_name.to_lowercase(),
+            filter_name: filter_name.to_string(),
+            actions: vec![],
+            cooldown_secs: None,
+            context: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_summary_counts_filters_and_sums_volume() {
+        let digest = AlertDigest::new(Duration::from_secs(900));
+        assert!(digest.holds("telegram", &AlertSeverity::Medium));
+        assert!(!digest.holds("telegram", &AlertSeverity::High));
+        assert!(!digest.holds("discord", &AlertSeverity::Low));
+        assert!(digest.take_summaries().is_empty());
+
+        for (signature, amount) in [("sigSmall", 1_000.0), ("sigLarge", 2_500_000.0)] {
+            digest.add(&transfer(signature, amount), &matched("Transfer"), &AlertSeverity::Low, vec!["telegram".to_string()]);
+        }
+
+        let summary = digest.take_summaries().remove("telegram").unwrap();
+        assert!(summary.contains("2 match(es) in the last 15 min"));
+        assert!(summary.contains("• Transfer (Low): 2"));
+        assert!(summary.contains("• 2.5M MINT"), "a transfer counts once: {}", summary);
+        assert!(summary.find("sigLarge").unwrap() < summary.find("sigSmall").unwrap());
+        assert!(digest.take_summaries().is_empty(), "entries are cleared once summarised");
+    }
+
+    #[test]
+    fn test_each_channel_only_gets_its_own_alerts() {
+        let digest = AlertDigest::new(Duration::from_secs(900));
+        let both = vec!["telegram".to_string(), "slack".to_string()];
+        digest.add(&transfer("sigBoth", 1_000.0), &matched("Shared"), &AlertSeverity::Low, both);
+        digest.add(&transfer("sigSlack", 5.0), &matched("SlackOnly"), &AlertSeverity::Medium, vec!["slack".to_string()]);
+
+        let summaries = digest.take_summaries();
+        assert_eq!(summaries.len(), 2);
+        let telegram = &summaries["telegram"];
+        assert!(telegram.contains("1 match(es)"), "{}", telegram);
+        assert!(telegram.contains("• Shared (Low): 1"));
+        assert!(telegram.contains("• 1k MINT"), "{}", telegram);
+        assert!(!telegram.contains("SlackOnly"));
+
+        let slack = &summaries["slack"];
+        assert!(slack.contains("2 match(es)"), "{}", slack);
+        assert!(slack.contains("• Shared (Low): 1"), "a two-channel alert counts once: {}", slack);
+        assert!(slack.contains("• SlackOnly (Medium): 1"));
+    }
+}
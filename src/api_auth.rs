@@ -0,0 +1,95 @@
+use anyhow::{bail, Result};
+use axum::extract::{Request, State};
+use axum::http::{header::AUTHORIZATION, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Bearer-token check for the HTTP API, which exposes stored transactions and control
+/// endpoints. Every request must carry `Authorization: Bearer <API_TOKEN>`; anything else gets
+/// a 401. Without a token the API is only allowed on localhost.
+#[derive(Debug, Clone, Default)]
+pub struct ApiAuth {
+    token: Option<String>,
+}
+
+impl ApiAuth {
+    pub fn new(token: Option<String>) -> Self {
+        Self { token: token.filter(|token| !token.trim().is_empty()) }
+    }
+
+    /// Reads `API_TOKEN`; unset or empty means no authentication
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("API_TOKEN").ok())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Whether an `Authorization` header value carries the configured token
+    pub fn authorize(&self, header: Option<&str>) -> bool {
+        let Some(expected) = &self.token else {
+            return true;
+        };
+        header
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.trim().as_bytes(), expected.as_bytes()))
+    }
+
+    /// Address for the API to listen on: localhost unless `public` was explicitly requested,
+    /// which in turn requires a token so the data is never served unauthenticated off-host
+    pub fn bind_address(&self, port: u16, public: bool) -> Result<SocketAddr> {
+        if !public {
+            if !self.is_enabled() {
+                warn!("API_TOKEN is not set; the HTTP API is unauthenticated (localhost only)");
+            }
+            return Ok(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port));
+        }
+        if !self.is_enabled() {
+            bail!("Binding the HTTP API publicly requires API_TOKEN to be set");
+        }
+        Ok(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port))
+    }
+}
+
+/// Axum middleware enforcing `ApiAuth` on every route, for
+/// `axum::middleware::from_fn_with_state(auth, require_token)`
+pub async fn require_token(State(auth): State<Arc<ApiAuth>>, request: Request, next: Next) -> Response {
+    let header = request.headers().get(AUTHORIZATION).and_then(|value| value.to_str().ok());
+    if !auth.authorize(header) {
+        return (StatusCode::UNAUTHORIZED, [("WWW-Authenticate", "Bearer")], "Unauthorized").into_response();
+    }
+    next.run(request).await
+}
+
+/// Compares without returning early on the first differing byte, so response timing doesn't
+/// reveal how much of the token a guess got right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_required_and_public_bind_needs_token() {
+        let auth = ApiAuth::new(Some("s3cret".to_string()));
+        assert!(auth.authorize(Some("Bearer s3cret")));
+        assert!(!auth.authorize(Some("Bearer s3cre")));
+        assert!(!auth.authorize(Some("s3cret")));
+        assert!(!auth.authorize(None));
+        assert_eq!(auth.bind_address(8080, false).unwrap().to_string(), "127.0.0.1:8080");
+        assert_eq!(auth.bind_address(8080, true).unwrap().to_string(), "0.0.0.0:8080");
+
+        let open = ApiAuth::new(Some(" ".to_string()));
+        assert!(open.authorize(None));
+        assert!(open.bind_address(8080, true).is_err());
+    }
+}
@@ -1,23 +1,136 @@
 use anyhow::{Result, Context};
 use clap::{Parser, Subcommand};
 use index_cli::{
-    filtered_monitor::{FilteredTransactionMonitor, save_filter_config, create_example_filter_config},
-    telegram_notifier::print_telegram_setup_instructions,
-    rpc_client_with_failover::RpcClientWithFailover,
-    concurrent_slot_processor::ConcurrentSlotProcessor,
+    filtered_monitor::{FilteredTransactionMonitor, TransactionStorage, save_filter_config, create_example_filter_config, export_collections, read_match_records},
+    filter_engine::FilterEngine,
+    filter_fixtures::{check_fixture, load_fixtures},
+    config_manager::ConfigManager,
+    config_watcher::ConfigWatcher,
+    filter_summary::{describe_condition, describe_filter, url_host},
+    transaction_extractor::{ExtractedTransaction, TransactionExtractor},
+    telegram_notifier::{print_telegram_setup_instructions, TelegramNotifier},
+    slack_notifier::SlackNotifier,
+    discord_notifier::DiscordNotifier,
+    email_notifier::EmailNotifier,
+    rpc_client_with_failover::{install_global_rpc_limit, install_rpc_call_budget, rpc_budget_exhausted, rpc_call_budget, RpcClientWithFailover},
+    concurrent_slot_processor::{ConcurrentSlotProcessor, SlotProcessingResult},
     slot_pre_filter::SlotPreFilter,
     selective_monitor::SelectiveMonitor,
     yu_focused_filter::YuFocusedFilter,
+    telemetry::init_tracing,
+    prefilter_audit::PrefilterAudit,
+    result_sink::GeyserFormatSink,
+    catchup_ledger::{CatchupLedger, DEFAULT_CHUNK_SIZE},
+    checkpoint::{configured_checkpoint_store, SlotCheckpoint},
+    geyser_source::{run_geyser_stream, GeyserConfig, IngestionBackend},
+    http_api::{self, HttpApiConfig},
+    storage_backend::configured_storage_backend,
 };
-use tracing::error;
+use tracing::{error, info, warn};
 use colored::*;
 use std::env;
-use std::time::Duration;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
-use serde::{Serialize, Deserialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::Serialize;
+use solana_commitment_config::CommitmentConfig;
+
+/// Exit code for `--fail-if-no-match` runs that found nothing (1 stays reserved for errors)
+const NO_MATCH_EXIT_CODE: u8 = 2;
+const DEFAULT_SLOT_AUDIT_FILE: &str = "slot_audit.jsonl";
+/// Attempts at a single slot in strict finalized mode before giving up (the checkpoint allows resuming)
+const MAX_SLOT_ATTEMPTS: u32 = 10;
+/// Largest --warmup-slots, about an hour of history; anything longer is a backfill (START_SLOT)
+const MAX_WARMUP_SLOTS: u64 = 9_000;
+const OPTIMIZATION_CONFIG_FILE: &str = "config/optimization.json";
+/// Programs nearly every token transaction touches; they say nothing about where a token trades
+const INFRASTRUCTURE_PROGRAMS: &[&str] = &[
+    "11111111111111111111111111111111",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+    "ComputeBudget111111111111111111111111111111",
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+    "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo",
+];
+
+/// Run-wide switches shared by every monitoring mode
+#[derive(Default)]
+struct RunOptions {
+    attribute_leader: bool,
+    /// Live modes stop at the first slot boundary after this
+    deadline: Option<Instant>,
+    /// Slot lists write each storage collection here as `<collection>.jsonl` when done
+    export_dir: Option<PathBuf>,
+    /// Write the exports as `<collection>.jsonl.gz`
+    export_gzip: bool,
+    /// Append every match here as a Geyser-style transaction notification (JSON lines)
+    geyser_out: Option<PathBuf>,
+    /// Live modes without a checkpoint start this many slots before the tip
+    warmup_slots: Option<u64>,
+    /// Catch-ups longer than this run as resumable chunks of this many slots
+    chunk_size: Option<u64>,
+    /// Live mode learns about new slots from a slotSubscribe WebSocket instead of polling
+    ws_slots: bool,
+    /// Live modes reload the config directory when it changes
+    watch_config: bool,
+    /// Set by the first Ctrl-C in live modes; they stop at the next slot boundary like `deadline`
+    interrupted: Arc<AtomicBool>,
+}
+
+impl RunOptions {
+    /// Live modes stop once the deadline passes, Ctrl-C was pressed or the RPC call budget is used up
+    fn time_is_up(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            || self.interrupted.load(Ordering::Relaxed)
+            || rpc_budget_exhausted()
+    }
+
+    /// Turn the first Ctrl-C into a clean stop (checkpoint, flush pending notifications); a
+    /// second one aborts right away
+    fn stop_on_ctrl_c(&self) {
+        let interrupted = self.interrupted.clone();
+        tokio::spawn(async move {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if interrupted.swap(true, Ordering::Relaxed) {
+                    std::process::exit(130);
+                }
+                println!("\n{}", "⏹️  Interrupted - stopping after the current slot (Ctrl-C again to abort)".bright_yellow());
+            }
+        });
+    }
+
+    /// Start the config watcher for --watch-config; dropping the watcher stops it
+    fn watch_config(&self, monitor: &Arc<FilteredTransactionMonitor>) -> Result<Option<ConfigWatcher>> {
+        if !self.watch_config {
+            return Ok(None);
+        }
+        if monitor.config_dir().is_none() {
+            println!("⚠️  --watch-config only applies to the config directory; a --filter-config file is not watched");
+            return Ok(None);
+        }
+        println!("👀 Config hot-reload: {}", "Enabled".bright_green());
+        Ok(Some(ConfigWatcher::spawn(monitor.clone())?))
+    }
+
+    fn apply(&self, monitor: FilteredTransactionMonitor, rpc_url: &str) -> Result<FilteredTransactionMonitor> {
+        let mut monitor = monitor;
+        if self.attribute_leader {
+            println!("🏷️  Slot leader attribution: {}", "Enabled".bright_green());
+            monitor = monitor.with_leader_attribution(rpc_url.to_string());
+        }
+        if let Some(path) = &self.geyser_out {
+            println!("📡 Geyser-format output: {}", path.display().to_string().bright_green());
+            monitor = monitor.with_sink(Arc::new(GeyserFormatSink::create(path)?));
+        }
+        Ok(monitor)
+    }
+}
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -33,6 +146,28 @@ struct Cli {
     #[clap(short, long, env = "SOLANA_RPC_URL")]
     rpc_url: Option<String>,
 
+    /// Fail on configuration problems (e.g. duplicate filter ids) instead of warning
+    #[clap(long)]
+    strict: bool,
+
+    /// Slot lists and `test`: exit with code 2 when no transaction matched (live modes ignore this)
+    #[clap(long, global = true)]
+    fail_if_no_match: bool,
+
+    /// Stop cleanly (saving the checkpoint) once this many RPC calls were made, e.g. to keep a
+    /// large backfill within an RPC quota
+    #[clap(long, value_name = "N", global = true)]
+    max_rpc_calls: Option<u64>,
+
+    /// More log output from the monitor itself: -v for debug, -vv for trace
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Extra log filter directive, e.g. `index_cli::slot_pre_filter=warn` (repeatable; applied
+    /// after RUST_LOG and -v)
+    #[clap(long = "log", value_name = "DIRECTIVE", global = true)]
+    log_directives: Vec<String>,
+
     /// Slots to monitor (when no subcommand is provided)
     slots: Option<String>,
 }
@@ -45,6 +180,64 @@ enum Commands {
         /// If not provided, will use HACK_SLOT from environment
         /// If HACK_SLOT not set, will monitor live slots
         slots: Option<String>,
+
+        /// Process the slot list newest-first (explicit slot lists only; live mode ignores this)
+        #[clap(long)]
+        reverse: bool,
+
+        /// Warn about unparseable slot tokens and continue with the valid ones instead of failing
+        #[clap(long)]
+        skip_invalid_slots: bool,
+
+        /// Live mode: process finalized slots one at a time in order, recording every slot as
+        /// processed or skipped in an audit trail (SLOT_AUDIT_FILE). Slower, but gap-free.
+        #[clap(long)]
+        strict_finalized: bool,
+
+        /// Look up the validator that produced each slot with matches and include it in
+        /// alerts and stored transactions (one getSlotLeaders call covers 500 slots)
+        #[clap(long)]
+        attribute_leader: bool,
+
+        /// Live mode: stop after this many seconds, saving the checkpoint and printing a summary
+        #[clap(long, value_name = "SECS")]
+        duration: Option<u64>,
+
+        /// Live mode without a checkpoint: start N slots before the current slot (750 is about
+        /// 5 minutes) to catch recent activity before going live. Takes precedence over
+        /// START_SLOT; at most 9000.
+        #[clap(long, value_name = "N", conflicts_with = "slots", value_parser = clap::value_parser!(u64).range(1..=MAX_WARMUP_SLOTS))]
+        warmup_slots: Option<u64>,
+
+        /// Live mode: catch-ups longer than this many slots (default 10000) are split into chunks
+        /// of this size, tracked in a ledger (CATCHUP_LEDGER_FILE) with each chunk's progress
+        /// and failed slots, so a restart resumes mid-catch-up at the exact next slot
+        #[clap(long, value_name = "SLOTS", conflicts_with = "slots", value_parser = clap::value_parser!(u64).range(1..))]
+        chunk_size: Option<u64>,
+
+        /// Slot lists: when done, write each storage collection to <DIR>/<collection>.jsonl
+        #[clap(long, value_name = "DIR")]
+        export_dir: Option<PathBuf>,
+
+        /// Gzip the --export-dir files (<collection>.jsonl.gz)
+        #[clap(long, requires = "export_dir")]
+        export_gzip: bool,
+
+        /// Append every match to this file as a Geyser-style transaction notification (JSON
+        /// lines), for consumers built for Geyser plugin output
+        #[clap(long, value_name = "FILE")]
+        geyser_out: Option<PathBuf>,
+
+        /// Live mode: get new slots pushed over a slotSubscribe WebSocket (SOLANA_WS_URL, or the
+        /// RPC URL with ws/wss) instead of polling getSlot; falls back to polling while the socket
+        /// is down
+        #[clap(long, conflicts_with_all = ["slots", "strict_finalized"])]
+        ws_slots: bool,
+
+        /// Live modes: reload config/monitors and config/alerts when they change, without
+        /// restarting or losing the checkpoint
+        #[clap(long, conflicts_with = "slots")]
+        watch_config: bool,
     },
 
     /// Generate example filter configuration
@@ -57,59 +250,209 @@ enum Commands {
     /// Show Telegram setup instructions
     TelegramSetup,
 
+    /// Send a test message through one notification channel and report the result
+    TestNotify {
+        /// Channel to test: telegram, slack, discord or email
+        channel: String,
+
+        /// Message text
+        #[clap(default_value = "🧪 Test notification from the Solana filtered monitor")]
+        message: String,
+    },
+
     /// Test filters with a specific slot
     Test {
         /// Slot to test
         slot: u64,
     },
+
+    /// Show progress of a running monitor from its checkpoint (read-only)
+    Status,
+
+    /// Re-run one slot with debug tracing and per-transaction, per-filter diagnostics
+    Debug {
+        /// Slot to replay
+        slot: u64,
+
+        /// Show every transaction, not just those where at least one condition matched
+        #[clap(long)]
+        all: bool,
+    },
+
+    /// Rank the programs that appear alongside a mint, to seed `monitored_programs` in
+    /// config/optimization.json
+    Discover {
+        /// Slots to scan (comma-separated slots or inclusive ranges like 100-200)
+        slots: String,
+
+        /// Mint to look for (defaults to YU_TOKEN_ADDRESS, then the YU mint)
+        #[clap(long)]
+        mint: Option<String>,
+
+        /// Number of programs to list
+        #[clap(long, default_value = "20")]
+        top: usize,
+    },
+
+    /// Time getBlock for one slot against every configured RPC URL separately (no failover) and
+    /// report min/median/p95 latency and error rate, to rank and weight endpoints
+    BenchRpc {
+        /// Slot to fetch; pick a recent, busy one
+        slot: u64,
+
+        /// Calls per endpoint
+        #[clap(long, default_value = "10")]
+        iterations: usize,
+    },
+
+    /// Check the filters against fixture transactions: every `*.json` in the directory holds a
+    /// `transaction` and the `expected` filter ids it must match, no more and no less. Exits
+    /// non-zero on any mismatch.
+    TestFilters {
+        /// Directory of fixture files
+        #[clap(default_value = "config/fixtures")]
+        fixtures_dir: String,
+    },
+
+    /// Load historical matches from a JSON-lines export (stored or bare transactions, `.gz`
+    /// allowed) into a storage collection
+    Import {
+        /// File to read, e.g. an earlier `--export-dir` output
+        file: String,
+
+        /// Collection the records are stored under
+        #[clap(long)]
+        collection: String,
+    },
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into())
-        )
-        .init();
-
+async fn main() -> Result<ExitCode> {
     // Load environment variables
     dotenv::dotenv().ok();
 
     let cli = Cli::parse();
 
-    match cli.command {
-        Some(Commands::Monitor { slots }) => {
-            monitor_slots(slots, cli.filter_config, cli.rpc_url).await?;
+    // Held until main returns so batched OTLP spans are flushed on exit
+    let _telemetry = init_tracing(build_env_filter(&cli)?)?;
+
+    // Installed before any RPC client exists, so every stage shares the one limit
+    if let Some(limit) = global_rpc_concurrency_from_env()? {
+        install_global_rpc_limit(limit);
+        info!("Global RPC concurrency limited to {} in-flight calls", limit);
+    }
+    if let Some(max_calls) = cli.max_rpc_calls {
+        install_rpc_call_budget(max_calls);
+        info!("RPC calls capped at {} for this run", max_calls);
+    }
+    #[cfg(unix)]
+    reload_rpc_urls_on_sighup();
+
+    // Matches found by runs with a definite end (slot lists, test); None for everything else
+    let matches = match cli.command {
+        Some(Commands::Monitor { slots, reverse, skip_invalid_slots, strict_finalized, attribute_leader, duration, warmup_slots, chunk_size, export_dir, export_gzip, geyser_out, ws_slots, watch_config }) => {
+            let options = RunOptions {
+                attribute_leader,
+                deadline: duration.map(|secs| Instant::now() + Duration::from_secs(secs)),
+                export_dir,
+                export_gzip,
+                geyser_out,
+                warmup_slots,
+                chunk_size,
+                ws_slots,
+                watch_config,
+                ..RunOptions::default()
+            };
+            monitor_slots(
+                slots,
+                cli.filter_config,
+                cli.rpc_url,
+                cli.strict,
+                reverse,
+                skip_invalid_slots,
+                strict_finalized,
+                &options,
+            ).await?
         },
 
         Some(Commands::GenerateConfig { output }) => {
             generate_config(&output)?;
+            None
+        },
+
+        Some(Commands::TestNotify { channel, message }) => {
+            test_notify(&channel, &message).await?;
+            None
         },
 
         Some(Commands::TelegramSetup) => {
             print_telegram_setup_instructions();
+            None
         },
 
         Some(Commands::Test { slot }) => {
-            test_slot(slot, cli.filter_config, cli.rpc_url).await?;
+            let matches = test_slot(slot, cli.filter_config, cli.rpc_url, cli.strict).await?;
+            if matches.is_none() && cli.fail_if_no_match {
+                // Not the same as "no matches": the slot was never checked
+                return Err(anyhow::anyhow!("Slot {} could not be tested", slot));
+            }
+            matches
+        },
+
+        Some(Commands::Status) => {
+            show_status(cli.rpc_url).await?;
+            None
+        },
+
+        Some(Commands::Debug { slot, all }) => {
+            debug_slot(slot, all, cli.filter_config, cli.rpc_url, cli.strict).await?;
+            None
+        },
+
+        Some(Commands::Discover { slots, mint, top }) => {
+            discover_programs(&slots, mint, top, cli.rpc_url).await?;
+            None
+        },
+
+        Some(Commands::BenchRpc { slot, iterations }) => {
+            bench_rpc(slot, iterations, cli.rpc_url).await?;
+            None
+        },
+
+        Some(Commands::TestFilters { fixtures_dir }) => {
+            test_filters(&fixtures_dir, cli.filter_config, cli.strict)?;
+            None
+        },
+
+        Some(Commands::Import { file, collection }) => {
+            import_matches(&file, &collection).await?;
+            None
         },
 
         None => {
             // Default to monitor command with provided slots or live monitoring
-            monitor_slots(cli.slots, cli.filter_config, cli.rpc_url).await?;
+            monitor_slots(cli.slots, cli.filter_config, cli.rpc_url, cli.strict, false, false, false, &RunOptions::default()).await?
         },
+    };
+
+    if cli.fail_if_no_match && matches == Some(0) {
+        println!("{}", format!("❌ No matches found - exiting with code {}", NO_MATCH_EXIT_CODE).bright_red());
+        return Ok(ExitCode::from(NO_MATCH_EXIT_CODE));
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 async fn monitor_slots(
     slots_opt: Option<String>,
     filter_config: Option<String>,
     rpc_url: Option<String>,
-) -> Result<()> {
+    strict: bool,
+    reverse: bool,
+    skip_invalid_slots: bool,
+    strict_finalized: bool,
+    options: &RunOptions,
+) -> Result<Option<usize>> {
     println!("{}", "🔍 Solana Transaction Monitor with Filters".bright_cyan().bold());
     println!("{}", "==========================================".bright_cyan());
 
@@ -130,14 +473,77 @@ async fn monitor_slots(
     match slots_to_monitor {
         Some(slots_str) => {
             // Monitor specific slots
-            monitor_specific_slots(slots_str, filter_config, rpc_url, use_config_dir).await
+            if strict_finalized {
+                println!("⚠️  --strict-finalized only applies to live monitoring; explicit slot lists are processed as given");
+            }
+            monitor_specific_slots(slots_str, filter_config, rpc_url, use_config_dir, strict, reverse, skip_invalid_slots, options)
+                .await
+                .map(Some)
         },
         None => {
             // Monitor live slots
+            if reverse {
+                println!("⚠️  --reverse only applies to explicit slot lists; live monitoring always runs oldest-first");
+            }
+            if let Some(deadline) = options.deadline {
+                println!("⏱️  Stopping after {}s", deadline.saturating_duration_since(Instant::now()).as_secs_f64().round());
+            }
+            options.stop_on_ctrl_c();
+            if strict_finalized {
+                println!("🔒 Starting strict finalized monitoring...");
+                monitor_finalized_slots(filter_config, rpc_url, use_config_dir, strict, options).await?;
+                return Ok(None);
+            }
+            if IngestionBackend::from_env()? == IngestionBackend::Geyser {
+                if options.ws_slots {
+                    println!("⚠️  --ws-slots only applies to the RPC backend; the Geyser stream pushes transactions itself");
+                }
+                println!("🌊 Starting Geyser stream monitoring...");
+                monitor_geyser_stream(filter_config, rpc_url, use_config_dir, strict, options).await?;
+                return Ok(None);
+            }
             println!("📡 Starting live slot monitoring...");
-            monitor_live_slots(filter_config, rpc_url, use_config_dir).await
+            monitor_live_slots(filter_config, rpc_url, use_config_dir, strict, options).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Longest range a slot list entry may expand to; anything longer is almost certainly a typo
+const MAX_SLOT_RANGE_LEN: u64 = 100_000;
+
+/// Parse a comma-separated slot list where each entry is a slot or an inclusive range like
+/// `100-200` of at most `MAX_SLOT_RANGE_LEN` slots. Returns the parsed slots along with a
+/// description of every entry that failed.
+fn parse_slot_list(input: &str) -> (Vec<u64>, Vec<String>) {
+    let mut slots = Vec::new();
+    let mut errors = Vec::new();
+
+    for token in input.split(',').map(str::trim) {
+        // Tolerate trailing/double commas
+        if token.is_empty() {
+            continue;
+        }
+
+        match token.split_once('-') {
+            Some((start, end)) => match (start.trim().parse::<u64>(), end.trim().parse::<u64>()) {
+                (Ok(start), Ok(end)) if start > end => {
+                    errors.push(format!("'{}': range start {} is after end {}", token, start, end));
+                },
+                (Ok(start), Ok(end)) if end - start >= MAX_SLOT_RANGE_LEN => {
+                    errors.push(format!("'{}': range covers more than {} slots", token, MAX_SLOT_RANGE_LEN));
+                },
+                (Ok(start), Ok(end)) => slots.extend(start..=end),
+                _ => errors.push(format!("'{}': not a valid slot range", token)),
+            },
+            None => match token.parse::<u64>() {
+                Ok(slot) => slots.push(slot),
+                Err(_) => errors.push(format!("'{}': not a valid slot", token)),
+            },
         }
     }
+
+    (slots, errors)
 }
 
 async fn monitor_specific_slots(
@@ -145,22 +551,44 @@ async fn monitor_specific_slots(
     filter_config: Option<String>,
     rpc_url: String,
     use_config_dir: bool,
-) -> Result<()> {
+    strict: bool,
+    reverse: bool,
+    skip_invalid_slots: bool,
+    options: &RunOptions,
+) -> Result<usize> {
     // Parse slots
-    let slots: Vec<u64> = if slots_str.starts_with('[') {
+    let mut slots: Vec<u64> = if slots_str.trim_start().starts_with('[') {
         serde_json::from_str(&slots_str).context("Failed to parse slots JSON")?
     } else {
-        slots_str
-            .split(',')
-            .filter_map(|s| s.trim().parse().ok())
-            .collect()
+        let (slots, errors) = parse_slot_list(&slots_str);
+        if !errors.is_empty() {
+            if !skip_invalid_slots {
+                return Err(anyhow::anyhow!(
+                    "Invalid slot entries: {} (use --skip-invalid-slots to continue without them)",
+                    errors.join("; ")
+                ));
+            }
+            for error in &errors {
+                println!("⚠️  Skipping {}", error);
+            }
+        }
+        slots
     };
 
     if slots.is_empty() {
         return Err(anyhow::anyhow!("No valid slots provided"));
     }
 
-    println!("📊 Monitoring {} slots", slots.len());
+    // Results are matched back to their position by slot, so each slot is processed once
+    let mut seen = HashSet::new();
+    slots.retain(|slot| seen.insert(*slot));
+
+    // Newest-first gets the most recent slots scheduled first (no checkpoint is kept for slot lists)
+    if reverse {
+        slots.sort_unstable_by(|a, b| b.cmp(a));
+    }
+
+    println!("📊 Monitoring {} slots{}", slots.len(), if reverse { " (newest first)" } else { "" });
     println!("🌐 RPC: {}", rpc_url.bright_blue());
 
     // Show filter config status
@@ -187,38 +615,91 @@ async fn monitor_specific_slots(
 
     // Create monitor
     let monitor = if use_config_dir {
-        FilteredTransactionMonitor::from_config_dir(rpc_url, "config").await?
+        FilteredTransactionMonitor::from_config_dir(rpc_url.clone(), "config", strict).await?
     } else {
-        FilteredTransactionMonitor::new(rpc_url, filter_config).await?
+        FilteredTransactionMonitor::new(rpc_url.clone(), filter_config, strict).await?
     };
+    let monitor = options.apply(monitor, &rpc_url)?;
+    print_active_filters(&monitor.filter_engine());
+    let monitor = Arc::new(monitor);
+
+    // Get max concurrent slots from env
+    let max_concurrent = env::var("MAX_CONCURRENT_SLOTS")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(20);
+
+    println!("🔧 Max concurrent slots: {}", max_concurrent);
+
+    // Catch-up checkpoints once per batch, so the catch-up interval is also the batch size
+    let catchup_interval = checkpoint_interval_from_env("CHECKPOINT_INTERVAL_CATCHUP", 500)?;
+    let live_interval = checkpoint_interval_from_env("CHECKPOINT_INTERVAL_LIVE", 10)?;
+    println!("🔧 Checkpoint every {} slots catching up, {} slots live", catchup_interval, live_interval);
+
+    let concurrent_processor = ConcurrentSlotProcessor::new(
+        monitor.clone(),
+        rpc_url,
+        Some(max_concurrent),
+    );
 
     let mut total_matched = 0;
     let mut total_scanned = 0;
 
-    // Process each slot
-    for slot in slots {
-        println!("⚙️  Processing slot {}...", slot);
+    let positions: HashMap<u64, usize> = slots.iter()
+        .enumerate()
+        .map(|(idx, &slot)| (slot, idx))
+        .collect();
 
-        match monitor.monitor_slot(slot).await {
-            Ok(matched_transactions) => {
-                let matched_count = matched_transactions.len();
-                println!("  ✅ Found {} matching transactions", matched_count.to_string().bright_green());
-
-                total_matched += matched_count;
-                total_scanned += 1;
-
-                // Show matched transactions
-                for tx in &matched_transactions {
-                    println!("    📌 {} - Filters: {}",
-                             &tx.transaction.signature[..20],
-                             tx.matched_filters.join(", ").bright_yellow()
-                    );
+    // Results stream in as slots complete; each one waits here until every earlier slot is
+    // printed, so output keeps the requested order
+    let (results_tx, mut results_rx) = tokio::sync::mpsc::channel::<SlotProcessingResult>(100);
+    let processing = concurrent_processor.process_slot_list_streaming(slots, results_tx);
+    tokio::pin!(processing);
+
+    let mut pending: BTreeMap<usize, SlotProcessingResult> = BTreeMap::new();
+    let mut next_position = 0;
+    let mut processing_done = false;
+    let mut interrupted = false;
+
+    loop {
+        tokio::select! {
+            received = results_rx.recv() => {
+                let Some(result) = received else { break };
+                let position = positions.get(&result.slot).copied().unwrap_or(usize::MAX);
+                pending.insert(position, result);
+
+                while let Some(result) = pending.remove(&next_position) {
+                    if let Some(matched_count) = print_slot_result(&result) {
+                        total_matched += matched_count;
+                        total_scanned += 1;
+                    }
+                    next_position += 1;
                 }
-            },
-            Err(e) => {
-                println!("  ❌ Error: {}", e.to_string().bright_red());
-                error!("Failed to monitor slot {}: {}", slot, e);
             }
+            finished = &mut processing, if !processing_done => {
+                finished?;
+                processing_done = true;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                if interrupted {
+                    std::process::exit(130);
+                }
+                interrupted = true;
+                println!("\n{}", "⏹️  Interrupted - finishing in-flight slots (Ctrl-C again to abort)".bright_yellow());
+                results_rx.close();
+            }
+        }
+    }
+
+    if !processing_done {
+        processing.await?;
+    }
+
+    // After an interrupt, slots that completed ahead of an unfinished one are still buffered
+    for result in pending.into_values() {
+        if let Some(matched_count) = print_slot_result(&result) {
+            total_matched += matched_count;
+            total_scanned += 1;
         }
     }
 
@@ -227,6 +708,10 @@ async fn monitor_specific_slots(
     println!("Slots processed: {}", total_scanned);
     println!("Total matches: {}", total_matched.to_string().bright_green());
 
+    if let Err(e) = monitor.filter_engine().save_state() {
+        error!("Failed to save filter state: {}", e);
+    }
+
     // Show storage summary
     let storage_summary = monitor.get_storage_summary().await;
     if !storage_summary.is_empty() {
@@ -236,16 +721,53 @@ async fn monitor_specific_slots(
         }
     }
 
-    Ok(())
+    if let Some(dir) = &options.export_dir {
+        let files = export_collections(&monitor, dir, options.export_gzip).await?;
+        println!("📤 Exported {} collection(s) to {}", files, dir.display().to_string().bright_green());
+    }
+
+    monitor.flush_notifications(notification_flush_timeout()).await;
+    print_budget_stop();
+    print_rpc_stats(&monitor);
+
+    Ok(total_matched)
+}
+
+/// Prints one slot's outcome; returns the number of matches if the slot was processed
+fn print_slot_result(result: &SlotProcessingResult) -> Option<usize> {
+    println!("⚙️  Slot {}...", result.slot);
+
+    if result.success {
+        let matched_count = result.matched_transactions.len();
+        println!("  ✅ Found {} matching transactions", matched_count.to_string().bright_green());
+        if let Some(leader) = result.matched_transactions.first().and_then(|tx| tx.slot_leader.as_ref()) {
+            println!("  🏷️  Leader: {}", leader);
+        }
+
+        // Show matched transactions
+        for tx in &result.matched_transactions {
+            println!("    📌 {} - Filters: {}",
+                     &tx.transaction.signature[..20],
+                     tx.matched_filters.join(", ").bright_yellow()
+            );
+        }
+
+        Some(matched_count)
+    } else {
+        let error_message = result.error.clone().unwrap_or_else(|| "Unknown error".to_string());
+        println!("  ❌ Error: {}", error_message.bright_red());
+        error!("Failed to monitor slot {}: {}", result.slot, error_message);
+        None
+    }
 }
 
 async fn monitor_live_slots(
     filter_config: Option<String>,
     rpc_url: String,
     use_config_dir: bool,
+    strict: bool,
+    options: &RunOptions,
 ) -> Result<()> {
-    const CHECKPOINT_FILE: &str = "slot_checkpoint.json";
-
     println!("🌐 RPC: {}", rpc_url.bright_blue());
 
     // Show filter config status
@@ -271,28 +793,53 @@ async fn monitor_live_slots(
     println!();
 
     // Create RPC client with failover to get current slot
-    let rpc_client = Arc::new(RpcClientWithFailover::new(rpc_url.clone()));
+    let mut rpc_client = RpcClientWithFailover::new(rpc_url.clone());
+    if options.ws_slots {
+        let ws_url = slot_subscription_url(&rpc_url);
+        println!("🔌 Slot subscription: {}", ws_url.bright_blue());
+        rpc_client = rpc_client.with_slot_subscription(ws_url);
+    }
+    let rpc_client = Arc::new(rpc_client);
 
     // Create monitor
     let monitor = if use_config_dir {
-        FilteredTransactionMonitor::from_config_dir(rpc_url.clone(), "config").await?
+        FilteredTransactionMonitor::from_config_dir(rpc_url.clone(), "config", strict).await?
     } else {
-        FilteredTransactionMonitor::new(rpc_url.clone(), filter_config).await?
+        FilteredTransactionMonitor::new(rpc_url.clone(), filter_config, strict).await?
     };
+    let monitor = options.apply(monitor, &rpc_url)?;
+    print_active_filters(&monitor.filter_engine());
 
     let mut total_matched = 0;
     let mut total_scanned = 0;
     let mut consecutive_errors = 0;
 
-    // Check for existing checkpoint
-    let checkpoint = SlotCheckpoint::load(CHECKPOINT_FILE)?;
-    let start_slot = if let Some(ref cp) = checkpoint {
+    // Check for existing checkpoint; an unfinished chunked catch-up resumes from its ledger
+    let checkpoint_store = configured_checkpoint_store().await?;
+    let checkpoint = checkpoint_store.load().await?;
+    let chunk_size = options.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+    let mut ledger = CatchupLedger::load(catchup_ledger_path())?;
+    let start_slot = if let Some(next_slot) = ledger.as_ref().and_then(CatchupLedger::next_slot) {
+        let ledger = ledger.as_ref().unwrap();
+        println!("📦 Resuming chunked catch-up at slot {} (chunk {}/{}, up to slot {})",
+                 next_slot,
+                 ledger.current().map_or(0, |index| index + 1),
+                 ledger.chunks.len(),
+                 ledger.end_slot().unwrap_or(next_slot)
+        );
+        next_slot
+    } else if let Some(ref cp) = checkpoint {
         println!("📂 Found checkpoint from slot {} (processed {} slots, {} matches)",
                  cp.last_processed_slot,
                  cp.total_slots_processed,
                  cp.total_matches_found
         );
         cp.last_processed_slot + 1
+    } else if let Some(warmup) = options.warmup_slots {
+        let current = rpc_client.get_slot().await?;
+        let slot = current.saturating_sub(warmup);
+        println!("🔥 Warming up from slot {} ({} slots before current slot {})", slot, warmup, current);
+        slot
     } else if let Ok(start_slot_str) = env::var("START_SLOT") {
         let slot = start_slot_str.trim().parse::<u64>()
             .context("Invalid START_SLOT value")?;
@@ -310,10 +857,25 @@ async fn monitor_live_slots(
         total_scanned = cp.total_slots_processed;
     }
 
+    // Chunks whose range was finished before a restart may still have failed slots to retry
+    if let Some(current) = ledger.as_mut() {
+        let finished: Vec<usize> = (0..current.chunks.len())
+            .filter(|&index| current.chunks[index].next_slot > current.chunks[index].end)
+            .collect();
+        for index in finished {
+            total_matched += retry_failed_chunk_slots(current, index, &monitor).await;
+        }
+        finish_or_save_ledger(&mut ledger);
+    }
+
     println!("Press Ctrl+C to stop\n");
 
     let mut current_slot = start_slot;
     let monitor_arc = Arc::new(monitor);
+    let silence_watchdog = spawn_silence_watchdog(&monitor_arc)?;
+    let digest_scheduler = monitor_arc.spawn_digest_scheduler();
+    let _config_watcher = options.watch_config(&monitor_arc)?;
+    let http_api = spawn_http_api(&monitor_arc).await?;
 
     // Get max concurrent slots from env
     let max_concurrent = env::var("MAX_CONCURRENT_SLOTS")
@@ -374,8 +936,8 @@ async fn monitor_live_slots(
     };
 
     // Create pre-filter if optimization config exists (fallback)
-    let pre_filter = if yu_filter.is_none() && selective_monitor.is_none() && Path::new("config/optimization.json").exists() {
-        match SlotPreFilter::from_config_file(rpc_url.clone(), "config/optimization.json") {
+    let pre_filter = if yu_filter.is_none() && selective_monitor.is_none() && Path::new(OPTIMIZATION_CONFIG_FILE).exists() {
+        match SlotPreFilter::from_config_file(rpc_url.clone(), OPTIMIZATION_CONFIG_FILE) {
             Ok(filter) => {
                 println!("✅ Pre-filtering enabled - will skip irrelevant slots");
                 Some(Arc::new(filter))
@@ -389,7 +951,23 @@ async fn monitor_live_slots(
         None
     };
 
-    loop {
+    // Re-check a sample of the slots the pre-filters skip (PREFILTER_AUDIT_SAMPLE_RATE)
+    let prefilter_audit = if yu_filter.is_some() || selective_monitor.is_some() || pre_filter.is_some() {
+        PrefilterAudit::from_env()?
+    } else {
+        None
+    };
+    if let Some(audit) = &prefilter_audit {
+        println!("🔎 Pre-filter audit: re-checking {:.1}% of skipped slots", audit.sample_rate() * 100.0);
+    }
+
+    // Announce the switch from catch-up to live once (CATCHUP_DONE_NOTIFICATION=false to turn off)
+    let mut notify_catchup_done = env::var("CATCHUP_DONE_NOTIFICATION")
+        .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false" | "no"))
+        .unwrap_or(true);
+    let mut caught_up = false;
+
+    while !options.time_is_up() {
         // Get the latest slot from RPC
         let latest_slot = match rpc_client.get_slot().await {
             Ok(slot) => slot,
@@ -407,14 +985,50 @@ async fn monitor_live_slots(
         };
 
         consecutive_errors = 0;
+        monitor_arc.record_chain_tip(latest_slot);
 
         // Check if we're catching up or monitoring live
         let slots_behind = latest_slot.saturating_sub(current_slot);
         let is_catching_up = slots_behind > 10;
 
         if is_catching_up {
-            // Process slots in batches when catching up
-            let batch_size = std::cmp::min(slots_behind, 500);
+            caught_up = false;
+        } else if !caught_up {
+            caught_up = true;
+            if notify_catchup_done && current_slot > start_slot {
+                let message = format!(
+                    "✅ Caught up to slot {} after {} slots, now live",
+                    current_slot - 1,
+                    current_slot - start_slot
+                );
+                println!("{}", message.bright_green());
+                monitor_arc.send_status_message(&message).await;
+            }
+            // Only the first catch-up after startup is announced
+            notify_catchup_done = false;
+        }
+
+        if is_catching_up {
+            if ledger.is_none() && slots_behind > chunk_size {
+                let planned = CatchupLedger::plan(catchup_ledger_path(), current_slot, latest_slot, chunk_size);
+                println!("📦 Catching up {} slots in {} chunks of {} (ledger: {})",
+                         slots_behind,
+                         planned.chunks.len(),
+                         chunk_size,
+                         catchup_ledger_path()
+                );
+                if let Err(e) = planned.save() {
+                    error!("Failed to save catch-up ledger: {:#}", e);
+                }
+                ledger = Some(planned);
+            }
+
+            // Process slots in batches when catching up; batches never straddle two chunks
+            let mut batch_size = std::cmp::min(slots_behind, catchup_interval);
+            if let Some(chunk_end) = ledger.as_ref().and_then(|ledger| ledger.chunk_end(current_slot)) {
+                batch_size = batch_size.min(chunk_end - current_slot + 1);
+            }
+            let batch_start = current_slot;
             let end_slot = current_slot + batch_size - 1;
 
             let slots_to_process: Vec<u64> = (current_slot..=end_slot).collect();
@@ -496,15 +1110,21 @@ async fn monitor_live_slots(
             if slots_to_process.is_empty() {
                 // No relevant slots in this batch, skip ahead
                 println!("  ⏩ Skipping batch - no relevant transactions");
+                monitor_arc.mark_processed();
                 current_slot = end_slot + 1;
 
                 // Important: Update checkpoint even when skipping
                 total_scanned += batch_size as u64;
                 let checkpoint = SlotCheckpoint::new(end_slot, total_scanned, total_matched);
-                if let Err(e) = checkpoint.save(CHECKPOINT_FILE) {
+                if let Err(e) = monitor_arc.filter_engine().save_state() {
+                    error!("Failed to save filter state: {}", e);
+                }
+                if let Err(e) = checkpoint_store.save(&checkpoint).await {
                     error!("Failed to save checkpoint: {}", e);
                 } else {
+                    total_matched += advance_catchup_ledger(&mut ledger, &monitor_arc, current_slot, &[]).await;
                     let new_latest = rpc_client.get_slot().await.unwrap_or(latest_slot);
+                    monitor_arc.record_chain_tip(new_latest);
                     let new_slots_behind = new_latest.saturating_sub(current_slot);
                     println!("\n💾 Checkpoint saved at slot {} (catching up: {} slots behind)",
                              end_slot,
@@ -516,6 +1136,8 @@ async fn monitor_live_slots(
                     );
                     println!("⏱️  Current slot: {}, Latest slot: {}\n", current_slot, new_latest);
                 }
+                let skipped: Vec<u64> = (batch_start..=end_slot).collect();
+                audit_skipped_slots(prefilter_audit.as_ref(), &monitor_arc, &skipped).await;
                 continue;
             }
 
@@ -534,7 +1156,22 @@ async fn monitor_live_slots(
                     let mut batch_matched = 0;
                     let mut batch_processed = 0;
 
+                    // Out of RPC budget mid-batch: slots from the first one that didn't complete
+                    // are left to the next run instead of being checkpointed past
+                    let resume_slot = if rpc_budget_exhausted() {
+                        results.iter().filter(|result| !result.success).map(|result| result.slot).min()
+                    } else {
+                        None
+                    };
+
+                    let mut failed_slots = Vec::new();
                     for result in &results {
+                        if resume_slot.is_some_and(|slot| result.slot >= slot) {
+                            continue;
+                        }
+                        if !result.success {
+                            failed_slots.push(result.slot);
+                        }
                         if result.success {
                             batch_processed += 1;
                             let matched_count = result.matched_transactions.len();
@@ -563,25 +1200,31 @@ async fn monitor_live_slots(
                     }
 
                     // Update current slot
-                    current_slot = end_slot + 1;
+                    current_slot = resume_slot.unwrap_or(end_slot + 1);
 
                     // Save checkpoint after batch
-                    let checkpoint = SlotCheckpoint::new(end_slot, total_scanned, total_matched);
-                    if let Err(e) = checkpoint.save(CHECKPOINT_FILE) {
+                    let checkpoint = SlotCheckpoint::new(current_slot - 1, total_scanned, total_matched);
+                    if let Err(e) = monitor_arc.filter_engine().save_state() {
+                        error!("Failed to save filter state: {}", e);
+                    }
+                    if let Err(e) = checkpoint_store.save(&checkpoint).await {
                         error!("Failed to save checkpoint: {}", e);
                     } else {
+                        total_matched += advance_catchup_ledger(&mut ledger, &monitor_arc, current_slot, &failed_slots).await;
                         println!("\n💾 Checkpoint saved at slot {} (catching up: {} slots behind)",
-                                 end_slot,
+                                 current_slot - 1,
                                  latest_slot.saturating_sub(current_slot).to_string().bright_yellow()
                         );
                         println!("📊 Batch summary: {} slots processed, {} matches found",
                                  batch_processed,
                                  batch_matched.to_string().bright_green()
                         );
-                        println!("📊 Total progress: {} slots scanned, {} matches found\n",
+                        println!("📊 Total progress: {} slots scanned, {} matches found",
                                  total_scanned,
                                  total_matched.to_string().bright_green()
                         );
+                        print_rpc_stats(&monitor_arc);
+                        println!();
 
                         // Update selective monitor with activity data if matches found
                         if let (Some(selective_monitor), true) = (&selective_monitor, batch_matched > 0) {
@@ -607,11 +1250,21 @@ async fn monitor_live_slots(
                     error!("Failed to process batch: {}", e);
                     // Fall back to sequential processing
                     current_slot = end_slot + 1;
+                    // A chunked catch-up retries the whole batch once its chunk is done
+                    let failed_slots: Vec<u64> = (batch_start..=end_slot).collect();
+                    total_matched += advance_catchup_ledger(&mut ledger, &monitor_arc, current_slot, &failed_slots).await;
                 }
             }
+
+            // Slots between the first and last relevant one were processed anyway
+            let skipped: Vec<u64> = (batch_start..start).chain(end + 1..=end_slot).collect();
+            audit_skipped_slots(prefilter_audit.as_ref(), &monitor_arc, &skipped).await;
         } else {
+            // The tail of a chunked catch-up can end up processed here
+            total_matched += advance_catchup_ledger(&mut ledger, &monitor_arc, current_slot, &[]).await;
+
             // Process slots individually when monitoring live
-            while current_slot <= latest_slot {
+            while current_slot <= latest_slot && !options.time_is_up() {
                 println!("⚡ Monitoring slot {} (live mode)...", current_slot);
 
                 match monitor_arc.monitor_slot(current_slot).await {
@@ -635,12 +1288,15 @@ async fn monitor_live_slots(
 
                         // Determine checkpoint frequency based on whether we're catching up
                         let is_catching_up = current_slot < latest_slot;
-                        let checkpoint_interval = if is_catching_up { 500 } else { 10 };
+                        let checkpoint_interval = if is_catching_up { catchup_interval } else { live_interval };
 
                         // Save checkpoint based on interval
                         if total_scanned % checkpoint_interval == 0 {
                             let checkpoint = SlotCheckpoint::new(current_slot, total_scanned, total_matched);
-                            if let Err(e) = checkpoint.save(CHECKPOINT_FILE) {
+                            if let Err(e) = monitor_arc.filter_engine().save_state() {
+                                error!("Failed to save filter state: {}", e);
+                            }
+                            if let Err(e) = checkpoint_store.save(&checkpoint).await {
                                 error!("Failed to save checkpoint: {}", e);
                             } else {
                                 if is_catching_up {
@@ -659,6 +1315,11 @@ async fn monitor_live_slots(
                             );
                         }
                     },
+                    Err(e) if rpc_budget_exhausted() => {
+                        // Not advancing keeps the slot for the next run
+                        error!("Stopped at slot {}: {}", current_slot, e);
+                        break;
+                    }
                     Err(e) => {
                         error!("Failed to monitor slot {}: {}", current_slot, e);
                     }
@@ -668,122 +1329,1175 @@ async fn monitor_live_slots(
             }
         }
 
-        // Wait before checking for new slots
-        sleep(Duration::from_millis(400)).await;
+        // Wait for the next pushed slot, or poll again after 400ms without a subscription
+        rpc_client.wait_for_new_slot(current_slot.saturating_sub(1), Duration::from_millis(400)).await;
     }
-}
-
-fn generate_config(output: &str) -> Result<()> {
-    println!("{}", "📝 Generating Example Filter Configuration".bright_cyan().bold());
-    println!("{}", "=========================================".bright_cyan());
-
-    let filters = create_example_filter_config();
-    save_filter_config(&filters, output)?;
-
-    println!("✅ Generated {} filters", filters.len());
-    println!("💾 Saved to: {}", output.bright_green());
 
-    println!("\nExample filters include:");
-    for filter in &filters {
-        println!("  • {} ({})", filter.name.bright_yellow(), filter.id);
+    // Only reached when --duration runs out, on Ctrl-C or when the RPC budget is used up; current_slot is the next slot that was never started
+    if current_slot > start_slot {
+        let checkpoint = SlotCheckpoint::new(current_slot - 1, total_scanned, total_matched);
+        if let Err(e) = monitor_arc.filter_engine().save_state() {
+            error!("Failed to save filter state: {}", e);
+        }
+        checkpoint_store.save(&checkpoint).await.context("Failed to save final checkpoint")?;
+        println!("\n💾 Checkpoint saved at slot {}", current_slot - 1);
     }
-
-    println!("\n📌 To use this configuration:");
-    println!("   cargo run --bin monitor_with_filters -- --filter-config {} monitor <SLOTS>", output);
-    println!("\n📌 Or create config/monitors/ and config/alerts/ directories for automatic loading");
+    if let Some(watchdog) = silence_watchdog {
+        watchdog.abort();
+    }
+    if let Some(server) = http_api {
+        server.abort();
+    }
+    if let Some(scheduler) = digest_scheduler {
+        scheduler.abort();
+    }
+    monitor_arc.flush_notifications(notification_flush_timeout()).await;
+    print_duration_summary(total_scanned, total_matched);
+    print_budget_stop();
+    if let Some(audit) = &prefilter_audit {
+        let stats = audit.stats();
+        println!("🔎 Pre-filter audit: {} skipped slots re-checked, {} had matches", stats.audited, stats.missed);
+    }
+    print_rpc_stats(&monitor_arc);
 
     Ok(())
 }
 
-async fn test_slot(
-    slot: u64,
+/// Live monitoring fed by a Yellowstone gRPC transaction stream (`INGESTION_BACKEND=geyser`).
+/// The stream starts at the tip, so slots missed while the monitor was down are not caught up;
+/// the checkpoint is still written on exit so a later RPC run can resume from it.
+async fn monitor_geyser_stream(
     filter_config: Option<String>,
-    rpc_url: Option<String>,
+    rpc_url: String,
+    use_config_dir: bool,
+    strict: bool,
+    options: &RunOptions,
 ) -> Result<()> {
-    println!("{}", "🧪 Testing Filters on Single Slot".bright_cyan().bold());
-    println!("{}", "=================================".bright_cyan());
-
-    let rpc_url = rpc_url.unwrap_or_else(|| {
-        env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
-    });
-
-    println!("📊 Testing slot: {}", slot);
-    println!("🌐 RPC: {}", rpc_url.bright_blue());
-
-    // Check if config directory exists
-    let config_dir = std::path::Path::new("config");
-    let use_config_dir = config_dir.exists() && config_dir.is_dir();
+    let geyser = GeyserConfig::from_env()?;
+    println!("🌊 Geyser: {}", geyser.endpoint.bright_blue());
+    if !geyser.accounts.is_empty() {
+        println!("🎯 Streaming transactions touching {} account(s)", geyser.accounts.len());
+    }
 
+    // RPC is still used for owner/supply lookups and leader attribution
     let monitor = if use_config_dir {
-        FilteredTransactionMonitor::from_config_dir(rpc_url, "config").await?
+        FilteredTransactionMonitor::from_config_dir(rpc_url.clone(), "config", strict).await?
     } else {
-        FilteredTransactionMonitor::new(rpc_url, filter_config).await?
+        FilteredTransactionMonitor::new(rpc_url.clone(), filter_config, strict).await?
     };
+    let monitor = Arc::new(options.apply(monitor, &rpc_url)?);
+    print_active_filters(&monitor.filter_engine());
+    let silence_watchdog = spawn_silence_watchdog(&monitor)?;
+    let digest_scheduler = monitor.spawn_digest_scheduler();
+    let _config_watcher = options.watch_config(&monitor)?;
+    let http_api = spawn_http_api(&monitor).await?;
+
+    let checkpoint_store = configured_checkpoint_store().await?;
+    let checkpoint = checkpoint_store.load().await?;
+    let (mut total_scanned, mut total_matched) = checkpoint.as_ref()
+        .map_or((0, 0), |cp| (cp.total_slots_processed, cp.total_matches_found));
 
-    match monitor.monitor_slot(slot).await {
-        Ok(matched_transactions) => {
-            println!("\n✅ Test completed successfully");
-            println!("Found {} matching transactions", matched_transactions.len());
-
-            for (i, tx) in matched_transactions.iter().enumerate() {
-                println!("\n{}. Transaction {}", i + 1, &tx.transaction.signature[..44]);
-                println!("   Matched filters: {}", tx.matched_filters.join(", ").bright_yellow());
-                println!("   Success: {}", tx.transaction.success);
-                println!("   Fee: {} SOL", tx.transaction.fee as f64 / 1_000_000_000.0);
+    println!("Press Ctrl+C to stop\n");
+    let stats = run_geyser_stream(&monitor, &geyser, || options.time_is_up()).await?;
+    total_scanned += stats.slots;
+    total_matched += stats.matches;
+    println!("🌊 Streamed {} transactions in {} slots, {} matches",
+             stats.transactions,
+             stats.slots,
+             stats.matches.to_string().bright_red()
+    );
 
-                // Show token changes if any
-                for change in &tx.transaction.token_balance_changes {
-                    if change.change.abs() > 0.0 {
-                        println!("   Token change: {:+.2} ({})",
-                                 change.change,
-                                 &change.mint[..8]
-                        );
-                    }
-                }
-            }
-        },
-        Err(e) => {
-            println!("❌ Test failed: {}", e);
+    if let Some(last_slot) = stats.last_slot {
+        if let Err(e) = monitor.filter_engine().save_state() {
+            error!("Failed to save filter state: {}", e);
         }
+        checkpoint_store.save(&SlotCheckpoint::new(last_slot, total_scanned, total_matched)).await
+            .context("Failed to save final checkpoint")?;
+        println!("\n💾 Checkpoint saved at slot {}", last_slot);
     }
+    if let Some(watchdog) = silence_watchdog {
+        watchdog.abort();
+    }
+    if let Some(server) = http_api {
+        server.abort();
+    }
+    if let Some(scheduler) = digest_scheduler {
+        scheduler.abort();
+    }
+    monitor.flush_notifications(notification_flush_timeout()).await;
+    print_duration_summary(total_scanned, total_matched);
+    print_rpc_stats(&monitor);
 
     Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct SlotCheckpoint {
-    last_processed_slot: u64,
-    timestamp: u64,
-    total_slots_processed: u64,
-    total_matches_found: u64,
-}
+/// Re-process the audit's sample of slots a pre-filter skipped. Matches found there go through
+/// the usual actions (they are real, just late), and a warning goes to the log and chat channels
+/// once the share of skipped slots with matches passes `PREFILTER_AUDIT_MAX_MISS_RATE`.
+async fn audit_skipped_slots(audit: Option<&PrefilterAudit>, monitor: &FilteredTransactionMonitor, skipped: &[u64]) {
+    let Some(audit) = audit else { return };
 
-impl SlotCheckpoint {
-    fn new(slot: u64, total_slots: u64, total_matches: u64) -> Self {
-        Self {
-            last_processed_slot: slot,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            total_slots_processed: total_slots,
-            total_matches_found: total_matches,
+    for slot in audit.sample(skipped) {
+        match monitor.monitor_slot(slot).await {
+            Ok(matched) => {
+                if !matched.is_empty() {
+                    println!("  🔎 Audit: skipped slot {} had {} matching transactions",
+                             slot,
+                             matched.len().to_string().bright_red()
+                    );
+                }
+                if let Some(warning) = audit.record(slot, matched.len()) {
+                    warn!("{}", warning);
+                    monitor.send_status_message(&warning).await;
+                }
+            },
+            // Slots without a block can't hide matches and don't count
+            Err(e) if is_skipped_slot_error(&e) => {},
+            Err(e) => warn!("Audit of skipped slot {} failed: {:#}", slot, e),
         }
     }
+}
 
-    fn load(path: &str) -> Result<Option<Self>> {
-        if Path::new(path).exists() {
-            let content = fs::read_to_string(path)?;
-            let checkpoint: SlotCheckpoint = serde_json::from_str(&content)?;
-            Ok(Some(checkpoint))
-        } else {
-            Ok(None)
+/// `kill -HUP <pid>` re-reads the RPC config file and moves every RPC client to its endpoints,
+/// so a dead or exhausted endpoint can be rotated out without a restart
+#[cfg(unix)]
+fn reload_rpc_urls_on_sighup() {
+    use index_cli::rpc_client_with_failover::reload_rpc_config;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            warn!("Could not listen for SIGHUP, RPC endpoints can't be reloaded: {}", e);
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            info!("SIGHUP received, reloading RPC endpoints");
+            match tokio::task::spawn_blocking(reload_rpc_config).await {
+                Ok(Ok(_)) => {},
+                Ok(Err(e)) => warn!("RPC endpoints not reloaded: {:#}", e),
+                Err(e) => warn!("RPC endpoint reload panicked: {}", e),
+            }
+        }
+    });
+}
+
+/// `SOLANA_WS_URL`, or the RPC URL with its scheme switched to ws/wss
+fn slot_subscription_url(rpc_url: &str) -> String {
+    if let Some(ws_url) = env::var("SOLANA_WS_URL").ok().filter(|url| !url.trim().is_empty()) {
+        return ws_url;
+    }
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// `CATCHUP_LEDGER_FILE`, or `catchup_ledger.json`
+fn catchup_ledger_path() -> String {
+    env::var("CATCHUP_LEDGER_FILE")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| "catchup_ledger.json".to_string())
+}
+
+/// Record a checkpointed catch-up batch in the ledger, retry the failed slots of chunks it
+/// finished, and drop the ledger once every chunk is done. Returns the matches found by retries.
+async fn advance_catchup_ledger(
+    ledger: &mut Option<CatchupLedger>,
+    monitor: &FilteredTransactionMonitor,
+    next_slot: u64,
+    failed_slots: &[u64],
+) -> u64 {
+    let Some(current) = ledger.as_mut() else {
+        return 0;
+    };
+
+    let mut matched = 0;
+    for index in current.record_progress(next_slot, failed_slots) {
+        matched += retry_failed_chunk_slots(current, index, monitor).await;
+        let chunk = &current.chunks[index];
+        println!("📦 Chunk {}/{} done (slots {}-{})", index + 1, current.chunks.len(), chunk.start, chunk.end);
+    }
+    finish_or_save_ledger(ledger);
+    matched
+}
+
+/// Process a finished chunk's failed slots once more; slots failing again are logged and
+/// dropped, unless the RPC budget ran out, in which case they stay for the next run
+async fn retry_failed_chunk_slots(ledger: &mut CatchupLedger, index: usize, monitor: &FilteredTransactionMonitor) -> u64 {
+    let failed = std::mem::take(&mut ledger.chunks[index].failed_slots);
+    if !failed.is_empty() {
+        println!("🔁 Retrying {} failed slot(s) of chunk {}/{}", failed.len(), index + 1, ledger.chunks.len());
+    }
+
+    let mut matched = 0;
+    for slot in failed {
+        if rpc_budget_exhausted() {
+            ledger.chunks[index].failed_slots.push(slot);
+            continue;
+        }
+        match monitor.monitor_slot(slot).await {
+            Ok(matched_transactions) => matched += matched_transactions.len() as u64,
+            Err(e) => error!("Giving up on slot {} of catch-up chunk {}: {:#}", slot, index + 1, e),
+        }
+    }
+    matched
+}
+
+fn finish_or_save_ledger(ledger: &mut Option<CatchupLedger>) {
+    let Some(current) = ledger.as_ref() else {
+        return;
+    };
+    if current.is_complete() {
+        if let Err(e) = current.remove() {
+            error!("Failed to remove catch-up ledger: {:#}", e);
+        }
+        println!("📦 Chunked catch-up complete ({} chunks)", current.chunks.len());
+        *ledger = None;
+    } else if let Err(e) = current.save() {
+        error!("Failed to save catch-up ledger: {:#}", e);
+    }
+}
+
+/// Upper bound for delivering buffered notifications on exit (`NOTIFICATION_FLUSH_TIMEOUT_SECS`, default 10)
+fn notification_flush_timeout() -> Duration {
+    let secs = env::var("NOTIFICATION_FLUSH_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(10);
+    Duration::from_secs(secs)
+}
+
+/// `SILENCE_ALERT_SECS`: send a Critical self-alert after this long without a successfully
+/// processed slot. Unset or 0 disables the watchdog.
+fn silence_alert_threshold() -> Result<Option<Duration>> {
+    let Some(value) = env::var("SILENCE_ALERT_SECS").ok().filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let secs = value.trim().parse::<u64>()
+        .with_context(|| format!("Invalid SILENCE_ALERT_SECS value '{}': expected a number of seconds", value))?;
+    Ok((secs > 0).then(|| Duration::from_secs(secs)))
+}
+
+/// Start the silence watchdog when `SILENCE_ALERT_SECS` is set
+fn spawn_silence_watchdog(monitor: &Arc<FilteredTransactionMonitor>) -> Result<Option<tokio::task::JoinHandle<()>>> {
+    let Some(threshold) = silence_alert_threshold()? else {
+        return Ok(None);
+    };
+    println!("🔇 Silence alert after: {}", format!("{}s without a processed slot", threshold.as_secs()).bright_yellow());
+    Ok(Some(monitor.spawn_silence_watchdog(threshold)))
+}
+
+/// Start the HTTP API when `API_ENABLED` is set
+async fn spawn_http_api(monitor: &Arc<FilteredTransactionMonitor>) -> Result<Option<tokio::task::JoinHandle<()>>> {
+    let Some(config) = HttpApiConfig::from_env()? else {
+        return Ok(None);
+    };
+    let handle = http_api::spawn(Arc::clone(monitor), &config).await?;
+    println!("📡 HTTP API: {}", format!("port {}", config.port).bright_blue());
+    Ok(Some(handle))
+}
+
+fn print_duration_summary(total_scanned: u64, total_matched: u64) {
+    println!("\n{}", "⏱️  Run duration reached".bright_magenta().bold());
+    println!("{}", "=======================".bright_magenta());
+    println!("Slots processed (total): {}", total_scanned);
+    println!("Total matches: {}", total_matched.to_string().bright_green());
+}
+
+/// Largest accepted checkpoint interval; catch-up batches this large already take minutes
+const MAX_CHECKPOINT_INTERVAL: u64 = 10_000;
+
+/// Read a checkpoint interval in slots, rejecting zero, garbage and values above MAX_CHECKPOINT_INTERVAL
+fn checkpoint_interval_from_env(key: &str, default: u64) -> Result<u64> {
+    let Some(value) = env::var(key).ok().filter(|v| !v.trim().is_empty()) else {
+        return Ok(default);
+    };
+
+    let interval = value.trim().parse::<u64>()
+        .with_context(|| format!("Invalid {} value '{}': expected a number of slots", key, value))?;
+    if interval == 0 || interval > MAX_CHECKPOINT_INTERVAL {
+        return Err(anyhow::anyhow!(
+            "Invalid {} value {}: must be between 1 and {}",
+            key, interval, MAX_CHECKPOINT_INTERVAL
+        ));
+    }
+
+    Ok(interval)
+}
+
+/// Log filter from, in increasing precedence: RUST_LOG (default `info`), -v/-vv and --log
+/// directives. The debug command always turns on debug output for this crate.
+fn build_env_filter(cli: &Cli) -> Result<tracing_subscriber::EnvFilter> {
+    use tracing_subscriber::filter::Directive;
+
+    let base = env::var("RUST_LOG").ok().filter(|v| !v.trim().is_empty()).unwrap_or_else(|| "info".to_string());
+    let mut env_filter = tracing_subscriber::EnvFilter::try_new(&base)
+        .with_context(|| format!("Invalid RUST_LOG '{}'", base))?;
+
+    let crate_level = match cli.verbose {
+        0 if matches!(cli.command, Some(Commands::Debug { .. })) => Some("debug"),
+        0 => None,
+        1 => Some("debug"),
+        _ => Some("trace"),
+    };
+    if let Some(level) = crate_level {
+        for target in ["index_cli", "monitor_with_filters"] {
+            env_filter = env_filter.add_directive(format!("{}={}", target, level).parse::<Directive>()?);
+        }
+    }
+
+    for directive in &cli.log_directives {
+        let parsed = directive.parse::<Directive>()
+            .with_context(|| format!("Invalid --log directive '{}'", directive))?;
+        env_filter = env_filter.add_directive(parsed);
+    }
+
+    Ok(env_filter)
+}
+
+/// `GLOBAL_RPC_CONCURRENCY`: max in-flight RPC calls across all stages; unset, empty or 0 means no limit
+fn global_rpc_concurrency_from_env() -> Result<Option<usize>> {
+    let Some(value) = env::var("GLOBAL_RPC_CONCURRENCY").ok().filter(|v| !v.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let limit = value.trim().parse::<usize>()
+        .with_context(|| format!("Invalid GLOBAL_RPC_CONCURRENCY value '{}': expected a number of calls", value))?;
+    Ok(Some(limit).filter(|limit| *limit > 0))
+}
+
+/// Strictly ordered, finalized-only monitoring. Slots are processed one at a time and each one
+/// is appended to the audit trail as processed or skipped before the checkpoint moves past it,
+/// so a restart can re-audit a slot but never leave a gap.
+async fn monitor_finalized_slots(
+    filter_config: Option<String>,
+    rpc_url: String,
+    use_config_dir: bool,
+    strict: bool,
+    options: &RunOptions,
+) -> Result<()> {
+    let audit_path = env::var("SLOT_AUDIT_FILE")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SLOT_AUDIT_FILE.to_string());
+
+    println!("🌐 RPC: {}", rpc_url.bright_blue());
+    println!("🧾 Audit trail: {}", audit_path.bright_yellow());
+    println!("🔧 Commitment: finalized, sequential processing (pre-filters disabled)");
+
+    let rpc_client = RpcClientWithFailover::new(rpc_url.clone());
+    let monitor = if use_config_dir {
+        FilteredTransactionMonitor::from_config_dir(rpc_url.clone(), "config", strict).await?
+    } else {
+        FilteredTransactionMonitor::new(rpc_url.clone(), filter_config, strict).await?
+    }
+    .with_commitment(CommitmentConfig::finalized());
+    let monitor = Arc::new(options.apply(monitor, &rpc_url)?);
+    print_active_filters(&monitor.filter_engine());
+    let silence_watchdog = spawn_silence_watchdog(&monitor)?;
+    let digest_scheduler = monitor.spawn_digest_scheduler();
+    let _config_watcher = options.watch_config(&monitor)?;
+    let http_api = spawn_http_api(&monitor).await?;
+
+    let checkpoint_store = configured_checkpoint_store().await?;
+    let checkpoint = checkpoint_store.load().await?;
+    let (mut total_scanned, mut total_matched) = checkpoint.as_ref()
+        .map(|cp| (cp.total_slots_processed, cp.total_matches_found))
+        .unwrap_or((0, 0));
+
+    let mut current_slot = if let Some(ref cp) = checkpoint {
+        println!("📂 Resuming after checkpoint slot {}", cp.last_processed_slot);
+        cp.last_processed_slot + 1
+    } else if let Some(warmup) = options.warmup_slots {
+        let finalized = rpc_client.get_slot_with_commitment(CommitmentConfig::finalized()).await?;
+        let slot = finalized.saturating_sub(warmup);
+        println!("🔥 Warming up from slot {} ({} slots before finalized slot {})", slot, warmup, finalized);
+        slot
+    } else if let Ok(start_slot_str) = env::var("START_SLOT") {
+        let slot = start_slot_str.trim().parse::<u64>()
+            .context("Invalid START_SLOT value")?;
+        println!("🎯 Starting from configured slot: {}", slot);
+        slot
+    } else {
+        let finalized = rpc_client.get_slot_with_commitment(CommitmentConfig::finalized()).await?;
+        println!("🚀 Starting from finalized slot: {}", finalized);
+        finalized
+    };
+
+    println!("Press Ctrl+C to stop\n");
+
+    let mut consecutive_errors = 0;
+
+    while !options.time_is_up() {
+        let finalized_slot = match rpc_client.get_slot_with_commitment(CommitmentConfig::finalized()).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                consecutive_errors += 1;
+                error!("Failed to get finalized slot: {}", e);
+
+                if consecutive_errors > 5 {
+                    return Err(anyhow::anyhow!("Too many consecutive errors getting finalized slot"));
+                }
+
+                sleep(Duration::from_secs(2)).await;
+                continue;
+            }
+        };
+
+        consecutive_errors = 0;
+        monitor.record_chain_tip(finalized_slot);
+
+        'slots: while current_slot <= finalized_slot && !options.time_is_up() {
+            let mut attempt = 1;
+            let record = loop {
+                match monitor.monitor_slot(current_slot).await {
+                    Ok(matched_transactions) => {
+                        for tx in &matched_transactions {
+                            println!("  📌 Slot {} {} - Filters: {}",
+                                     current_slot,
+                                     &tx.transaction.signature[..20],
+                                     tx.matched_filters.join(", ").bright_yellow()
+                            );
+                        }
+                        break SlotAuditRecord::new(current_slot, "processed", matched_transactions.len(), None);
+                    },
+                    Err(e) if is_skipped_slot_error(&e) => {
+                        println!("  ⏭️  Slot {} skipped by the cluster", current_slot);
+                        monitor.mark_processed();
+                        break SlotAuditRecord::new(current_slot, "skipped", 0, Some(format!("{:#}", e)));
+                    },
+                    Err(e) if rpc_budget_exhausted() => {
+                        error!("Stopped at slot {}: {:#}", current_slot, e);
+                        break 'slots;
+                    },
+                    Err(e) => {
+                        if attempt >= MAX_SLOT_ATTEMPTS {
+                            return Err(e.context(format!(
+                                "Giving up on slot {} after {} attempts; restart to resume from the checkpoint",
+                                current_slot, attempt
+                            )));
+                        }
+                        error!("Failed to process slot {} (attempt {}/{}): {:#}", current_slot, attempt, MAX_SLOT_ATTEMPTS, e);
+                        sleep(Duration::from_secs(2u64.pow(attempt.min(5)))).await;
+                        attempt += 1;
+                    }
+                }
+            };
+
+            // Audit first, then checkpoint: a crash in between re-audits the slot rather than losing it
+            record.append(&audit_path)?;
+
+            total_scanned += 1;
+            total_matched += record.matches as u64;
+
+            if let Err(e) = monitor.filter_engine().save_state() {
+                error!("Failed to save filter state: {}", e);
+            }
+            checkpoint_store.save(&SlotCheckpoint::new(current_slot, total_scanned, total_matched)).await
+                .context("Failed to save checkpoint")?;
+
+            if total_scanned % 100 == 0 {
+                println!("📊 Progress: slot {}, {} slots audited, {} matches found ({} behind finalized)",
+                         current_slot,
+                         total_scanned,
+                         total_matched.to_string().bright_green(),
+                         finalized_slot.saturating_sub(current_slot)
+                );
+            }
+
+            current_slot += 1;
+        }
+
+        sleep(Duration::from_millis(400)).await;
+    }
+
+    // Every processed slot is already checkpointed; only buffered notifications are left
+    if let Some(watchdog) = silence_watchdog {
+        watchdog.abort();
+    }
+    if let Some(server) = http_api {
+        server.abort();
+    }
+    if let Some(scheduler) = digest_scheduler {
+        scheduler.abort();
+    }
+    monitor.flush_notifications(notification_flush_timeout()).await;
+    print_duration_summary(total_scanned, total_matched);
+    print_budget_stop();
+    print_rpc_stats(&monitor);
+
+    Ok(())
+}
+
+/// RPC errors for slots that will never have a block (-32007 skipped, -32009 missing in long-term storage)
+fn is_skipped_slot_error(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error);
+    message.contains("-32007") || message.contains("-32009") || message.contains("was skipped")
+}
+
+#[derive(Debug, Serialize)]
+struct SlotAuditRecord {
+    slot: u64,
+    status: &'static str,
+    matches: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    timestamp: u64,
+}
+
+impl SlotAuditRecord {
+    fn new(slot: u64, status: &'static str, matches: usize, reason: Option<String>) -> Self {
+        Self {
+            slot,
+            status,
+            matches,
+            reason,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
         }
     }
 
-    fn save(&self, path: &str) -> Result<()> {
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(path, json)?;
+    fn append(&self, path: &str) -> Result<()> {
+        use std::io::Write;
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open audit file {}", path))?;
+        writeln!(file, "{}", serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write audit file {}", path))?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Requests, errors and average latency per RPC endpoint used for block fetches
+fn print_rpc_stats(monitor: &FilteredTransactionMonitor) {
+    let stats = monitor.transaction_extractor().rpc_stats();
+    if stats.iter().all(|s| s.requests == 0) {
+        return;
+    }
+
+    if let Some((used, limit)) = rpc_call_budget() {
+        println!("🌐 RPC call budget: {} of {} used", used, limit);
+    }
+    println!("🌐 RPC endpoints:");
+    for endpoint in stats {
+        println!("  • {} (weight {}): {} requests, {} errors, {:.0}ms avg",
+                 url_host(&endpoint.url),
+                 endpoint.weight,
+                 endpoint.requests,
+                 endpoint.errors,
+                 endpoint.avg_latency_ms
+        );
+    }
+}
+
+/// Explains why a run ended early when the --max-rpc-calls budget ran out
+fn print_budget_stop() {
+    if let Some((used, limit)) = rpc_call_budget().filter(|_| rpc_budget_exhausted()) {
+        println!("{}", format!(
+            "🛑 Stopped early: {} of {} allowed RPC calls used (--max-rpc-calls)",
+            used, limit
+        ).bright_yellow());
+    }
+}
+
+/// Startup summary of the filters that will actually run
+fn print_active_filters(filter_engine: &FilterEngine) {
+    let filters = filter_engine.filters();
+    println!("🧩 Active filters ({}):", filters.len());
+    for filter in filters {
+        println!("  • {} ({})", filter.name.bright_yellow(), filter.id);
+        println!("    {}", describe_filter(filter));
+    }
+    println!();
+}
+
+fn generate_config(output: &str) -> Result<()> {
+    println!("{}", "📝 Generating Example Filter Configuration".bright_cyan().bold());
+    println!("{}", "=========================================".bright_cyan());
+
+    let filters = create_example_filter_config();
+    // Generated configs are meant to be edited by hand, so they stay indented
+    save_filter_config(&filters, output, true)?;
+
+    println!("✅ Generated {} filters", filters.len());
+    println!("💾 Saved to: {}", output.bright_green());
+
+    println!("\nExample filters include:");
+    for filter in &filters {
+        println!("  • {} ({})", filter.name.bright_yellow(), filter.id);
+    }
+
+    println!("\n📌 To use this configuration:");
+    println!("   cargo run --bin monitor_with_filters -- --filter-config {} monitor <SLOTS>", output);
+    println!("\n📌 Or create config/monitors/ and config/alerts/ directories for automatic loading");
+
+    Ok(())
+}
+
+/// Returns the number of matching transactions, or None if the slot couldn't be processed
+async fn test_slot(
+    slot: u64,
+    filter_config: Option<String>,
+    rpc_url: Option<String>,
+    strict: bool,
+) -> Result<Option<usize>> {
+    println!("{}", "🧪 Testing Filters on Single Slot".bright_cyan().bold());
+    println!("{}", "=================================".bright_cyan());
+
+    let rpc_url = rpc_url.unwrap_or_else(|| {
+        env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
+    });
+
+    println!("📊 Testing slot: {}", slot);
+    println!("🌐 RPC: {}", rpc_url.bright_blue());
+
+    // Check if config directory exists
+    let config_dir = std::path::Path::new("config");
+    let use_config_dir = config_dir.exists() && config_dir.is_dir();
+
+    let monitor = if use_config_dir {
+        FilteredTransactionMonitor::from_config_dir(rpc_url, "config", strict).await?
+    } else {
+        FilteredTransactionMonitor::new(rpc_url, filter_config, strict).await?
+    };
+
+    match monitor.monitor_slot(slot).await {
+        Ok(matched_transactions) => {
+            println!("\n✅ Test completed successfully");
+            println!("Found {} matching transactions", matched_transactions.len());
+
+            for (i, tx) in matched_transactions.iter().enumerate() {
+                println!("\n{}. Transaction {}", i + 1, &tx.transaction.signature[..44]);
+                println!("   Matched filters: {}", tx.matched_filters.join(", ").bright_yellow());
+                println!("   Success: {}", tx.transaction.success);
+                println!("   Fee: {} SOL", tx.transaction.fee as f64 / 1_000_000_000.0);
+
+                // Show token changes if any
+                for change in &tx.transaction.token_balance_changes {
+                    if change.change.abs() > 0.0 {
+                        println!("   Token change: {:+.2} ({})",
+                                 change.change,
+                                 &change.mint[..8]
+                        );
+                    }
+                }
+            }
+            Ok(Some(matched_transactions.len()))
+        },
+        Err(e) => {
+            println!("❌ Test failed: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+async fn debug_slot(
+    slot: u64,
+    show_all: bool,
+    filter_config: Option<String>,
+    rpc_url: Option<String>,
+    strict: bool,
+) -> Result<()> {
+    println!("{}", "🐞 Debugging Single Slot".bright_cyan().bold());
+    println!("{}", "========================".bright_cyan());
+
+    let rpc_url = rpc_url.unwrap_or_else(|| {
+        env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
+    });
+
+    println!("📊 Slot: {}", slot);
+    println!("🌐 RPC: {}", rpc_url.bright_blue());
+
+    let use_config_dir = Path::new("config").is_dir();
+    let monitor = if use_config_dir {
+        FilteredTransactionMonitor::from_config_dir(rpc_url, "config", strict).await?
+    } else {
+        FilteredTransactionMonitor::new(rpc_url, filter_config, strict).await?
+    };
+    print_active_filters(&monitor.filter_engine());
+
+    let mut extraction = match monitor.transaction_extractor().extract_from_slot_detailed(slot).await {
+        Ok(extraction) => extraction,
+        Err(e) => {
+            println!("❌ Block fetch failed: {:#}", e);
+            return Err(e);
+        }
+    };
+
+    println!("📦 Raw transactions in block: {}", extraction.raw_transaction_count);
+    println!("✅ Extracted: {}", extraction.transactions.len().to_string().bright_green());
+    if !extraction.failures.is_empty() {
+        println!("❌ Failed to extract: {}", extraction.failures.len().to_string().bright_red());
+        for (index, error) in &extraction.failures {
+            println!("   #{}: {}", index, error);
+        }
+    }
+    println!();
+    monitor.resolve_account_owners(&mut extraction.transactions).await;
+    monitor.resolve_mint_supplies(&mut extraction.transactions).await;
+
+    let mut matched_transactions = 0;
+    let mut hidden = 0;
+
+    for (i, transaction) in extraction.transactions.iter().enumerate() {
+        // Read-only evaluation: no actions run and the seen-set is left untouched
+        let explanations = monitor.filter_engine().explain_transaction(transaction);
+        let any_filter = explanations.iter().any(|e| e.matched);
+        let any_condition = explanations.iter().any(|e| e.conditions.iter().any(|c| c.matched));
+
+        if any_filter {
+            matched_transactions += 1;
+        }
+        if !show_all && !any_condition {
+            hidden += 1;
+            continue;
+        }
+
+        println!("{}. {} ({}, {} instructions, {} token changes)",
+                 i + 1,
+                 transaction.signature,
+                 if transaction.success { "success" } else { "failed" },
+                 transaction.instructions.len(),
+                 transaction.token_balance_changes.len()
+        );
+
+        for explanation in &explanations {
+            let marker = if explanation.matched { "✅" } else { "❌" };
+            println!("   {} {} ({})", marker, explanation.filter_name, explanation.filter_id);
+            for condition in &explanation.conditions {
+                println!("      {} {:<7} {}",
+                         if condition.matched { "✓".bright_green() } else { "✗".bright_red() },
+                         condition.group,
+                         describe_condition(&condition.condition)
+                );
+            }
+        }
+    }
+
+    println!("\n📋 {} of {} transactions matched at least one filter", matched_transactions, extraction.transactions.len());
+    if hidden > 0 {
+        println!("   ({} transactions with no matching conditions hidden; use --all to show them)", hidden);
+    }
+
+    Ok(())
+}
+
+/// Builds the filter engine without a monitor, so no RPC or notifier is touched: from
+/// `--filter-config` when given, otherwise from the config directory
+fn test_filters(fixtures_dir: &str, filter_config: Option<String>, strict: bool) -> Result<()> {
+    let engine = match filter_config {
+        Some(path) => FilterEngine::from_json_file(&path, strict)?,
+        None => {
+            let mut config_manager = ConfigManager::new("config").with_strict(strict);
+            config_manager.load_all()?;
+            FilterEngine::try_new(config_manager.get_filters_with_alerts()?)?
+        },
+    };
+    let fixtures = load_fixtures(fixtures_dir)?;
+    if fixtures.is_empty() {
+        return Err(anyhow::anyhow!("No fixtures (*.json) in {}", fixtures_dir));
+    }
+
+    let mut failed = 0;
+    for (path, fixture) in &fixtures {
+        let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let mismatch = check_fixture(&engine, fixture);
+        if mismatch.is_empty() {
+            println!("  {} {}", "✓".bright_green(), name);
+            continue;
+        }
+        failed += 1;
+        println!("  {} {}{}", "✗".bright_red(), name,
+                 fixture.description.as_deref().map(|d| format!(" ({})", d)).unwrap_or_default());
+        if !mismatch.missing.is_empty() {
+            println!("      expected but not matched: {}", mismatch.missing.join(", "));
+        }
+        if !mismatch.unexpected.is_empty() {
+            println!("      matched but not expected: {}", mismatch.unexpected.join(", "));
+        }
+    }
+
+    println!("\n{} fixtures, {} passed, {} failed", fixtures.len(), fixtures.len() - failed, failed);
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} of {} filter fixtures failed", failed, fixtures.len()));
+    }
+    Ok(())
+}
+
+async fn import_matches(file: &str, collection: &str) -> Result<()> {
+    let imported = read_match_records(std::path::Path::new(file))?;
+    for (line, error) in imported.rejected.iter().take(10) {
+        println!("  {} line {}: {}", "⚠️".yellow(), line, error);
+    }
+    if imported.rejected.len() > 10 {
+        println!("  ... and {} more rejected lines", imported.rejected.len() - 10);
+    }
+    if imported.records.is_empty() {
+        return Err(anyhow::anyhow!("No importable records in {}", file));
+    }
+
+    let count = imported.records.len();
+    if let Some(backend) = configured_storage_backend().await? {
+        for mut record in imported.records {
+            record.collection = collection.to_string();
+            backend.store(&record).await
+                .with_context(|| format!("Failed to import {} into {}", record.transaction.signature, backend.name()))?;
+        }
+        println!("📥 Imported {} records into {} collection {} ({} lines rejected)",
+                 count.to_string().bright_green(), backend.name(), collection.bright_cyan(), imported.rejected.len());
+        return Ok(());
+    }
+
+    // Without STORAGE_BACKEND the records only pass through in-memory storage, which validates them
+    let mut storage = TransactionStorage::new();
+    for record in imported.records {
+        storage.import_record(record, collection);
+    }
+    println!("📥 Imported {} records into collection {} ({} lines rejected)",
+             count.to_string().bright_green(), collection.bright_cyan(), imported.rejected.len());
+    Ok(())
+}
+
+async fn show_status(rpc_url: Option<String>) -> Result<()> {
+    println!("{}", "📋 Monitor Status".bright_cyan().bold());
+    println!("{}", "=================".bright_cyan());
+
+    let checkpoint_store = configured_checkpoint_store().await?;
+    let Some(checkpoint) = checkpoint_store.load().await? else {
+        println!("No checkpoint found in the {} checkpoint store - the monitor has not saved progress yet", checkpoint_store.name());
+        return Ok(());
+    };
+
+    let saved_at = chrono::DateTime::from_timestamp(checkpoint.timestamp as i64, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| checkpoint.timestamp.to_string());
+
+    println!("📍 Last processed slot: {}", checkpoint.last_processed_slot.to_string().bright_green());
+    println!("🕒 Checkpoint saved:    {}", saved_at);
+    println!("📊 Slots processed:     {}", checkpoint.total_slots_processed);
+    println!("🎯 Total matches:       {}", checkpoint.total_matches_found.to_string().bright_yellow());
+
+    let rpc_url = rpc_url.unwrap_or_else(|| {
+        env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
+    });
+    let rpc_client = RpcClientWithFailover::new(rpc_url);
+    match rpc_client.get_slot().await {
+        Ok(current_slot) => {
+            let lag = current_slot.saturating_sub(checkpoint.last_processed_slot);
+            println!("⛓️  Current slot:        {}", current_slot);
+            println!("⏱️  Lag:                 {} slots (~{:.1}s)", lag, lag as f64 * 0.4);
+        },
+        Err(e) => {
+            println!("⚠️  Could not fetch current slot: {}", e);
+        }
+    }
+
+    match configured_storage_backend().await? {
+        Some(backend) => {
+            println!("\n🗄️  Collections ({}):", backend.name());
+            for (collection, count) in backend.collections().await? {
+                println!("   {}: {}", collection.bright_cyan(), count);
+            }
+        },
+        // Without a storage backend matches live in the monitor's memory, out of reach of another process
+        None => println!("\n🗄️  Collections: storage is in-memory only (configure config/storage.json or STORAGE_BACKEND to persist matches)"),
+    }
+
+    Ok(())
+}
+
+/// Sends `message` through the same notifier and env configuration the monitor uses
+async fn test_notify(channel: &str, message: &str) -> Result<()> {
+    println!("{}", "🔔 Testing Notification Channel".bright_cyan().bold());
+    println!("{}", "==============================".bright_cyan());
+    println!("📡 Channel: {}", channel.bright_yellow());
+
+    let result = match channel.to_lowercase().as_str() {
+        "telegram" => {
+            let token = env::var("TELEGRAM_BOT_TOKEN").ok().filter(|t| !t.is_empty())
+                .context("TELEGRAM_BOT_TOKEN is not set")?;
+            let chat_id = env::var("TELEGRAM_CHAT_ID").ok().filter(|c| !c.is_empty())
+                .context("TELEGRAM_CHAT_ID is not set")?;
+            println!("💬 Chat: {}", chat_id);
+            TelegramNotifier::new(token, chat_id)
+                .with_threads_from_env()
+                .send_custom_message("Test notification", message)
+                .await
+        },
+        "slack" => {
+            let notifier = SlackNotifier::new()?;
+            notifier.send_simple_message(message).await
+        },
+        "discord" => {
+            let notifier = DiscordNotifier::from_env().context("DISCORD_WEBHOOK_URL is not set")?;
+            println!("🔗 Webhook host: {}", url_host(notifier.webhook_for(None)));
+            notifier.send_message(message).await
+        },
+        "email" => {
+            let notifier = EmailNotifier::from_env()?.context("SMTP_HOST is not set")?;
+            if notifier.recipients_for(None).is_empty() {
+                return Err(anyhow::anyhow!("EMAIL_TO is not set; test messages go to the default recipients"));
+            }
+            println!("📧 Recipients: {}", notifier.recipients_for(None).iter().map(ToString::to_string).collect::<Vec<_>>().join(", "));
+            notifier.send_message("Test notification", message).await
+        },
+        other => {
+            return Err(anyhow::anyhow!("Unknown channel '{}' (expected telegram, slack, discord or email)", other));
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            println!("✅ {}", "Test message delivered".bright_green());
+            Ok(())
+        },
+        Err(e) => {
+            println!("❌ {} {:#}", "Delivery failed:".bright_red(), e);
+            Err(e)
+        }
+    }
+}
+
+/// How often a program appeared in transactions that moved the discovered mint
+#[derive(Default)]
+struct ProgramTally {
+    name: Option<String>,
+    transactions: usize,
+    slots: HashSet<u64>,
+}
+
+/// Count every non-infrastructure program (top-level or CPI) in transactions that change a
+/// balance of `mint` into `tallies`, returning how many such transactions there were.
+fn tally_co_occurring_programs<'a>(
+    transactions: impl IntoIterator<Item = &'a ExtractedTransaction>,
+    mint: &str,
+    tallies: &mut HashMap<String, ProgramTally>,
+) -> usize {
+    let mut mint_transactions = 0;
+
+    for transaction in transactions {
+        if !transaction.token_balance_changes.iter().any(|change| change.mint == mint) {
+            continue;
+        }
+        mint_transactions += 1;
+
+        let instructions = transaction.instructions.iter()
+            .chain(transaction.inner_instructions.iter().flat_map(|set| set.instructions.iter()));
+
+        let mut counted = HashSet::new();
+        for instruction in instructions {
+            if INFRASTRUCTURE_PROGRAMS.contains(&instruction.program_id.as_str())
+                || !counted.insert(instruction.program_id.as_str())
+            {
+                continue;
+            }
+
+            let tally = tallies.entry(instruction.program_id.clone()).or_default();
+            tally.transactions += 1;
+            tally.slots.insert(transaction.slot);
+            if tally.name.is_none() {
+                tally.name = instruction.program_name.clone();
+            }
+        }
+    }
+
+    mint_transactions
+}
+
+async fn bench_rpc(slot: u64, iterations: usize, rpc_url: Option<String>) -> Result<()> {
+    println!("{}", "⏱️  Benchmarking RPC Endpoints".bright_cyan().bold());
+    println!("{}", "=============================".bright_cyan());
+
+    if iterations == 0 {
+        return Err(anyhow::anyhow!("--iterations must be at least 1"));
+    }
+    let rpc_url = rpc_url.unwrap_or_else(|| {
+        env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
+    });
+
+    // Same URL list as the monitor: primary, SOLANA_RPC_URL_2..5 and config/rpc.json
+    let client = RpcClientWithFailover::new(rpc_url);
+    println!("📊 getBlock {} x{} per endpoint\n", slot, iterations);
+    let mut results = client.bench_get_block(slot, iterations).await;
+    // Fastest typical latency first; endpoints that never answered go last
+    results.sort_by_key(|result| result.median().unwrap_or(Duration::MAX));
+
+    let ms = |latency: Option<Duration>| latency
+        .map(|latency| format!("{:.0}ms", latency.as_secs_f64() * 1000.0))
+        .unwrap_or_else(|| "-".to_string());
+    println!("{:<40} {:>6} {:>8} {:>8} {:>8} {:>7}", "Endpoint", "Weight", "Min", "Median", "P95", "Errors");
+    for result in &results {
+        println!("{:<40} {:>6} {:>8} {:>8} {:>8} {:>6.0}%",
+                 url_host(&result.url),
+                 result.weight,
+                 ms(result.min()),
+                 ms(result.median()),
+                 ms(result.p95()),
+                 result.error_rate() * 100.0
+        );
+    }
+
+    Ok(())
+}
+
+async fn discover_programs(
+    slots_input: &str,
+    mint: Option<String>,
+    top: usize,
+    rpc_url: Option<String>,
+) -> Result<()> {
+    use futures::stream::{self, StreamExt};
+
+    println!("{}", "🔭 Discovering Co-occurring Programs".bright_cyan().bold());
+    println!("{}", "===================================".bright_cyan());
+
+    let (slots, errors) = parse_slot_list(slots_input);
+    if !errors.is_empty() {
+        return Err(anyhow::anyhow!("Invalid slot entries: {}", errors.join("; ")));
+    }
+    if slots.is_empty() {
+        return Err(anyhow::anyhow!("No valid slots provided"));
+    }
+
+    let mint = mint
+        .or_else(|| env::var("YU_TOKEN_ADDRESS").ok().filter(|m| !m.is_empty()))
+        .unwrap_or_else(|| "YUYAiJo8KVbnc6Fb6h3MnH2VGND4uGWDH4iLnw7DLEu".to_string());
+    let rpc_url = rpc_url.unwrap_or_else(|| {
+        env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string())
+    });
+    let max_concurrent = env::var("MAX_CONCURRENT_SLOTS")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(20);
+
+    println!("🪙 Mint: {}", mint.bright_yellow());
+    println!("📊 Slots: {}", slots.len());
+    println!("🌐 RPC: {}", rpc_url.bright_blue());
+    println!();
+
+    let extractor = TransactionExtractor::new(rpc_url);
+    let mut extractions = stream::iter(slots.iter().copied())
+        .map(|slot| {
+            let extractor = &extractor;
+            async move { (slot, extractor.extract_from_slot(slot).await) }
+        })
+        .buffer_unordered(max_concurrent.max(1));
+
+    let mut tallies: HashMap<String, ProgramTally> = HashMap::new();
+    let mut mint_transactions = 0;
+    let mut slots_with_mint = 0;
+    let mut failed_slots = 0;
+
+    while let Some((slot, extraction)) = extractions.next().await {
+        match extraction {
+            Ok(transactions) => {
+                let found = tally_co_occurring_programs(&transactions, &mint, &mut tallies);
+                if found > 0 {
+                    slots_with_mint += 1;
+                    mint_transactions += found;
+                }
+            },
+            Err(e) => {
+                failed_slots += 1;
+                error!("Failed to fetch slot {}: {}", slot, e);
+            }
+        }
+    }
+
+    println!("📦 {} transactions moving the mint in {} of {} slots ({} failed to fetch)",
+             mint_transactions, slots_with_mint, slots.len(), failed_slots);
+
+    if tallies.is_empty() {
+        println!("No programs found alongside this mint; try a wider slot range.");
+        return Ok(());
+    }
+
+    let known_programs: HashSet<String> = fs::read_to_string(OPTIMIZATION_CONFIG_FILE)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config["pre_filters"]["monitored_programs"].as_array().cloned())
+        .map(|programs| programs.iter().filter_map(|p| p.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+
+    let mut ranked: Vec<(String, ProgramTally)> = tallies.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.transactions.cmp(&a.1.transactions).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(top);
+
+    println!("\n{}", "Rank  Program                                       Txs    Share  Slots  Name".bright_white().bold());
+    for (rank, (program_id, tally)) in ranked.iter().enumerate() {
+        let share = tally.transactions as f64 / mint_transactions as f64 * 100.0;
+        let marker = if known_programs.contains(program_id) { " (already monitored)".dimmed().to_string() } else { String::new() };
+        println!("{:>4}  {:<44}  {:>5}  {:>5.1}%  {:>5}  {}{}",
+                 rank + 1,
+                 program_id,
+                 tally.transactions,
+                 share,
+                 tally.slots.len(),
+                 tally.name.as_deref().unwrap_or("-"),
+                 marker);
+    }
+
+    let candidates: Vec<&str> = ranked.iter()
+        .map(|(program_id, _)| program_id.as_str())
+        .filter(|program_id| !known_programs.contains(*program_id))
+        .collect();
+
+    if candidates.is_empty() {
+        println!("\n✅ Every listed program is already in {}", OPTIMIZATION_CONFIG_FILE);
+    } else {
+        println!("\n💡 Candidates for pre_filters.monitored_programs in {}:", OPTIMIZATION_CONFIG_FILE);
+        println!("{}", serde_json::to_string_pretty(&candidates)?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slot_list_singles_and_ranges() {
+        let (slots, errors) = parse_slot_list("100, 105-107,200");
+        assert_eq!(slots, vec![100, 105, 106, 107, 200]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_slot_list_reports_invalid_tokens() {
+        let (slots, errors) = parse_slot_list("100,1O1,102,abc-5,300-200,,");
+        assert_eq!(slots, vec![100, 102]);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("'1O1'"));
+        assert!(errors[1].contains("'abc-5'"));
+        assert!(errors[2].contains("after end"));
+    }
+
+    #[test]
+    fn test_parse_slot_list_rejects_oversized_ranges() {
+        let (slots, errors) = parse_slot_list("1-18446744073709551615,5,10-100010");
+        assert_eq!(slots, vec![5]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| error.contains("more than 100000 slots")));
+        assert_eq!(parse_slot_list("10-100009").0.len(), 100_000);
+    }
+
+    #[test]
+    fn test_parse_slot_list_rejects_negative_and_open_ranges() {
+        let (slots, errors) = parse_slot_list("-5,10-,7");
+        assert_eq!(slots, vec![7]);
+        assert_eq!(errors.len(), 2);
+    }
+}
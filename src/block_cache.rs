@@ -0,0 +1,121 @@
+use solana_client::rpc_config::RpcBlockConfig;
+use solana_transaction_status::UiConfirmedBlock;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use tracing::{debug, warn};
+
+const DEFAULT_BLOCK_CACHE_SIZE: usize = 100;
+
+/// Blocks the slot pre-filters fetched, shared by every `RpcClientWithFailover` in the process
+static SHARED_BLOCK_CACHE: LazyLock<BlockCache> = LazyLock::new(|| BlockCache::new(block_cache_size_from_env()));
+
+/// `BLOCK_CACHE_SIZE` (default 100); 0 turns the cache off
+fn block_cache_size_from_env() -> usize {
+    match std::env::var("BLOCK_CACHE_SIZE") {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse::<usize>().unwrap_or_else(|_| {
+            warn!("Invalid BLOCK_CACHE_SIZE '{}', using {}", value, DEFAULT_BLOCK_CACHE_SIZE);
+            DEFAULT_BLOCK_CACHE_SIZE
+        }),
+        _ => DEFAULT_BLOCK_CACHE_SIZE,
+    }
+}
+
+/// The process-wide cache `RpcClientWithFailover::get_block_with_config` takes blocks from
+pub fn shared() -> &'static BlockCache {
+    &SHARED_BLOCK_CACHE
+}
+
+/// Blocks fetched ahead of their processing, so a slot that passed a pre-filter isn't fetched
+/// again for extraction. Each block is handed out once (a parsed block can take tens of MB);
+/// beyond `capacity` blocks the least recently added one is dropped and refetched when needed.
+pub struct BlockCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    blocks: HashMap<u64, (RpcBlockConfig, UiConfirmedBlock)>,
+    /// Slots in the order they were added, oldest first
+    order: VecDeque<u64>,
+}
+
+impl BlockCache {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, entries: Mutex::new(CacheEntries::default()) }
+    }
+
+    /// Keep `block` of `slot`, fetched with `config`, for a later `take`
+    pub fn insert(&self, slot: u64, config: RpcBlockConfig, block: UiConfirmedBlock) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.blocks.insert(slot, (config, block)).is_none() {
+            entries.order.push_back(slot);
+        }
+        while entries.blocks.len() > self.capacity {
+            let Some(oldest) = entries.order.pop_front() else { break };
+            if entries.blocks.remove(&oldest).is_some() {
+                debug!("Block cache full, dropped slot {}", oldest);
+            }
+        }
+    }
+
+    /// The cached block of `slot`, removed from the cache, if it was fetched with the same
+    /// `config` (a block without rewards can't stand in for one with them)
+    pub fn take(&self, slot: u64, config: &RpcBlockConfig) -> Option<UiConfirmedBlock> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.blocks.get(&slot).is_none_or(|(cached, _)| cached != config) {
+            return None;
+        }
+        entries.order.retain(|cached| *cached != slot);
+        entries.blocks.remove(&slot).map(|(_, block)| block)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+
+    fn block(parent_slot: u64) -> UiConfirmedBlock {
+        serde_json::from_value(serde_json::json!({
+            "previousBlockhash": "", "blockhash": "", "parentSlot": parent_slot,
+            "transactions": [], "blockTime": null, "blockHeight": null
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_blocks_are_taken_once_and_oldest_dropped() {
+        let config = RpcBlockConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            transaction_details: Some(TransactionDetails::Full),
+            rewards: Some(false),
+            commitment: None,
+            max_supported_transaction_version: Some(0),
+        };
+        let cache = BlockCache::new(2);
+        for slot in [10, 11, 12] {
+            cache.insert(slot, config, block(slot - 1));
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.take(10, &config).is_none(), "oldest block is dropped");
+        assert!(cache.take(11, &RpcBlockConfig { rewards: Some(true), ..config }).is_none());
+        assert_eq!(cache.take(11, &config).unwrap().parent_slot, 10);
+        assert!(cache.take(11, &config).is_none(), "a block is handed out once");
+
+        let disabled = BlockCache::new(0);
+        disabled.insert(12, config, block(11));
+        assert!(disabled.is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Default slots per catch-up chunk (`--chunk-size`)
+pub const DEFAULT_CHUNK_SIZE: u64 = 10_000;
+
+/// Progress of a long catch-up, split into fixed-size chunks that each record how far they got
+/// and which slots failed. Saved after every batch, so a restart resumes at the exact next slot
+/// and still retries the failures of an interrupted chunk, instead of relying on the single
+/// slot checkpoint. The file is removed once every chunk is done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatchupLedger {
+    #[serde(skip)]
+    path: PathBuf,
+    pub chunk_size: u64,
+    pub chunks: Vec<CatchupChunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatchupChunk {
+    pub start: u64,
+    pub end: u64,
+    /// First slot of the chunk not processed yet; past `end` once the range is done
+    pub next_slot: u64,
+    /// Slots of the range that failed and are retried when the range is done
+    #[serde(default)]
+    pub failed_slots: Vec<u64>,
+}
+
+impl CatchupChunk {
+    pub fn is_done(&self) -> bool {
+        self.next_slot > self.end && self.failed_slots.is_empty()
+    }
+}
+
+impl CatchupLedger {
+    /// Split `start..=end` into chunks of `chunk_size` slots
+    pub fn plan(path: impl Into<PathBuf>, start: u64, end: u64, chunk_size: u64) -> Self {
+        let chunk_size = chunk_size.max(1);
+        let chunks = (start..=end)
+            .step_by(chunk_size as usize)
+            .map(|chunk_start| CatchupChunk {
+                start: chunk_start,
+                end: chunk_start.saturating_add(chunk_size - 1).min(end),
+                next_slot: chunk_start,
+                failed_slots: Vec::new(),
+            })
+            .collect();
+        Self { path: path.into(), chunk_size, chunks }
+    }
+
+    /// The ledger of an unfinished catch-up, if one was saved at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read catch-up ledger {}", path.display()))?;
+        let mut ledger: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse catch-up ledger {}", path.display()))?;
+        ledger.path = path.to_path_buf();
+        Ok(Some(ledger))
+    }
+
+    /// Written to a temporary file first, so a crash mid-write leaves the previous ledger intact
+    pub fn save(&self) -> Result<()> {
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))
+    }
+
+    /// Delete the ledger file once the catch-up is complete
+    pub fn remove(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", self.path.display()))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Index of the first unfinished chunk
+    pub fn current(&self) -> Option<usize> {
+        self.chunks.iter().position(|chunk| !chunk.is_done())
+    }
+
+    /// Where processing resumes: the next slot of the first chunk whose range isn't done
+    pub fn next_slot(&self) -> Option<u64> {
+        self.chunks.iter()
+            .find(|chunk| chunk.next_slot <= chunk.end)
+            .map(|chunk| chunk.next_slot)
+    }
+
+    /// Last slot of the chunk containing `slot`, so batches never straddle two chunks
+    pub fn chunk_end(&self, slot: u64) -> Option<u64> {
+        self.chunks.iter()
+            .find(|chunk| (chunk.start..=chunk.end).contains(&slot))
+            .map(|chunk| chunk.end)
+    }
+
+    pub fn end_slot(&self) -> Option<u64> {
+        self.chunks.last().map(|chunk| chunk.end)
+    }
+
+    /// Record that every slot before `next_slot` was processed, `failed` among them having
+    /// failed. Returns the indices of chunks whose range this completed.
+    pub fn record_progress(&mut self, next_slot: u64, failed: &[u64]) -> Vec<usize> {
+        let mut completed = Vec::new();
+        for (index, chunk) in self.chunks.iter_mut().enumerate() {
+            if chunk.start >= next_slot || chunk.next_slot > chunk.end {
+                continue;
+            }
+            chunk.failed_slots.extend(failed.iter().filter(|slot| (chunk.start..=chunk.end).contains(*slot)));
+            chunk.next_slot = next_slot.min(chunk.end + 1);
+            if chunk.next_slot > chunk.end {
+                completed.push(index);
+            }
+        }
+        completed
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.chunks.iter().all(CatchupChunk::is_done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_resumes_mid_chunk_and_keeps_failures() {
+        let path = std::env::temp_dir().join(format!("catchup_ledger_{}.json", uuid::Uuid::new_v4()));
+        let mut ledger = CatchupLedger::plan(&path, 100, 124, 10);
+        assert_eq!(ledger.chunks.len(), 3);
+        assert_eq!(ledger.chunks[2].end, 124);
+
+        assert!(ledger.record_progress(105, &[]).is_empty());
+        assert_eq!(ledger.record_progress(113, &[108, 111]), vec![0]);
+        ledger.save().unwrap();
+
+        let mut resumed = CatchupLedger::load(&path).unwrap().unwrap();
+        assert_eq!(resumed.next_slot(), Some(113));
+        assert_eq!(resumed.chunks[0].failed_slots, vec![108]);
+        assert_eq!(resumed.chunks[1].failed_slots, vec![111]);
+        assert_eq!(resumed.current(), Some(0), "failed slots keep the chunk open");
+        assert_eq!(resumed.chunk_end(113), Some(119));
+
+        resumed.chunks[0].failed_slots.clear();
+        resumed.chunks[1].failed_slots.clear();
+        assert_eq!(resumed.record_progress(125, &[]), vec![1, 2]);
+        assert!(resumed.is_complete());
+        resumed.remove().unwrap();
+        assert!(CatchupLedger::load(&path).unwrap().is_none());
+    }
+}
@@ -0,0 +1,396 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Checkpoint file of the `file` backend unless `CHECKPOINT_FILE` says otherwise
+pub const DEFAULT_CHECKPOINT_FILE: &str = "slot_checkpoint.json";
+/// Redis key / Postgres row of the checkpoint unless `CHECKPOINT_KEY` says otherwise
+pub const DEFAULT_CHECKPOINT_KEY: &str = "slot_checkpoint";
+
+/// How far live monitoring got, saved every checkpoint interval and resumed from on startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotCheckpoint {
+    pub last_processed_slot: u64,
+    /// Unix time of the save, in seconds
+    pub timestamp: u64,
+    pub total_slots_processed: u64,
+    pub total_matches_found: u64,
+}
+
+impl SlotCheckpoint {
+    pub fn new(slot: u64, total_slots: u64, total_matches: u64) -> Self {
+        Self {
+            last_processed_slot: slot,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            total_slots_processed: total_slots,
+            total_matches_found: total_matches,
+        }
+    }
+}
+
+/// Where the checkpoint lives. A shared backend (Redis, Postgres) lets a standby instance on
+/// another machine resume where the active one stopped, and survives losing the local disk.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Shown in logs and `status`
+    fn name(&self) -> &str;
+
+    /// The saved checkpoint, or None before the first save
+    async fn load(&self) -> Result<Option<SlotCheckpoint>>;
+
+    /// Replace the saved checkpoint. Shared stores keep a saved checkpoint that is further
+    /// along instead, so an instance that fell behind can't move another one's progress back.
+    async fn save(&self, checkpoint: &SlotCheckpoint) -> Result<()>;
+}
+
+/// Which `CheckpointStore` the monitor resumes from and saves to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CheckpointKind {
+    /// JSON file next to the binary (default)
+    #[default]
+    File,
+    Redis,
+    Postgres,
+}
+
+impl FromStr for CheckpointKind {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "file" => Ok(Self::File),
+            "redis" => Ok(Self::Redis),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            other => bail!("Invalid checkpoint backend '{}': expected file, redis or postgres", other),
+        }
+    }
+}
+
+/// `CHECKPOINT_BACKEND` (file, redis or postgres; default file), `CHECKPOINT_FILE`,
+/// `CHECKPOINT_URL` (Redis or Postgres URL) and `CHECKPOINT_KEY`
+#[derive(Debug, Clone)]
+pub struct CheckpointConfig {
+    pub backend: CheckpointKind,
+    pub file: PathBuf,
+    pub url: Option<String>,
+    /// Instances using the same key share one checkpoint
+    pub key: String,
+}
+
+impl CheckpointConfig {
+    pub fn from_env() -> Result<Self> {
+        let env = |key: &str| env::var(key).ok().filter(|v| !v.trim().is_empty());
+        Ok(Self {
+            backend: match env("CHECKPOINT_BACKEND") {
+                Some(backend) => backend.parse().context("Invalid CHECKPOINT_BACKEND")?,
+                None => CheckpointKind::File,
+            },
+            file: env("CHECKPOINT_FILE").unwrap_or_else(|| DEFAULT_CHECKPOINT_FILE.to_string()).into(),
+            url: env("CHECKPOINT_URL"),
+            key: env("CHECKPOINT_KEY").unwrap_or_else(|| DEFAULT_CHECKPOINT_KEY.to_string()),
+        })
+    }
+
+    pub async fn connect(&self) -> Result<Arc<dyn CheckpointStore>> {
+        let url = || {
+            self.url.as_deref().context("CHECKPOINT_BACKEND=redis or postgres needs CHECKPOINT_URL")
+        };
+
+        Ok(match self.backend {
+            CheckpointKind::File => Arc::new(FileCheckpointStore::new(&self.file)),
+            CheckpointKind::Redis => Arc::new(RedisCheckpointStore::connect(url()?, &self.key).await?),
+            CheckpointKind::Postgres => Arc::new(PostgresCheckpointStore::connect(url()?, &self.key).await?),
+        })
+    }
+}
+
+/// The checkpoint store configured in the environment
+pub async fn configured_checkpoint_store() -> Result<Arc<dyn CheckpointStore>> {
+    CheckpointConfig::from_env()?.connect().await
+}
+
+/// The checkpoint as a pretty-printed JSON file. Saves never leave a half-written file
+/// behind: the old file is copied to `<file>.bak`, then the new checkpoint goes to
+/// `<file>.tmp` and is renamed over it. The backup is loaded instead when the file is missing
+/// or unreadable.
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn backup_path(&self) -> PathBuf {
+        with_suffix(&self.path, "bak")
+    }
+
+    fn read(path: &Path) -> Result<Option<SlotCheckpoint>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checkpoint {}", path.display()))?;
+        let checkpoint = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse checkpoint {}", path.display()))?;
+        Ok(Some(checkpoint))
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn load(&self) -> Result<Option<SlotCheckpoint>> {
+        let error = match Self::read(&self.path) {
+            Ok(Some(checkpoint)) => return Ok(Some(checkpoint)),
+            Ok(None) => None,
+            Err(e) => Some(e),
+        };
+
+        // A crash in the middle of `save` can leave only the backup
+        let backup = self.backup_path();
+        match Self::read(&backup) {
+            Ok(Some(checkpoint)) => {
+                match &error {
+                    Some(e) => warn!("{:#}; resuming from backup {} (slot {})", e, backup.display(), checkpoint.last_processed_slot),
+                    None => warn!("Checkpoint {} is missing; resuming from backup {} (slot {})", self.path.display(), backup.display(), checkpoint.last_processed_slot),
+                }
+                Ok(Some(checkpoint))
+            },
+            Ok(None) => error.map_or(Ok(None), Err),
+            Err(backup_error) => Err(error.unwrap_or(backup_error)),
+        }
+    }
+
+    async fn save(&self, checkpoint: &SlotCheckpoint) -> Result<()> {
+        let json = serde_json::to_string_pretty(checkpoint)?;
+        // Copied rather than moved, so the checkpoint file exists throughout the save
+        if self.path.exists() {
+            std::fs::copy(&self.path, self.backup_path())
+                .with_context(|| format!("Failed to back up checkpoint {}", self.path.display()))?;
+        }
+        write_atomic(&self.path, json.as_bytes())
+            .with_context(|| format!("Failed to write checkpoint {}", self.path.display()))
+    }
+}
+
+/// `path` with `.suffix` appended to its file name
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Replace `path` with `contents` all at once: write and fsync `<path>.tmp`, then rename it
+/// over `path`. A crash leaves either the old or the new file, never a truncated one.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let tmp = with_suffix(path, "tmp");
+    let mut file = std::fs::File::create(&tmp)
+        .with_context(|| format!("Failed to create {}", tmp.display()))?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, path)
+        .with_context(|| format!("Failed to move {} into place", tmp.display()))?;
+
+    // Persist the rename itself; directories can't be opened for syncing on every platform
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    if let Ok(dir) = std::fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Sets KEYS[1] to ARGV[2] unless the checkpoint saved there is past slot ARGV[1]; 1 when set
+const REDIS_SAVE_SCRIPT: &str = r#"
+local saved = redis.call('GET', KEYS[1])
+if saved and cjson.decode(saved)['last_processed_slot'] > tonumber(ARGV[1]) then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[2])
+return 1
+"#;
+
+/// The checkpoint as JSON under one Redis key
+pub struct RedisCheckpointStore {
+    connection: redis::aio::MultiplexedConnection,
+    key: String,
+}
+
+impl RedisCheckpointStore {
+    pub async fn connect(url: &str, key: &str) -> Result<Self> {
+        let connection = redis::Client::open(url)
+            .context("Invalid Redis checkpoint URL")?
+            .get_multiplexed_async_connection()
+            .await
+            .context("Failed to connect to the Redis checkpoint store")?;
+        info!("Keeping the checkpoint in Redis key {}", key);
+        Ok(Self { connection, key: key.to_string() })
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for RedisCheckpointStore {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn load(&self) -> Result<Option<SlotCheckpoint>> {
+        let json: Option<String> = self.connection.clone().get(&self.key).await
+            .context("Failed to read the checkpoint from Redis")?;
+        json.map(|json| serde_json::from_str(&json).context("Invalid checkpoint in Redis"))
+            .transpose()
+    }
+
+    async fn save(&self, checkpoint: &SlotCheckpoint) -> Result<()> {
+        let json = serde_json::to_string(checkpoint)?;
+        let saved: i64 = redis::Script::new(REDIS_SAVE_SCRIPT)
+            .key(&self.key)
+            .arg(checkpoint.last_processed_slot)
+            .arg(json)
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .context("Failed to save the checkpoint to Redis")?;
+        if saved == 0 {
+            warn!("Redis checkpoint {} is past slot {}, kept it", self.key, checkpoint.last_processed_slot);
+        }
+        Ok(())
+    }
+}
+
+/// The checkpoint as a row of the `monitor_checkpoints` table, created by the migrations in
+/// `migrations/postgres`
+pub struct PostgresCheckpointStore {
+    pool: Pool<Postgres>,
+    key: String,
+}
+
+impl PostgresCheckpointStore {
+    /// Connect and run pending migrations
+    pub async fn connect(database_url: &str, key: &str) -> Result<Self> {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(2)
+            .connect(database_url)
+            .await
+            .context("Failed to connect to the PostgreSQL checkpoint database")?;
+        sqlx::migrate!("./migrations/postgres")
+            .run(&pool)
+            .await
+            .context("Failed to migrate the PostgreSQL checkpoint database")?;
+        info!("Keeping the checkpoint in PostgreSQL as {}", key);
+        Ok(Self { pool, key: key.to_string() })
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for PostgresCheckpointStore {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    async fn load(&self) -> Result<Option<SlotCheckpoint>> {
+        let row = sqlx::query(
+            "SELECT last_processed_slot, saved_at, total_slots_processed, total_matches_found
+             FROM monitor_checkpoints WHERE name = $1"
+        )
+        .bind(&self.key)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to read the checkpoint from PostgreSQL")?;
+
+        row.map(|row| -> Result<SlotCheckpoint> {
+            Ok(SlotCheckpoint {
+                last_processed_slot: row.try_get::<i64, _>("last_processed_slot")? as u64,
+                timestamp: row.try_get::<i64, _>("saved_at")? as u64,
+                total_slots_processed: row.try_get::<i64, _>("total_slots_processed")? as u64,
+                total_matches_found: row.try_get::<i64, _>("total_matches_found")? as u64,
+            })
+        })
+        .transpose()
+    }
+
+    async fn save(&self, checkpoint: &SlotCheckpoint) -> Result<()> {
+        let result = sqlx::query(
+            "INSERT INTO monitor_checkpoints
+                (name, last_processed_slot, saved_at, total_slots_processed, total_matches_found)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (name) DO UPDATE SET
+                last_processed_slot = EXCLUDED.last_processed_slot,
+                saved_at = EXCLUDED.saved_at,
+                total_slots_processed = EXCLUDED.total_slots_processed,
+                total_matches_found = EXCLUDED.total_matches_found
+             WHERE monitor_checkpoints.last_processed_slot <= EXCLUDED.last_processed_slot"
+        )
+        .bind(&self.key)
+        .bind(checkpoint.last_processed_slot as i64)
+        .bind(checkpoint.timestamp as i64)
+        .bind(checkpoint.total_slots_processed as i64)
+        .bind(checkpoint.total_matches_found as i64)
+        .execute(&self.pool)
+        .await
+        .context("Failed to save the checkpoint to PostgreSQL")?;
+        if result.rows_affected() == 0 {
+            warn!("PostgreSQL checkpoint {} is past slot {}, kept it", self.key, checkpoint.last_processed_slot);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_store_round_trips_checkpoint() {
+        let path = std::env::temp_dir().join(format!("checkpoint-{}.json", uuid::Uuid::new_v4()));
+        let store = FileCheckpointStore::new(&path);
+        assert!(store.load().await.unwrap().is_none());
+
+        let checkpoint = SlotCheckpoint::new(250_000_000, 1_200, 7);
+        store.save(&checkpoint).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(checkpoint));
+
+        assert_eq!("PostgreSQL".parse::<CheckpointKind>().unwrap(), CheckpointKind::Postgres);
+        assert!("s3".parse::<CheckpointKind>().is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_checkpoint_falls_back_to_backup() {
+        let path = std::env::temp_dir().join(format!("checkpoint-{}.json", uuid::Uuid::new_v4()));
+        let store = FileCheckpointStore::new(&path);
+        let first = SlotCheckpoint::new(100, 10, 1);
+        store.save(&first).await.unwrap();
+        store.save(&SlotCheckpoint::new(200, 20, 2)).await.unwrap();
+        assert!(!with_suffix(&path, "tmp").exists());
+
+        // A torn write of the main file
+        std::fs::write(&path, "{\"last_processed_slot\": 2").unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(first.clone()));
+
+        // The next save replaces the torn file and backs it up as it was
+        let third = SlotCheckpoint::new(300, 30, 3);
+        store.save(&third).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(third));
+        assert!(FileCheckpointStore::read(&store.backup_path()).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(store.backup_path()).unwrap();
+    }
+}
@@ -2,11 +2,12 @@ use anyhow::{Result, Context};
 use futures::stream::{FuturesUnordered, StreamExt};
 use std::sync::Arc;
 use tokio::sync::{mpsc, Semaphore};
-use tracing::{info, error, debug, warn};
-use std::time::Instant;
+use tracing::{info, debug, warn};
+use std::time::{Duration, Instant};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
-use crate::filtered_monitor::{FilteredTransactionMonitor, StoredTransaction};
+use crate::filtered_monitor::{FilteredTransactionMonitor, SlotTimings, StoredTransaction};
 
 #[derive(Debug, Clone)]
 pub struct SlotProcessingResult {
@@ -15,11 +16,59 @@ pub struct SlotProcessingResult {
     pub success: bool,
     pub error: Option<String>,
     pub processing_time_ms: u64,
+    /// Per-stage breakdown; stages a failed slot never reached stay zero
+    pub timings: SlotTimings,
+}
+
+/// Stage timings summed over every successful slot since the processor was created, enabled
+/// with `SLOT_PROFILE=true`. Only `Instant` reads per stage, so it is cheap enough to leave on.
+#[derive(Debug, Default)]
+pub struct StageProfile {
+    slots: u64,
+    totals: SlotTimings,
+}
+
+impl StageProfile {
+    pub fn record(&mut self, timings: &SlotTimings) {
+        self.slots += 1;
+        self.totals.fetch += timings.fetch;
+        self.totals.extract += timings.extract;
+        self.totals.lookups += timings.lookups;
+        self.totals.evaluate += timings.evaluate;
+        self.totals.actions += timings.actions;
+    }
+
+    pub fn slots(&self) -> u64 {
+        self.slots
+    }
+
+    /// Average per slot and share of the total for each stage, e.g.
+    /// `fetch 182.4ms (71%) | extract 35.0ms (14%) | ...`
+    pub fn summary(&self) -> String {
+        let stages = [
+            ("fetch", self.totals.fetch),
+            ("extract", self.totals.extract),
+            ("lookups", self.totals.lookups),
+            ("evaluate", self.totals.evaluate),
+            ("actions", self.totals.actions),
+        ];
+        let total: Duration = stages.iter().map(|(_, time)| *time).sum();
+        stages.iter()
+            .map(|(stage, time)| {
+                let average_ms = time.as_secs_f64() * 1000.0 / self.slots.max(1) as f64;
+                let share = if total.is_zero() { 0.0 } else { time.as_secs_f64() / total.as_secs_f64() * 100.0 };
+                format!("{} {:.1}ms ({:.0}%)", stage, average_ms, share)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
 }
 
 pub struct ConcurrentSlotProcessor {
     monitor: Arc<FilteredTransactionMonitor>,
     max_concurrent_slots: usize,
+    /// Set when `SLOT_PROFILE` is enabled
+    profile: Option<Arc<Mutex<StageProfile>>>,
 }
 
 impl ConcurrentSlotProcessor {
@@ -31,11 +80,22 @@ impl ConcurrentSlotProcessor {
         let max_concurrent = max_concurrent_slots.unwrap_or(20);
         info!("Initialized concurrent processor with max {} concurrent slots", max_concurrent);
         
+        let profile = std::env::var("SLOT_PROFILE")
+            .is_ok_and(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .then(|| Arc::new(Mutex::new(StageProfile::default())));
+        
         Self {
             monitor,
             max_concurrent_slots: max_concurrent,
+            profile,
         }
     }
+    
+    /// Average stage timings so far, when `SLOT_PROFILE` is enabled and a slot succeeded
+    pub fn stage_profile(&self) -> Option<String> {
+        let profile = self.profile.as_ref()?.lock().unwrap_or_else(|e| e.into_inner());
+        (profile.slots() > 0).then(|| profile.summary())
+    }
 
     /// Process multiple slots concurrently
     pub async fn process_slots(
@@ -43,46 +103,92 @@ impl ConcurrentSlotProcessor {
         start_slot: u64,
         end_slot: u64,
     ) -> Result<Vec<SlotProcessingResult>> {
-        let total_slots = end_slot - start_slot + 1;
+        self.process_slot_list((start_slot..=end_slot).collect()).await
+    }
+
+    /// Process an explicit list of slots concurrently, returning results in the order given
+    pub async fn process_slot_list(
+        &self,
+        slots: Vec<u64>,
+    ) -> Result<Vec<SlotProcessingResult>> {
+        let positions: HashMap<u64, usize> = slots.iter()
+            .enumerate()
+            .map(|(idx, &slot)| (slot, idx))
+            .collect();
+        
+        let (tx, mut rx) = mpsc::channel::<SlotProcessingResult>(100);
+        
+        let collect = async {
+            let mut results = Vec::new();
+            while let Some(result) = rx.recv().await {
+                results.push(result);
+            }
+            results
+        };
+        let (processed, mut results) = tokio::join!(self.process_slot_list_streaming(slots, tx), collect);
+        processed?;
+        
+        // Restore the caller's slot order
+        results.sort_by_key(|r| positions.get(&r.slot).copied().unwrap_or(usize::MAX));
+        
+        Ok(results)
+    }
+
+    /// Process slots concurrently, sending each result to `results` as soon as it completes.
+    ///
+    /// Results arrive in completion order. Dropping the receiver stops the run: slots already
+    /// being fetched finish, no new ones are started, and everything sent before that point has
+    /// already been handed to the caller. Returns the number of slots that were processed.
+    pub async fn process_slot_list_streaming(
+        &self,
+        slots: Vec<u64>,
+        results: mpsc::Sender<SlotProcessingResult>,
+    ) -> Result<usize> {
+        let total_slots = slots.len();
+        if total_slots == 0 {
+            return Ok(0);
+        }
         info!("🚀 Starting concurrent processing of {} slots ({}..{})", 
-            total_slots, start_slot, end_slot);
+            total_slots, slots[0], slots[total_slots - 1]);
         
         let start_time = Instant::now();
         let semaphore = Arc::new(Semaphore::new(self.max_concurrent_slots));
-        let (tx, mut rx) = mpsc::channel::<SlotProcessingResult>(100);
         
         // Create a pool of futures for processing slots
         let mut futures = FuturesUnordered::new();
         
-        // Statistics tracking
-        let mut slot_times = HashMap::new();
-        
-        for (idx, slot) in (start_slot..=end_slot).enumerate() {
+        for slot in slots {
             let semaphore = semaphore.clone();
-            let tx = tx.clone();
-            
-            // Round-robin fast monitor selection for better load distribution
+            let results = results.clone();
             let monitor = self.monitor.clone();
+            let profile = self.profile.clone();
             
             futures.push(async move {
-                let slot_start = Instant::now();
                 let _permit = semaphore.acquire().await.unwrap();
+                if results.is_closed() {
+                    return None;
+                }
                 
+                let slot_start = Instant::now();
                 debug!("Processing slot {}", slot);
                 
-                let result = match monitor.monitor_slot(slot).await {
-                    Ok(matched_transactions) => {
+                let result = match monitor.monitor_slot_timed(slot).await {
+                    Ok((matched_transactions, timings)) => {
                         let processing_time = slot_start.elapsed().as_millis() as u64;
                         if !matched_transactions.is_empty() {
                             info!("✅ Slot {} found {} matches in {}ms", 
                                 slot, matched_transactions.len(), processing_time);
                         }
+                        if let Some(profile) = &profile {
+                            profile.lock().unwrap_or_else(|e| e.into_inner()).record(&timings);
+                        }
                         SlotProcessingResult {
                             slot,
                             matched_transactions,
                             success: true,
                             error: None,
                             processing_time_ms: processing_time,
+                            timings,
                         }
                     }
                     Err(e) => {
@@ -94,42 +200,40 @@ impl ConcurrentSlotProcessor {
                             success: false,
                             error: Some(e.to_string()),
                             processing_time_ms: processing_time,
+                            timings: SlotTimings::default(),
                         }
                     }
                 };
                 
-                if let Err(e) = tx.send(result.clone()).await {
-                    error!("Failed to send result for slot {}: {}", slot, e);
+                let summary = (result.processing_time_ms, result.success, result.matched_transactions.len());
+                if results.send(result).await.is_err() {
+                    debug!("Result receiver dropped, discarding slot {}", slot);
+                    return None;
                 }
                 
-                (slot, result.processing_time_ms)
+                Some(summary)
             });
         }
         
-        // Drop the original sender to signal completion
-        drop(tx);
+        // Drop our own sender so the receiver closes once every slot is done
+        drop(results);
         
-        // Collect results and statistics
-        let mut results = Vec::new();
+        let mut times = Vec::with_capacity(total_slots);
         let mut processed_count = 0;
         let mut success_count = 0;
         let mut total_matches = 0;
         
-        // Process futures and collect timing stats
-        let processing_handle = tokio::spawn(async move {
-            while let Some((slot, time_ms)) = futures.next().await {
-                slot_times.insert(slot, time_ms);
-            }
-            slot_times
-        });
-        
-        // Collect results
-        while let Some(result) = rx.recv().await {
-            if result.success {
+        while let Some(outcome) = futures.next().await {
+            let Some((time_ms, success, matches)) = outcome else {
+                continue;
+            };
+            
+            times.push(time_ms);
+            processed_count += 1;
+            if success {
                 success_count += 1;
-                total_matches += result.matched_transactions.len();
+                total_matches += matches;
             }
-            processed_count += 1;
             
             // Progress update every 100 slots
             if processed_count % 100 == 0 {
@@ -137,20 +241,22 @@ impl ConcurrentSlotProcessor {
                 let rate = processed_count as f64 / elapsed.as_secs_f64();
                 info!("📊 Progress: {}/{} slots ({:.1} slots/sec)", 
                     processed_count, total_slots, rate);
+                if let Some(profile) = self.stage_profile() {
+                    info!("⏱️  Stages per slot: {}", profile);
+                }
             }
-            
-            results.push(result);
         }
         
-        // Get timing statistics
-        let slot_times = processing_handle.await.unwrap();
+        if processed_count < total_slots {
+            warn!("Stopped early: {}/{} slots processed before the receiver was dropped",
+                processed_count, total_slots);
+        }
         
         // Calculate statistics
         let total_duration = start_time.elapsed();
-        let avg_rate = total_slots as f64 / total_duration.as_secs_f64();
+        let avg_rate = processed_count as f64 / total_duration.as_secs_f64();
         
         // Calculate timing percentiles
-        let mut times: Vec<u64> = slot_times.values().copied().collect();
         times.sort_unstable();
         
         let p50 = times.get(times.len() / 2).copied().unwrap_or(0);
@@ -165,10 +271,10 @@ impl ConcurrentSlotProcessor {
         info!("   Total time: {:.2}s", total_duration.as_secs_f64());
         info!("   Average rate: {:.1} slots/sec", avg_rate);
         info!("   Slot processing times - P50: {}ms, P95: {}ms, P99: {}ms", p50, p95, p99);
+        if let Some(profile) = self.stage_profile() {
+            info!("   Stages per slot (run average): {}", profile);
+        }
         
-        // Sort results by slot number
-        results.sort_by_key(|r| r.slot);
-        
-        Ok(results)
+        Ok(processed_count)
     }
-}
\ No newline at end of file
+}
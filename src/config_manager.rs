@@ -1,7 +1,7 @@
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use tracing::{info, warn, error};
 use crate::filter_engine::{FilterConfig, Action, AlertSeverity};
@@ -12,6 +12,10 @@ pub struct MonitorConfig {
     pub filter: FilterConfig,
     #[serde(default)]
     pub alerts: Vec<String>,
+    /// Severity used for this monitor's alerts (default High); Discord alerts pick
+    /// `discord_url_<severity>` over `discord_url` when present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<AlertSeverity>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,7 +25,7 @@ pub struct AlertConfig {
     pub config: AlertConfigDetails,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AlertType {
     Discord,
@@ -51,11 +55,59 @@ pub struct MessageTemplate {
     pub body: String,
 }
 
+/// Per-filter message templates from `config/templates.json`, keyed by filter id. They are
+/// checked before the templates of the alerts a monitor references.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilterTemplates {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telegram: Option<MessageTemplate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discord: Option<MessageTemplate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack: Option<MessageTemplate>,
+    /// Subject and HTML (or plain text) body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<MessageTemplate>,
+}
+
+/// How long a storage collection keeps its records, from `config/retention.json` keyed by
+/// collection name (`"*"` applies to collections without their own entry). Both limits are
+/// optional and enforced whenever a record is added; the oldest records go first.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+}
+
+/// Chat channels notified whenever a record is stored into a collection, from
+/// `config/collection_routing.json` keyed by collection name. Applied on top of the storing
+/// filter's own actions, so filters sharing a collection don't each repeat the alert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionRoute {
+    pub channels: Vec<String>,
+    #[serde(default = "default_route_severity")]
+    pub severity: AlertSeverity,
+}
+
+fn default_route_severity() -> AlertSeverity {
+    AlertSeverity::High
+}
+
 pub struct ConfigManager {
     monitors_dir: PathBuf,
     alerts_dir: PathBuf,
+    templates_path: PathBuf,
+    retention_path: PathBuf,
+    routing_path: PathBuf,
     pub loaded_monitors: HashMap<String, MonitorConfig>,
     loaded_alerts: HashMap<String, AlertConfig>,
+    filter_templates: HashMap<String, FilterTemplates>,
+    retention: HashMap<String, RetentionPolicy>,
+    collection_routing: HashMap<String, CollectionRoute>,
+    monitor_sources: HashMap<String, Vec<PathBuf>>,
+    strict: bool,
 }
 
 impl ConfigManager {
@@ -64,15 +116,139 @@ impl ConfigManager {
         Self {
             monitors_dir: config_path.join("monitors"),
             alerts_dir: config_path.join("alerts"),
+            templates_path: config_path.join("templates.json"),
+            retention_path: config_path.join("retention.json"),
+            routing_path: config_path.join("collection_routing.json"),
             loaded_monitors: HashMap::new(),
             loaded_alerts: HashMap::new(),
+            filter_templates: HashMap::new(),
+            retention: HashMap::new(),
+            collection_routing: HashMap::new(),
+            monitor_sources: HashMap::new(),
+            strict: false,
         }
     }
     
+    /// Treat configuration problems such as duplicate filter ids as errors instead of warnings
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    
     /// Load all configurations from the config directories
     pub fn load_all(&mut self) -> Result<()> {
         self.load_alerts()?;
         self.load_monitors()?;
+        self.load_templates()?;
+        self.load_retention()?;
+        self.load_collection_routing()?;
+        Ok(())
+    }
+    
+    /// Load the optional per-collection retention policies from config/retention.json
+    fn load_retention(&mut self) -> Result<()> {
+        if !self.retention_path.exists() {
+            return Ok(());
+        }
+        
+        let content = std::fs::read_to_string(&self.retention_path)
+            .context("Failed to read retention file")?;
+        let retention: HashMap<String, RetentionPolicy> = match serde_json::from_str(&content) {
+            Ok(retention) => retention,
+            Err(e) if !self.strict => {
+                error!("Failed to parse retention policies from {:?}: {}", self.retention_path, e);
+                return Ok(());
+            },
+            Err(e) => return Err(e).context("Failed to parse retention JSON"),
+        };
+        
+        let collections = self.stored_collections();
+        for collection in retention.keys() {
+            if collection != "*" && !collections.contains(collection.as_str()) {
+                warn!("Retention defined for collection '{}' that no monitor stores to", collection);
+            }
+        }
+        
+        info!("Loaded retention policies for {} collections from {:?}", retention.len(), self.retention_path);
+        self.retention = retention;
+        Ok(())
+    }
+    
+    /// Per-collection retention policies from retention.json (empty when the file is absent)
+    pub fn retention(&self) -> &HashMap<String, RetentionPolicy> {
+        &self.retention
+    }
+    
+    /// Load the optional collection → channel routing from config/collection_routing.json
+    fn load_collection_routing(&mut self) -> Result<()> {
+        if !self.routing_path.exists() {
+            return Ok(());
+        }
+        
+        let content = std::fs::read_to_string(&self.routing_path)
+            .context("Failed to read collection routing file")?;
+        let routing: HashMap<String, CollectionRoute> = match serde_json::from_str(&content) {
+            Ok(routing) => routing,
+            Err(e) if !self.strict => {
+                error!("Failed to parse collection routing from {:?}: {}", self.routing_path, e);
+                return Ok(());
+            },
+            Err(e) => return Err(e).context("Failed to parse collection routing JSON"),
+        };
+        
+        let collections = self.stored_collections();
+        for collection in routing.keys() {
+            if !collections.contains(collection.as_str()) {
+                warn!("Routing defined for collection '{}' that no monitor stores to", collection);
+            }
+        }
+        
+        info!("Loaded notification routing for {} collections from {:?}", routing.len(), self.routing_path);
+        self.collection_routing = routing;
+        Ok(())
+    }
+    
+    /// Collection → channels routing from collection_routing.json (empty when the file is absent)
+    pub fn collection_routing(&self) -> &HashMap<String, CollectionRoute> {
+        &self.collection_routing
+    }
+    
+    /// Collections some loaded monitor stores into
+    fn stored_collections(&self) -> std::collections::HashSet<&str> {
+        self.loaded_monitors.values()
+            .flat_map(|monitor| monitor.filter.actions.iter())
+            .filter_map(|action| match action {
+                Action::Store { collection } => Some(collection.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+    
+    /// Load the optional per-filter templates from config/templates.json
+    fn load_templates(&mut self) -> Result<()> {
+        if !self.templates_path.exists() {
+            return Ok(());
+        }
+        
+        let content = std::fs::read_to_string(&self.templates_path)
+            .context("Failed to read templates file")?;
+        let templates: HashMap<String, FilterTemplates> = match serde_json::from_str(&content) {
+            Ok(templates) => templates,
+            Err(e) if !self.strict => {
+                error!("Failed to parse templates from {:?}: {}", self.templates_path, e);
+                return Ok(());
+            },
+            Err(e) => return Err(e).context("Failed to parse templates JSON"),
+        };
+        
+        for filter_id in templates.keys() {
+            if !self.loaded_monitors.contains_key(filter_id) {
+                warn!("Templates defined for unknown filter '{}' in {:?}", filter_id, self.templates_path);
+            }
+        }
+        
+        info!("Loaded templates for {} filters from {:?}", templates.len(), self.templates_path);
+        self.filter_templates = templates;
         Ok(())
     }
     
@@ -126,13 +302,15 @@ impl ConfigManager {
             return Err(anyhow::anyhow!("Monitors directory does not exist: {:?}", self.monitors_dir));
         }
         
-        let entries = std::fs::read_dir(&self.monitors_dir)
-            .context("Failed to read monitors directory")?;
+        let mut paths = std::fs::read_dir(&self.monitors_dir)
+            .context("Failed to read monitors directory")?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
         
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-            
+        // Sort so that the definition kept for a duplicate id doesn't depend on directory order
+        paths.sort();
+        
+        for path in paths {
             if path.extension().and_then(|s| s.to_str()) == Some("json") {
                 match self.load_monitor_file(&path) {
                     Ok(count) => info!("Loaded {} monitors from {:?}", count, path.file_name().unwrap()),
@@ -141,10 +319,40 @@ impl ConfigManager {
             }
         }
         
+        let duplicates = self.duplicate_monitor_ids();
+        for (id, sources) in &duplicates {
+            let files: Vec<String> = sources.iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            warn!(
+                "Filter id '{}' is defined {} times ({}); keeping the definition from {}",
+                id,
+                sources.len(),
+                files.join(", "),
+                files[0]
+            );
+        }
+        
+        if self.strict && !duplicates.is_empty() {
+            let ids: Vec<&str> = duplicates.iter().map(|(id, _)| id.as_str()).collect();
+            return Err(anyhow::anyhow!("Duplicate filter ids in monitor configs: {}", ids.join(", ")));
+        }
+        
         info!("Loaded {} total monitor configurations", self.loaded_monitors.len());
         Ok(())
     }
     
+    /// Filter ids defined more than once, with every file that defines them (in load order)
+    pub fn duplicate_monitor_ids(&self) -> Vec<(String, Vec<PathBuf>)> {
+        let mut duplicates: Vec<(String, Vec<PathBuf>)> = self.monitor_sources
+            .iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(id, sources)| (id.clone(), sources.clone()))
+            .collect();
+        duplicates.sort_by(|a, b| a.0.cmp(&b.0));
+        duplicates
+    }
+    
     /// Load monitors from a single JSON file
     fn load_monitor_file(&mut self, path: &Path) -> Result<usize> {
         let content = std::fs::read_to_string(path)
@@ -156,7 +364,14 @@ impl ConfigManager {
         let count = monitors.len();
         
         for monitor in monitors {
-            self.loaded_monitors.insert(monitor.filter.id.clone(), monitor);
+            let id = monitor.filter.id.clone();
+            self.monitor_sources
+                .entry(id.clone())
+                .or_insert_with(Vec::new)
+                .push(path.to_path_buf());
+            
+            // First definition wins; duplicates are reported once all files are loaded
+            self.loaded_monitors.entry(id).or_insert(monitor);
         }
         
         Ok(count)
@@ -172,7 +387,8 @@ impl ConfigManager {
             // Add alert actions based on configured alerts
             for alert_id in &monitor.alerts {
                 if let Some(alert_config) = self.loaded_alerts.get(alert_id) {
-                    let action = self.create_action_from_alert(alert_config)?;
+                    let severity = monitor.severity.clone().unwrap_or(AlertSeverity::High);
+                    let action = self.create_action_from_alert(alert_config, &severity)?;
                     filter.actions.push(action);
                 } else {
                     warn!("Alert '{}' referenced in monitor '{}' not found", alert_id, id);
@@ -186,16 +402,20 @@ impl ConfigManager {
     }
     
     /// Create an Action from an AlertConfig
-    fn create_action_from_alert(&self, alert: &AlertConfig) -> Result<Action> {
+    fn create_action_from_alert(&self, alert: &AlertConfig, severity: &AlertSeverity) -> Result<Action> {
         match alert.trigger_type {
             AlertType::Discord => Ok(Action::Webhook {
-                url: alert.config.connection.get("discord_url")
+                url: alert.config.connection.get(&format!("discord_url_{}", severity.as_str()))
+                    .or_else(|| alert.config.connection.get("discord_url"))
                     .and_then(|v| Some(v.value.clone()))
                     .ok_or_else(|| anyhow::anyhow!("Discord alert missing discord_url"))?,
                 method: "POST".to_string(),
+                headers: BTreeMap::new(),
+                payload_template: None,
+                secret: None,
             }),
             AlertType::Telegram => Ok(Action::Alert {
-                severity: AlertSeverity::High,
+                severity: severity.clone(),
                 channels: vec!["telegram".to_string()],
             }),
             AlertType::Webhook => Ok(Action::Webhook {
@@ -205,16 +425,23 @@ impl ConfigManager {
                 method: alert.config.connection.get("method")
                     .and_then(|v| Some(v.value.clone()))
                     .unwrap_or_else(|| "POST".to_string()),
+                // `header_<Name>` entries become request headers
+                headers: alert.config.connection.iter()
+                    .filter_map(|(key, v)| Some((key.strip_prefix("header_")?.to_string(), v.value.clone())))
+                    .collect(),
+                payload_template: alert.config.connection.get("payload_template").map(|v| v.value.clone()),
+                // `"type": "env"` names the environment variable holding the secret
+                secret: alert.config.connection.get("secret").map(|v| match v.value_type.as_str() {
+                    "env" => format!("env:{}", v.value),
+                    _ => v.value.clone(),
+                }),
+            }),
+            AlertType::Email => Ok(Action::Alert {
+                severity: severity.clone(),
+                channels: vec!["email".to_string()],
             }),
-            AlertType::Email => {
-                warn!("Email alerts not yet implemented");
-                Ok(Action::Log {
-                    level: "info".to_string(),
-                    message: format!("Email alert: {}", alert.name),
-                })
-            }
             AlertType::Slack => Ok(Action::Alert {
-                severity: AlertSeverity::High,
+                severity: severity.clone(),
                 channels: vec!["slack".to_string()],
             }),
         }
@@ -225,6 +452,27 @@ impl ConfigManager {
         self.loaded_alerts.get(alert_id)
     }
     
+    /// Template for a filter's alerts on `channel`: the filter's entry in templates.json if it
+    /// has one, else the first alert of that type the monitor references
+    pub fn find_template(&self, filter_id: &str, channel: AlertType) -> Option<&MessageTemplate> {
+        let from_templates = self.filter_templates.get(filter_id).and_then(|templates| match channel {
+            AlertType::Telegram => templates.telegram.as_ref(),
+            AlertType::Discord => templates.discord.as_ref(),
+            AlertType::Slack => templates.slack.as_ref(),
+            AlertType::Email => templates.email.as_ref(),
+            AlertType::Webhook => None,
+        });
+        if from_templates.is_some() {
+            return from_templates;
+        }
+        
+        let monitor = self.loaded_monitors.get(filter_id)?;
+        monitor.alerts.iter()
+            .filter_map(|alert_id| self.get_alert(alert_id))
+            .find(|alert| alert.trigger_type == channel)
+            .map(|alert| &alert.config.message)
+    }
+    
     /// Format a message template with transaction data
     pub fn format_message(
         template: &MessageTemplate,
@@ -248,6 +496,16 @@ pub fn format_message(
 
 /// Replace ${...} placeholders in template with actual values
 pub fn replace_placeholders(template: &str, data: &serde_json::Value) -> String {
+    fill_placeholders(template, data, false)
+}
+
+/// `replace_placeholders` for templates of JSON documents: string values are JSON-escaped, so
+/// a quoted placeholder stays one valid JSON string whatever quotes or newlines the value holds
+pub fn replace_json_placeholders(template: &str, data: &serde_json::Value) -> String {
+    fill_placeholders(template, data, true)
+}
+
+fn fill_placeholders(template: &str, data: &serde_json::Value, escape_strings: bool) -> String {
     let mut result = template.to_string();
     
     // Find all placeholders
@@ -260,6 +518,10 @@ pub fn replace_placeholders(template: &str, data: &serde_json::Value) -> String
                 // Don't format slot numbers or signatures
                 let formatted_value = if path_str == "slot" || path_str.contains("signature") {
                     value.to_string().trim_matches('"').to_string()
+                } else if let (true, Value::String(text)) = (escape_strings, value) {
+                    // The encoded string without its surrounding quotes
+                    let encoded = Value::String(text.clone()).to_string();
+                    encoded[1..encoded.len() - 1].to_string()
                 } else {
                     value_to_string(&value)
                 };
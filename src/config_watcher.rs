@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use notify::{recommended_watcher, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use crate::filtered_monitor::FilteredTransactionMonitor;
+
+/// Editors write a file in several steps; changes this close together trigger one reload
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Reloads a monitor's config whenever a file under `monitors/` or `alerts/` of its config
+/// directory changes. Stops watching when dropped.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// Watch the config directory the monitor was created from. The outcome of every reload
+    /// is logged and sent to the chat channels as a status message; a rejected config leaves
+    /// the running one in place.
+    pub fn spawn(monitor: Arc<FilteredTransactionMonitor>) -> Result<Self> {
+        let dir = monitor.config_dir()
+            .context("Config hot-reload needs a monitor created from a config directory")?
+            .to_path_buf();
+
+        let (changes_tx, mut changes) = mpsc::unbounded_channel();
+        let mut watcher = recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if is_content_change(&event.kind) && event.paths.iter().any(|path| is_config_file(path)) => {
+                let _ = changes_tx.send(());
+            },
+            Ok(_) => {},
+            Err(e) => warn!("Config watcher error: {}", e),
+        })?;
+        for subdir in ["monitors", "alerts"] {
+            let path = dir.join(subdir);
+            if path.exists() {
+                watcher.watch(&path, RecursiveMode::Recursive)
+                    .with_context(|| format!("Failed to watch {}", path.display()))?;
+            }
+        }
+        info!("Watching {} for config changes", dir.display());
+
+        let task = tokio::spawn(async move {
+            while changes.recv().await.is_some() {
+                tokio::time::sleep(DEBOUNCE).await;
+                while changes.try_recv().is_ok() {}
+
+                debug!("Config change detected, reloading");
+                // Parsing the config is blocking file IO
+                let reloading = monitor.clone();
+                let message = match tokio::task::spawn_blocking(move || reloading.reload_config()).await {
+                    Ok(Ok(active)) => format!("🔄 Config reloaded: {} active filters", active),
+                    Ok(Err(e)) => {
+                        warn!("Config reload rejected, keeping the running config: {:#}", e);
+                        format!("⚠️ Config reload rejected, keeping the running config: {:#}", e)
+                    },
+                    Err(e) => {
+                        warn!("Config reload panicked: {}", e);
+                        continue;
+                    },
+                };
+                monitor.send_status_message(&message).await;
+            }
+        });
+
+        Ok(Self { _watcher: watcher, task })
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn is_content_change(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+}
+
+/// Ignores editor swap and backup files
+fn is_config_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("json")
+}
@@ -1,22 +1,49 @@
 use anyhow::{Result, Context};
 use reqwest;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::{info, warn, error};
 use crate::transaction_extractor::ExtractedTransaction;
 use crate::config_manager::MessageTemplate;
+use crate::filter_engine::AlertSeverity;
+use crate::notifier_retry::post_json_with_retry;
 
 #[derive(Debug, Clone)]
 pub struct DiscordNotifier {
     webhook_url: String,
+    /// Per-severity webhook overrides, e.g. Critical alerts into #incidents
+    severity_webhook_urls: HashMap<AlertSeverity, String>,
     client: reqwest::Client,
 }
 
 #[derive(Debug, Serialize)]
-struct DiscordWebhookPayload {
+pub struct DiscordWebhookPayload {
     content: Option<String>,
     embeds: Vec<DiscordEmbed>,
 }
 
+impl DiscordWebhookPayload {
+    /// Hash of `url` plus the payload body, ignoring embed timestamps (they are the send time,
+    /// so two otherwise identical payloads would never compare equal)
+    pub fn dedup_key(&self, url: &str) -> u64 {
+        use std::hash::{Hash, Hasher};
+        
+        let mut body = serde_json::to_value(self).unwrap_or_default();
+        if let Some(embeds) = body.get_mut("embeds").and_then(|e| e.as_array_mut()) {
+            for embed in embeds {
+                if let Some(embed) = embed.as_object_mut() {
+                    embed.remove("timestamp");
+                }
+            }
+        }
+        
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        url.hash(&mut hasher);
+        body.to_string().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct DiscordEmbed {
     title: Option<String>,
@@ -43,30 +70,100 @@ impl DiscordNotifier {
     pub fn new(webhook_url: String) -> Self {
         Self {
             webhook_url,
+            severity_webhook_urls: HashMap::new(),
             client: reqwest::Client::new(),
         }
     }
     
+    /// Build from `DISCORD_WEBHOOK_URL` plus optional `DISCORD_WEBHOOK_URL_<SEVERITY>` overrides
+    /// (LOW, MEDIUM, HIGH, CRITICAL). Returns None when no default URL is set.
+    pub fn from_env() -> Option<Self> {
+        let webhook_url = std::env::var("DISCORD_WEBHOOK_URL").ok().filter(|url| !url.trim().is_empty())?;
+        let mut notifier = Self::new(webhook_url);
+        
+        for severity in AlertSeverity::ALL {
+            let key = format!("DISCORD_WEBHOOK_URL_{}", severity.as_str().to_uppercase());
+            if let Some(url) = std::env::var(&key).ok().filter(|url| !url.trim().is_empty()) {
+                notifier = notifier.with_severity_webhook(severity, url);
+            }
+        }
+        
+        Some(notifier)
+    }
+    
+    pub fn with_severity_webhook(mut self, severity: AlertSeverity, webhook_url: String) -> Self {
+        self.severity_webhook_urls.insert(severity, webhook_url);
+        self
+    }
+    
+    /// Webhook for a severity, falling back to the default URL
+    pub fn webhook_for(&self, severity: Option<&AlertSeverity>) -> &str {
+        severity
+            .and_then(|severity| self.severity_webhook_urls.get(severity))
+            .unwrap_or(&self.webhook_url)
+    }
+    
     pub async fn send_transaction_alert(
         &self,
         transaction: &ExtractedTransaction,
         filter_name: &str,
         template: Option<&MessageTemplate>,
     ) -> Result<()> {
-        let payload = if let Some(tmpl) = template {
+        self.send_severity_alert(transaction, filter_name, template, None).await
+    }
+    
+    /// Send an alert to the webhook configured for `severity`
+    pub async fn send_severity_alert(
+        &self,
+        transaction: &ExtractedTransaction,
+        filter_name: &str,
+        template: Option<&MessageTemplate>,
+        severity: Option<&AlertSeverity>,
+    ) -> Result<()> {
+        let payload = self.transaction_payload(transaction, filter_name, template);
+        self.send_webhook(self.webhook_for(severity), payload).await
+    }
+    
+    /// The payload `send_transaction_alert` would post, for callers that inspect it first
+    pub fn transaction_payload(
+        &self,
+        transaction: &ExtractedTransaction,
+        filter_name: &str,
+        template: Option<&MessageTemplate>,
+    ) -> DiscordWebhookPayload {
+        if let Some(tmpl) = template {
             self.create_payload_from_template(transaction, tmpl)
         } else {
             self.create_default_payload(transaction, filter_name)
+        }
+    }
+    
+    /// Post a prepared payload to the default webhook
+    pub async fn send_payload(&self, payload: DiscordWebhookPayload) -> Result<()> {
+        self.send_webhook(&self.webhook_url, payload).await
+    }
+    
+    /// Plain text message to the default webhook
+    pub async fn send_message(&self, content: &str) -> Result<()> {
+        let payload = DiscordWebhookPayload {
+            content: Some(content.to_string()),
+            embeds: Vec::new(),
         };
-        
-        self.send_webhook(payload).await
+        self.send_webhook(&self.webhook_url, payload).await
     }
     
-    async fn send_webhook(&self, payload: DiscordWebhookPayload) -> Result<()> {
-        let response = self.client
-            .post(&self.webhook_url)
-            .json(&payload)
-            .send()
+    /// Plain text message to the webhook configured for `severity`
+    pub async fn send_severity_message(&self, content: &str, severity: &AlertSeverity) -> Result<()> {
+        let payload = DiscordWebhookPayload {
+            content: Some(content.to_string()),
+            embeds: Vec::new(),
+        };
+        self.send_webhook(self.webhook_for(Some(severity)), payload).await
+    }
+    
+    #[tracing::instrument(name = "notify", skip_all, fields(channel = "discord"))]
+    async fn send_webhook(&self, webhook_url: &str, payload: DiscordWebhookPayload) -> Result<()> {
+        let response = post_json_with_retry(&self.client, webhook_url, &payload, "Discord")
             .await
             .context("Failed to send Discord webhook")?;
         
@@ -155,6 +252,14 @@ impl DiscordNotifier {
             }
         }
         
+        if let Some(leader) = &transaction.slot_leader {
+            fields.push(DiscordEmbedField {
+                name: "Leader".to_string(),
+                value: format!("`{}`", leader),
+                inline: false,
+            });
+        }
+        
         // Add signature
         fields.push(DiscordEmbedField {
             name: "Signature".to_string(),
@@ -0,0 +1,183 @@
+use anyhow::{bail, Context, Result};
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::collections::HashMap;
+use crate::filter_engine::{AlertSeverity, MatchedFilter};
+use crate::transaction_extractor::ExtractedTransaction;
+
+/// Sends alerts as HTML email over SMTP. Recipients can differ per severity, e.g. only
+/// Critical alerts to the on-call list.
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    recipients: Vec<Mailbox>,
+    /// Per-severity recipient overrides
+    severity_recipients: HashMap<AlertSeverity, Vec<Mailbox>>,
+}
+
+impl EmailNotifier {
+    pub fn new(transport: AsyncSmtpTransport<Tokio1Executor>, from: Mailbox, recipients: Vec<Mailbox>) -> Self {
+        Self { transport, from, recipients, severity_recipients: HashMap::new() }
+    }
+
+    /// `SMTP_HOST`, `SMTP_PORT`, `SMTP_USERNAME`/`SMTP_PASSWORD`, `SMTP_TLS` (starttls, tls or
+    /// none), `EMAIL_FROM`, and comma-separated `EMAIL_TO` plus optional `EMAIL_TO_<SEVERITY>`.
+    /// `None` when `SMTP_HOST` is not set.
+    pub fn from_env() -> Result<Option<Self>> {
+        let env = |key: &str| std::env::var(key).ok().filter(|value| !value.trim().is_empty());
+        let Some(host) = env("SMTP_HOST") else {
+            return Ok(None);
+        };
+        let host = host.trim();
+
+        let mut builder = match env("SMTP_TLS").as_deref().map(str::trim).unwrap_or("starttls") {
+            "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            other => bail!("Invalid SMTP_TLS '{}': expected starttls, tls or none", other),
+        };
+        if let Some(port) = env("SMTP_PORT") {
+            builder = builder.port(port.trim().parse().with_context(|| format!("Invalid SMTP_PORT '{}'", port))?);
+        }
+        if let Some(username) = env("SMTP_USERNAME") {
+            builder = builder.credentials(Credentials::new(username, env("SMTP_PASSWORD").unwrap_or_default()));
+        }
+
+        let from = env("EMAIL_FROM")
+            .context("EMAIL_FROM must be set when SMTP_HOST is")?
+            .parse()
+            .context("Invalid EMAIL_FROM address")?;
+        let recipients = parse_recipients(&env("EMAIL_TO").unwrap_or_default()).context("Invalid EMAIL_TO")?;
+        let mut notifier = Self::new(builder.build(), from, recipients);
+
+        for severity in AlertSeverity::ALL {
+            let key = format!("EMAIL_TO_{}", severity.as_str().to_uppercase());
+            if let Some(list) = env(&key) {
+                let recipients = parse_recipients(&list).with_context(|| format!("Invalid {}", key))?;
+                notifier = notifier.with_severity_recipients(severity, recipients);
+            }
+        }
+        if notifier.recipients.is_empty() && notifier.severity_recipients.is_empty() {
+            bail!("SMTP_HOST is set but neither EMAIL_TO nor an EMAIL_TO_<SEVERITY> list is");
+        }
+        Ok(Some(notifier))
+    }
+
+    pub fn with_severity_recipients(mut self, severity: AlertSeverity, recipients: Vec<Mailbox>) -> Self {
+        self.severity_recipients.insert(severity, recipients);
+        self
+    }
+
+    /// Recipients for a severity, falling back to the default list
+    pub fn recipients_for(&self, severity: Option<&AlertSeverity>) -> &[Mailbox] {
+        severity
+            .and_then(|severity| self.severity_recipients.get(severity))
+            .unwrap_or(&self.recipients)
+    }
+
+    /// Send `body` (HTML, or plain text that gets escaped) with `title` as the subject
+    pub async fn send_custom_alert(&self, title: &str, body: &str, severity: &AlertSeverity) -> Result<()> {
+        self.send(title, body, self.recipients_for(Some(severity))).await
+    }
+
+    /// The default alert for a match, for filters without an email template
+    pub async fn send_alert(
+        &self,
+        transaction: &ExtractedTransaction,
+        matched_filter: &MatchedFilter,
+        severity: &AlertSeverity,
+    ) -> Result<()> {
+        let subject = format!("🚨 {} - {:?}", matched_filter.filter_name, severity);
+        self.send_custom_alert(&subject, &default_alert_body(transaction, matched_filter), severity).await
+    }
+
+    /// Plain message to the default recipients, e.g. from `test-notify`
+    pub async fn send_message(&self, subject: &str, text: &str) -> Result<()> {
+        self.send(subject, text, &self.recipients).await
+    }
+
+    async fn send(&self, subject: &str, body: &str, recipients: &[Mailbox]) -> Result<()> {
+        if recipients.is_empty() {
+            return Ok(());
+        }
+
+        let mut message = Message::builder().from(self.from.clone()).subject(subject);
+        for recipient in recipients {
+            message = message.to(recipient.clone());
+        }
+        let message = message.multipart(MultiPart::alternative_plain_html(body.to_string(), render_html(subject, body)))?;
+        self.transport.send(message).await.context("Failed to send email")?;
+        Ok(())
+    }
+}
+
+fn parse_recipients(list: &str) -> Result<Vec<Mailbox>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| address.parse().with_context(|| format!("Invalid email address '{}'", address)))
+        .collect()
+}
+
+fn default_alert_body(transaction: &ExtractedTransaction, matched_filter: &MatchedFilter) -> String {
+    let mut lines = vec![
+        format!("Filter: {}", matched_filter.filter_name),
+        format!("Signature: {}", transaction.signature),
+        format!("Slot: {}", transaction.slot),
+        format!("Success: {}", transaction.success),
+        format!("Fee: {} lamports", transaction.fee),
+    ];
+    if let Some(change) = transaction.token_balance_changes.first() {
+        lines.push(format!("Token change: {} {}", change.change, change.mint));
+    }
+    if let Some(leader) = &transaction.slot_leader {
+        lines.push(format!("Leader: {}", leader));
+    }
+    for (key, value) in &matched_filter.context {
+        lines.push(format!("{}: {}", key, value));
+    }
+    lines.push(format!("https://solscan.io/tx/{}", transaction.signature));
+    lines.join("\n")
+}
+
+/// Bodies that already contain markup are used as they are; plain text is escaped and keeps its line breaks
+fn render_html(title: &str, body: &str) -> String {
+    let looks_like_html = body.contains("</") || body.contains("<br");
+    let content = if looks_like_html {
+        body.to_string()
+    } else {
+        escape_html(body).replace('\n', "<br>\n")
+    };
+    format!(
+        "<html><body style=\"font-family: sans-serif\"><h2>{}</h2><div>{}</div></body></html>",
+        escape_html(title), content
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recipients_fall_back_to_default_list() {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous("localhost").build();
+        let notifier = EmailNotifier::new(
+            transport,
+            "monitor@example.com".parse().unwrap(),
+            parse_recipients("team@example.com, ops@example.com").unwrap(),
+        ).with_severity_recipients(AlertSeverity::Critical, parse_recipients("oncall@example.com").unwrap());
+
+        assert_eq!(notifier.recipients_for(Some(&AlertSeverity::Critical)).len(), 1);
+        assert_eq!(notifier.recipients_for(Some(&AlertSeverity::Low)).len(), 2);
+        assert!(parse_recipients("not an address").is_err());
+        assert_eq!(
+            render_html("Mint", "a < b\nnext"),
+            "<html><body style=\"font-family: sans-serif\"><h2>Mint</h2><div>a &lt; b<br>\nnext</div></body></html>"
+        );
+    }
+}
@@ -1,6 +1,11 @@
 use anyhow::{Result, Context};
 use solana_client::rpc_config::RpcBlockConfig;
-use solana_transaction_status::TransactionDetails;
+use solana_transaction_status::{
+    TransactionDetails,
+    UiConfirmedBlock,
+    UiTransactionEncoding,
+    option_serializer::OptionSerializer,
+};
 use std::sync::Arc;
 use tracing::{info, debug};
 
@@ -15,6 +20,8 @@ pub struct FastSlotMonitor {
     transaction_extractor: Arc<TransactionExtractor>,
     filter_processor: Arc<ParallelFilterProcessor>,
     monitor: Arc<FilteredTransactionMonitor>,
+    /// When set, slots whose token balances never mention this mint skip full extraction
+    focus_mint: Option<String>,
 }
 
 impl FastSlotMonitor {
@@ -24,22 +31,35 @@ impl FastSlotMonitor {
     ) -> Self {
         let rpc_client = Arc::new(RpcClientWithFailover::new(rpc_url.clone()));
         let transaction_extractor = Arc::new(TransactionExtractor::new(rpc_url));
-        let filter_processor = Arc::new(ParallelFilterProcessor::new(monitor.filter_engine.clone()));
+        let filter_processor = Arc::new(ParallelFilterProcessor::new(monitor.filter_engine()));
         
         Self {
             rpc_client,
             transaction_extractor,
             filter_processor,
             monitor,
+            focus_mint: None,
         }
     }
 
+    /// Only extract slots where some transaction's pre or post token balances include `mint`.
+    /// Filters on other mints or on programs alone won't see the skipped slots, so use this
+    /// when every active filter is about the one token (e.g. YU monitoring).
+    pub fn with_focus_mint(mut self, mint: String) -> Self {
+        self.focus_mint = Some(mint);
+        self
+    }
+
     /// Fast check if a slot might contain matching transactions
     pub async fn quick_check_slot(&self, slot: u64) -> Result<bool> {
-        // First just get the slot metadata without transaction details
+        if let Some(mint) = &self.focus_mint {
+            return self.slot_mentions_mint(slot, mint).await;
+        }
+        
+        // Signatures only: enough to count transactions without fetching them
         let config = RpcBlockConfig {
-            encoding: Some(solana_transaction_status::UiTransactionEncoding::Base64),
-            transaction_details: Some(TransactionDetails::None),
+            encoding: Some(UiTransactionEncoding::Base64),
+            transaction_details: Some(TransactionDetails::Signatures),
             rewards: Some(false),
             commitment: None,
             max_supported_transaction_version: Some(0),
@@ -47,7 +67,7 @@ impl FastSlotMonitor {
         
         match self.rpc_client.get_block_with_config(slot, config).await {
             Ok(block) => {
-                let tx_count = block.transactions.map(|txs| txs.len()).unwrap_or(0);
+                let tx_count = block.signatures.map(|sigs| sigs.len()).unwrap_or(0);
                 debug!("Slot {} has {} transactions", slot, tx_count);
                 Ok(tx_count > 0)
             }
@@ -58,11 +78,32 @@ impl FastSlotMonitor {
         }
     }
 
+    /// Cheap pass for a focus mint: `accounts` details carry the token balances but no
+    /// instructions or logs, so this is much lighter than the `JsonParsed` extraction
+    async fn slot_mentions_mint(&self, slot: u64, mint: &str) -> Result<bool> {
+        let config = RpcBlockConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            transaction_details: Some(TransactionDetails::Accounts),
+            rewards: Some(false),
+            commitment: None,
+            max_supported_transaction_version: Some(0),
+        };
+        
+        match self.rpc_client.get_block_with_config(slot, config).await {
+            Ok(block) => {
+                let found = block_mentions_mint(&block, mint);
+                debug!("Slot {} {} focus mint {}", slot, if found { "contains" } else { "does not contain" }, mint);
+                Ok(found)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
     /// Process a slot with optimizations
     pub async fn process_slot(&self, slot: u64) -> Result<Vec<StoredTransaction>> {
         // Quick check first
         if !self.quick_check_slot(slot).await? {
-            debug!("Slot {} is empty, skipping", slot);
+            debug!("Slot {} is empty or has no focus mint balances, skipping", slot);
             return Ok(vec![]);
         }
         
@@ -81,6 +122,7 @@ impl FastSlotMonitor {
         // Convert to StoredTransaction format
         let stored: Vec<StoredTransaction> = matched.into_iter()
             .map(|(tx, filters)| StoredTransaction {
+                slot_leader: tx.slot_leader.clone(),
                 transaction: tx,
                 matched_filters: filters.iter().map(|f| f.filter_id.clone()).collect(),
                 stored_at: chrono::Utc::now(),
@@ -94,4 +136,16 @@ impl FastSlotMonitor {
         
         Ok(stored)
     }
-}
\ No newline at end of file
+}
+
+/// Whether any transaction in the block has a pre or post token balance for `mint`; a mint or
+/// burn that empties or creates an account still shows on one side
+fn block_mentions_mint(block: &UiConfirmedBlock, mint: &str) -> bool {
+    let has_mint = |balances: &OptionSerializer<Vec<solana_transaction_status::UiTransactionTokenBalance>>| {
+        matches!(balances, OptionSerializer::Some(balances) if balances.iter().any(|b| b.mint == mint))
+    };
+    
+    block.transactions.iter().flatten()
+        .filter_map(|tx| tx.meta.as_ref())
+        .any(|meta| has_mint(&meta.pre_token_balances) || has_mint(&meta.post_token_balances))
+}
@@ -1,8 +1,14 @@
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use regex::Regex;
 use tracing::{info, warn, debug};
-use crate::transaction_extractor::ExtractedTransaction;
+use crate::filter_state::{
+    SeenAccountSet, SignerActivity, TransferActivity, VolumeWindow, VolumeWindows,
+    DEFAULT_TRANSFER_VELOCITY_MAX_ACCOUNTS, DEFAULT_VOLUME_WINDOWS_FILE,
+};
+use crate::transaction_extractor::{ExtractedTransaction, TokenBalanceChange, COMPUTE_BUDGET_PROGRAM_ID};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterConfig {
@@ -11,16 +17,63 @@ pub struct FilterConfig {
     pub enabled: bool,
     pub conditions: ConditionSet,
     pub actions: Vec<Action>,
+    /// Minimum seconds between chat alerts for this filter; Store and generic Webhook actions still
+    /// run, and the matches held back are summed up in one digest alert when the cooldown ends
+    #[serde(default, alias = "cooldown_seconds", skip_serializing_if = "Option::is_none")]
+    pub cooldown_secs: Option<u64>,
+    /// Filters are evaluated from the highest priority down; equal priorities keep config order
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
 }
 
+fn is_zero(priority: &i32) -> bool {
+    *priority == 0
+}
+
+/// Groups are ANDed together. An omitted group or an empty `all_of`/`none_of` imposes no
+/// constraint. An empty `any_of` can never be satisfied, so `FilterEngine::try_new` (used by
+/// every config loader) rejects a config that contains one; `FilterEngine::new` only warns.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConditionSet {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub all_of: Option<Vec<Condition>>,
+    pub all_of: Option<Vec<ConditionEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub any_of: Option<Vec<Condition>>,
+    pub any_of: Option<Vec<ConditionEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub none_of: Option<Vec<Condition>>,
+    pub none_of: Option<Vec<ConditionEntry>>,
+}
+
+impl ConditionSet {
+    /// Iterate over every enabled condition in all_of, any_of and none_of
+    pub fn iter(&self) -> impl Iterator<Item = &Condition> {
+        self.all_of.iter().flatten()
+            .chain(self.any_of.iter().flatten())
+            .chain(self.none_of.iter().flatten())
+            .filter(|entry| entry.is_enabled())
+            .map(|entry| &entry.condition)
+    }
+}
+
+/// A condition plus an optional `enabled` flag, so a condition can be switched off in config
+/// without deleting it. The flag sits next to `type` in the JSON object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionEntry {
+    #[serde(flatten)]
+    pub condition: Condition,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+impl ConditionEntry {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+}
+
+impl From<Condition> for ConditionEntry {
+    fn from(condition: Condition) -> Self {
+        Self { condition, enabled: None }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +82,11 @@ pub enum Condition {
     ProgramInvoked {
         program_id: String,
     },
+    /// Any of `program_ids` invoked, top-level or inner; shorthand for an `any_of` of
+    /// `ProgramInvoked` conditions, e.g. a set of DEXes or bridges
+    ProgramInvokedAny {
+        program_ids: Vec<String>,
+    },
     TokenTransfer {
         mint: Option<String>,
         operator: ComparisonOperator,
@@ -52,21 +110,275 @@ pub enum Condition {
     TransactionStatus {
         success: bool,
     },
+    /// A failed transaction that was going to touch `mint`. Failed transactions have no balance
+    /// changes, so this looks at intent instead: the mint among the account keys, in a parsed
+    /// instruction's `mint` field (top-level or CPI), or in the token balances the RPC reports.
+    /// Successful transactions never match.
+    FailedWithMint {
+        mint: String,
+    },
+    /// `amount` is in lamports unless `unit` is `Sol`
     FeeAmount {
         operator: ComparisonOperator,
-        amount: u64,
+        amount: f64,
+        #[serde(default)]
+        unit: FeeUnit,
     },
     InstructionCount {
         operator: ComparisonOperator,
         count: usize,
     },
+    /// Number of distinct programs invoked, top-level and CPI, e.g. aggregator routes that pass
+    /// through several DEXes. Each program counts once however often it is called.
+    DistinctProgramCount {
+        operator: ComparisonOperator,
+        count: usize,
+    },
+    /// `child_program` invoked via CPI from a top-level instruction of `parent_program`, e.g.
+    /// Jupiter routing through Raydium. Inner instruction sets are matched to their top-level
+    /// instruction by index; nesting depth inside the set doesn't matter.
+    CpiFrom {
+        parent_program: String,
+        child_program: String,
+    },
+    /// Compares the heuristic serialized size (`approx_size_bytes`); the hard limit is 1232 bytes
+    TransactionSizeBytes {
+        operator: ComparisonOperator,
+        bytes: usize,
+    },
+    /// Message version: `0` for versioned transactions, omitted (or null) for legacy ones
+    TransactionVersion {
+        #[serde(default)]
+        version: Option<u8>,
+    },
     AccountInvolved {
         account: String,
     },
+    /// Some writable account of the transaction is owned by `owner_program` (e.g. a pool state
+    /// owned by an AMM). Owners are looked up over RPC, batched and cached, and only when an
+    /// enabled filter uses this condition.
+    AccountOwnedBy {
+        owner_program: String,
+    },
+    /// The transaction's slot was produced by one of `leaders` (validator identities). Leaders
+    /// are fetched with `getSlotLeaders` and cached, and only when an enabled filter uses this
+    /// condition.
+    SlotLeader {
+        leaders: Vec<String>,
+    },
     LogContains {
         pattern: String,
         case_sensitive: bool,
     },
+    /// Some log line matches the regular expression `pattern` (`regex` crate syntax; prefix
+    /// `(?i)` for any case), e.g. `Instruction: (MintTo|Burn) amount: (\d+)`. Compiled once when
+    /// the engine is built; an invalid pattern is reported then and never matches. Capture
+    /// groups of the first matching line are added to the match context as `log_<name>` or
+    /// `log_<index>`.
+    LogMatchesRegex {
+        pattern: String,
+    },
+    /// An instruction of `program_id` (top-level or CPI) whose raw data starts with the Anchor
+    /// discriminator of `anchor_instruction` (`sha256("global:<name>")[..8]`) followed by
+    /// `discriminator`, written in `encoding`; e.g. a withdraw or set_authority of a program the
+    /// RPC can't parse. With neither set, any raw instruction of the program matches.
+    /// Instructions the RPC parsed (System, SPL Token, ...) carry no raw data and never match.
+    InstructionData {
+        program_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminator: Option<String>,
+        #[serde(default)]
+        encoding: DataEncoding,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor_instruction: Option<String>,
+    },
+    /// Byte-level match on base64 `Program data:` log lines (e.g. Anchor events). The payload is
+    /// not decoded into fields; `decoded_contains_hex` must appear somewhere in the raw bytes.
+    /// Without a pattern any data line matches; `program_id` restricts to lines emitted by that program.
+    ProgramDataContains {
+        #[serde(default)]
+        program_id: Option<String>,
+        #[serde(default)]
+        decoded_contains_hex: Option<String>,
+    },
+    /// A bridge program is invoked (top-level or CPI) alongside a token balance change of at least
+    /// `amount`. `programs` defaults to `BRIDGE_PROGRAM_IDS` or, if unset, the LayerZero programs.
+    BridgeTransfer {
+        #[serde(default)]
+        mint: Option<String>,
+        operator: ComparisonOperator,
+        amount: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        programs: Option<Vec<String>>,
+    },
+    /// Balance increase of at least `amount` on a `mint` token account owned by `program_id`, e.g. a
+    /// deposit into a lending or staking vault. Only the token account's direct owner is compared, so
+    /// vaults held by a PDA need the PDA address as `program_id`.
+    DepositToProgram {
+        mint: String,
+        program_id: String,
+        operator: ComparisonOperator,
+        amount: f64,
+    },
+    /// Compute unit limit requested with a top-level ComputeBudget `setComputeUnitLimit`
+    /// instruction (the max is 1,400,000). Transactions that don't set a limit never match.
+    ComputeUnitLimit {
+        operator: ComparisonOperator,
+        units: u32,
+    },
+    /// The transaction both mints and burns `mint`, detected from parsed SPL Token
+    /// `mintTo`/`burn` instructions (top-level or CPI). Balance changes aren't used, since a
+    /// mint and burn on the same account can cancel out.
+    MintAndBurn {
+        mint: String,
+    },
+    /// The fee payer has signed at least `min_transactions` transactions touching `mint`
+    /// (this one included) within the last `window_secs` of block time, e.g. a bot hammering the
+    /// token. Fires on every transaction past the threshold, so pair it with `cooldown_secs`.
+    /// The signer and count are added to the match context.
+    RepeatedSigner {
+        mint: String,
+        min_transactions: usize,
+        window_secs: u64,
+    },
+    /// A token account of `mint` changed by this transaction has moved the token both in and out
+    /// within the last `window_secs` of block time (this transaction included), with inflow plus
+    /// outflow of at least `min_turnover`, e.g. wash-trading-style cycling. The account and its
+    /// turnover are added to the match context.
+    TransferVelocity {
+        mint: String,
+        min_turnover: f64,
+        window_secs: u64,
+    },
+    /// Total `measure` of `mint` across all transactions in the last `window_slots` slots or
+    /// `window_secs` of block time (exactly one of them), this transaction included, e.g. "more
+    /// than 50M YU minted in the last 1000 slots". With `account`, only balance changes of that
+    /// token account (or of token accounts it owns) count. Only transactions adding to the total
+    /// are checked, so quiet transactions later in the window don't fire again. Windows are
+    /// saved with the filter state, so they survive restarts. The total is added to the match context.
+    CumulativeVolume {
+        mint: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account: Option<String>,
+        measure: VolumeMeasure,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        window_slots: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        window_secs: Option<u64>,
+        operator: ComparisonOperator,
+        amount: f64,
+    },
+    /// Net lamport change of `account` in this transaction, with the fee added back when the
+    /// account pays it, so only transfers count. `direction` picks outflows, inflows or either;
+    /// `lamports` is compared against the size of the change.
+    AccountSolFlow {
+        account: String,
+        direction: FlowDirection,
+        operator: ComparisonOperator,
+        lamports: u64,
+    },
+    /// Amount of `mint` moved in this transaction as a percentage of its current total supply,
+    /// e.g. "more than 5% of supply moved in one go". The moved amount is the larger of the total
+    /// increase and total decrease across the mint's token accounts, so a transfer counts once
+    /// and a mint or burn counts its size. Supply comes from `getTokenSupply`, cached and
+    /// refreshed every `MINT_SUPPLY_TTL_SECS`; while it is unknown the condition never matches.
+    SupplyPercentage {
+        mint: String,
+        operator: ComparisonOperator,
+        percent: f64,
+    },
+    /// A reward in the transaction's block (leader fees, rent, staking or voting) to `pubkey`, or to
+    /// any account when unset, compared by signed lamports (rent collected is negative). Rewards
+    /// belong to the block, so every transaction of that block matches; pair it with other
+    /// conditions or a cooldown. Blocks are fetched with rewards only when a filter uses this.
+    Reward {
+        #[serde(default)]
+        pubkey: Option<String>,
+        operator: ComparisonOperator,
+        lamports: i64,
+    },
+    /// Several mints moving in the same transaction, e.g. YU and its pair token in a swap or LP
+    /// deposit rather than a one-sided transfer. A mint counts when one of its balance changes
+    /// satisfies `operator`/`amount` (by size, like `TokenTransfer`); every listed mint must count
+    /// with `require_all` (the default), at least one without it.
+    MultiMint {
+        mints: Vec<String>,
+        #[serde(default = "default_require_all")]
+        require_all: bool,
+        operator: ComparisonOperator,
+        amount: f64,
+    },
+    /// Edge trigger on renewed activity: the filter matches only if its other conditions haven't
+    /// held for `quiet_secs` of block time before this transaction (or ever, since startup), e.g.
+    /// "the first YU mint after an hour without one". Every transaction the other conditions
+    /// match restarts the quiet period, whether or not it fired. Only meaningful in `all_of`.
+    TimeSinceLastMatch {
+        quiet_secs: u64,
+    },
+    /// The fee payer of a transaction touching `mint` was first seen transacting with it within
+    /// the last `within_slots` slots (a payer never seen before counts as first seen now), e.g.
+    /// wallets funded just in time to interact with the token. First-seen slots live in the
+    /// persistent seen-set, which only covers what the monitor processed, so every payer looks
+    /// fresh until it has warmed up. The payer and first-seen slot are added to the match context.
+    FreshFeePayer {
+        mint: String,
+        within_slots: u64,
+    },
+    /// Mint of `mint` where at least `min_new_recipients` receiving accounts have never
+    /// held the mint before, according to the persistent seen-set
+    MintToNewAccounts {
+        mint: String,
+        min_new_recipients: usize,
+    },
+}
+
+fn default_require_all() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum FeeUnit {
+    #[default]
+    Lamports,
+    Sol,
+}
+
+impl FeeUnit {
+    /// Threshold in lamports, rounded to the nearest whole lamport
+    pub fn to_lamports(self, amount: f64) -> u64 {
+        match self {
+            FeeUnit::Lamports => amount.round() as u64,
+            FeeUnit::Sol => (amount * 1_000_000_000.0).round() as u64,
+        }
+    }
+}
+
+/// What a `CumulativeVolume` condition adds up per transaction, in token units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VolumeMeasure {
+    /// Net increase of the summed balances (supply minted, or an account's net inflow)
+    Minted,
+    /// Net decrease of the summed balances (supply burned, or an account's net outflow)
+    Burned,
+    /// The larger of summed increases and summed decreases, so a transfer counts once
+    Moved,
+}
+
+/// How `InstructionData::discriminator` is written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum DataEncoding {
+    /// Like instruction data in RPC responses
+    #[default]
+    Base58,
+    Base64,
+    Hex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FlowDirection {
+    In,
+    Out,
+    Either,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +404,17 @@ pub enum Action {
     Webhook {
         url: String,
         method: String,
+        /// Extra request headers, e.g. an API key (generic webhooks only)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        headers: BTreeMap<String, String>,
+        /// JSON body with `${...}` placeholders; by default the body carries the filter and the
+        /// whole transaction (generic webhooks only)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload_template: Option<String>,
+        /// HMAC-SHA256 key for the `X-Signature-256` header; `env:NAME` reads it from the
+        /// environment (generic webhooks only)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        secret: Option<String>,
     },
     Log {
         level: String,
@@ -99,7 +422,7 @@ pub enum Action {
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AlertSeverity {
     Low,
     Medium,
@@ -107,64 +430,713 @@ pub enum AlertSeverity {
     Critical,
 }
 
+impl AlertSeverity {
+    pub const ALL: [AlertSeverity; 4] = [
+        AlertSeverity::Low,
+        AlertSeverity::Medium,
+        AlertSeverity::High,
+        AlertSeverity::Critical,
+    ];
+    
+    /// Lowercase name used in config keys such as `discord_url_critical`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Low => "low",
+            AlertSeverity::Medium => "medium",
+            AlertSeverity::High => "high",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// LayerZero bridge programs (old and new deployments)
+pub const LAYERZERO_BRIDGE_PROGRAMS: [&str; 2] = [
+    "6doghB248px58JSSwG4qejQ46kFMW4AMj7vzJnWZHNZn",
+    "3fCoNdCEoEcERakCPM17NjLE9AocA86LMwRRWDpzjLVh",
+];
+
 pub struct FilterEngine {
     filters: Vec<FilterConfig>,
+    /// Programs used by BridgeTransfer conditions that don't list their own
+    default_bridge_programs: Vec<String>,
+    tracked_mints: HashSet<String>,
+    /// Mints used by `FreshFeePayer`; fee payers are only recorded for transactions touching these
+    fee_payer_mints: HashSet<String>,
+    seen_accounts: Option<Mutex<SeenAccountSet>>,
+    /// Whether any enabled filter uses `AccountOwnedBy`, so owners need resolving
+    needs_account_owners: bool,
+    /// Mints used by `SupplyPercentage` conditions, whose supply needs resolving
+    supply_mints: Vec<String>,
+    /// Whether any enabled filter uses `Reward`, so blocks need fetching with rewards
+    needs_rewards: bool,
+    /// Whether any enabled filter uses `SlotLeader`, so leaders are resolved before evaluation
+    needs_slot_leaders: bool,
+    /// Longest `RepeatedSigner` window per mint; only these mints' signers are tracked
+    signer_windows: HashMap<String, u64>,
+    signer_activity: Option<Mutex<SignerActivity>>,
+    /// Longest `TransferVelocity` window per mint; only these mints' token accounts are tracked
+    velocity_windows: HashMap<String, u64>,
+    transfer_activity: Option<Mutex<TransferActivity>>,
+    /// Volumes tracked for `CumulativeVolume`, by volume key
+    volume_specs: HashMap<String, VolumeSpec>,
+    volume_windows: Option<Mutex<VolumeWindows>>,
+    /// `TimeSinceLastMatch` quiet period per filter id (the longest, if a filter has several)
+    quiet_periods: HashMap<String, u64>,
+    /// Block time each gated filter's other conditions last held, by filter id
+    last_activity: Mutex<HashMap<String, i64>>,
+    /// Compiled `LogMatchesRegex` patterns, by pattern; invalid patterns are left out
+    log_regexes: HashMap<String, Regex>,
+    /// Stop evaluating a transaction at its first (highest-priority) match
+    first_match_only: bool,
+}
+
+/// One volume tracked for `CumulativeVolume` conditions, with every window they use on it
+#[derive(Debug)]
+struct VolumeSpec {
+    mint: String,
+    account: Option<String>,
+    measure: VolumeMeasure,
+    windows: Vec<VolumeWindow>,
+}
+
+/// Key of a tracked volume in `VolumeWindows`
+fn volume_key(mint: &str, account: Option<&str>, measure: VolumeMeasure) -> String {
+    match account {
+        Some(account) => format!("{}:{}:{:?}", mint, account, measure),
+        None => format!("{}:{:?}", mint, measure),
+    }
+}
+
+/// The single window of a `CumulativeVolume` condition; None when both or neither are set
+fn volume_window(window_slots: Option<u64>, window_secs: Option<u64>) -> Option<VolumeWindow> {
+    match (window_slots, window_secs) {
+        (Some(slots), None) => Some(VolumeWindow::Slots(slots)),
+        (None, Some(secs)) => Some(VolumeWindow::Secs(secs)),
+        _ => None,
+    }
 }
 
 impl FilterEngine {
+    /// Like `new`, but rejects filters with an empty `any_of` instead of warning about them
+    pub fn try_new(filters: Vec<FilterConfig>) -> Result<Self> {
+        let empty_any_of = find_empty_any_of(&filters);
+        if !empty_any_of.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Filters with an empty any_of (remove the key or add a condition): {}",
+                empty_any_of.join(", ")
+            ));
+        }
+        Ok(Self::new(filters))
+    }
+    
     pub fn new(filters: Vec<FilterConfig>) -> Self {
-        let enabled_filters: Vec<FilterConfig> = filters
+        for id in find_duplicate_filter_ids(&filters) {
+            warn!("Filter id '{}' is defined more than once; keeping the first enabled definition", id);
+        }
+        for id in find_empty_any_of(&filters) {
+            warn!("Filter '{}' has an empty any_of and will never match", id);
+        }
+        
+        // Disabled definitions are dropped first, so they can't shadow an enabled one
+        let mut seen_ids = std::collections::HashSet::new();
+        let mut enabled_filters: Vec<FilterConfig> = filters
             .into_iter()
             .filter(|f| f.enabled)
+            .filter(|f| seen_ids.insert(f.id.clone()))
+            .collect();
+        // Stable, so filters of equal priority keep their config order
+        enabled_filters.sort_by_key(|f| std::cmp::Reverse(f.priority));
+        
+        for filter in &enabled_filters {
+            for cond in filter.conditions.iter() {
+                if let Condition::InstructionData { discriminator: Some(discriminator), encoding, .. } = cond {
+                    if decode_data(discriminator, *encoding).is_none() {
+                        warn!(
+                            "Filter '{}': InstructionData discriminator '{}' is not valid {:?} and will never match",
+                            filter.id, discriminator, encoding
+                        );
+                    }
+                }
+                if let Condition::ProgramDataContains { decoded_contains_hex: Some(hex), .. } = cond {
+                    if decode_hex(hex).is_none() {
+                        warn!(
+                            "Filter '{}': ProgramDataContains pattern '{}' is not valid hex and will never match",
+                            filter.id, hex
+                        );
+                    }
+                }
+            }
+        }
+        
+        let mut log_regexes = HashMap::new();
+        for filter in &enabled_filters {
+            for cond in filter.conditions.iter() {
+                let Condition::LogMatchesRegex { pattern } = cond else {
+                    continue;
+                };
+                if log_regexes.contains_key(pattern) {
+                    continue;
+                }
+                match Regex::new(pattern) {
+                    Ok(regex) => {
+                        log_regexes.insert(pattern.clone(), regex);
+                    },
+                    Err(e) => warn!(
+                        "Filter '{}': LogMatchesRegex pattern '{}' is invalid and will never match: {}",
+                        filter.id, pattern, e
+                    ),
+                }
+            }
+        }
+        
+        // Only mints used by first-seen conditions are tracked, which keeps the seen-set small
+        let tracked_mints: HashSet<String> = enabled_filters.iter()
+            .flat_map(|f| f.conditions.iter())
+            .filter_map(|cond| match cond {
+                Condition::MintToNewAccounts { mint, .. } => Some(mint.clone()),
+                _ => None,
+            })
+            .collect();
+        let fee_payer_mints: HashSet<String> = enabled_filters.iter()
+            .flat_map(|f| f.conditions.iter())
+            .filter_map(|cond| match cond {
+                Condition::FreshFeePayer { mint, .. } => Some(mint.clone()),
+                _ => None,
+            })
             .collect();
         
+        let mut signer_windows: HashMap<String, u64> = HashMap::new();
+        for cond in enabled_filters.iter().flat_map(|f| f.conditions.iter()) {
+            if let Condition::RepeatedSigner { mint, window_secs, .. } = cond {
+                let window = signer_windows.entry(mint.clone()).or_insert(0);
+                *window = (*window).max(*window_secs);
+            }
+        }
+        let signer_activity = signer_windows.values().max()
+            .map(|max_window| Mutex::new(SignerActivity::new(*max_window)));
+        
+        let mut velocity_windows: HashMap<String, u64> = HashMap::new();
+        for cond in enabled_filters.iter().flat_map(|f| f.conditions.iter()) {
+            if let Condition::TransferVelocity { mint, window_secs, .. } = cond {
+                let window = velocity_windows.entry(mint.clone()).or_insert(0);
+                *window = (*window).max(*window_secs);
+            }
+        }
+        let transfer_activity = velocity_windows.values().max().map(|max_window| {
+            let max_accounts = std::env::var("TRANSFER_VELOCITY_MAX_ACCOUNTS").ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .filter(|max| *max > 0)
+                .unwrap_or(DEFAULT_TRANSFER_VELOCITY_MAX_ACCOUNTS);
+            Mutex::new(TransferActivity::new(*max_window, max_accounts))
+        });
+        
+        let mut volume_specs: HashMap<String, VolumeSpec> = HashMap::new();
+        for filter in &enabled_filters {
+            for cond in filter.conditions.iter() {
+                let Condition::CumulativeVolume { mint, account, measure, window_slots, window_secs, .. } = cond else {
+                    continue;
+                };
+                let Some(window) = volume_window(*window_slots, *window_secs) else {
+                    warn!("Filter '{}': CumulativeVolume needs exactly one of window_slots and window_secs; it will never match", filter.id);
+                    continue;
+                };
+                let spec = volume_specs.entry(volume_key(mint, account.as_deref(), *measure))
+                    .or_insert_with(|| VolumeSpec { mint: mint.clone(), account: account.clone(), measure: *measure, windows: Vec::new() });
+                if !spec.windows.contains(&window) {
+                    spec.windows.push(window);
+                }
+            }
+        }
+        let volume_windows = if volume_specs.is_empty() {
+            None
+        } else {
+            let path = std::env::var("VOLUME_WINDOWS_FILE").ok()
+                .filter(|path| !path.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_VOLUME_WINDOWS_FILE.to_string());
+            match VolumeWindows::load(&path) {
+                Ok(windows) => Some(Mutex::new(windows)),
+                Err(e) => {
+                    warn!("Failed to load volume windows from {}: {} (tracking in memory only)", path, e);
+                    Some(Mutex::new(VolumeWindows::default()))
+                }
+            }
+        };
+        
+        let mut quiet_periods: HashMap<String, u64> = HashMap::new();
+        for filter in &enabled_filters {
+            let gates = filter.conditions.all_of.iter().flatten()
+                .filter(|entry| entry.is_enabled())
+                .filter_map(|entry| match entry.condition {
+                    Condition::TimeSinceLastMatch { quiet_secs } => Some(quiet_secs),
+                    _ => None,
+                });
+            if let Some(quiet_secs) = gates.max() {
+                quiet_periods.insert(filter.id.clone(), quiet_secs);
+            }
+            
+            let misplaced = filter.conditions.any_of.iter()
+                .chain(filter.conditions.none_of.iter())
+                .flatten()
+                .any(|entry| matches!(entry.condition, Condition::TimeSinceLastMatch { .. }));
+            if misplaced {
+                warn!("Filter '{}': TimeSinceLastMatch only gates in all_of; elsewhere it is always true", filter.id);
+            }
+        }
+        
+        let needs_account_owners = enabled_filters.iter()
+            .flat_map(|f| f.conditions.iter())
+            .any(|cond| matches!(cond, Condition::AccountOwnedBy { .. }));
+        
+        let needs_rewards = enabled_filters.iter()
+            .flat_map(|f| f.conditions.iter())
+            .any(|cond| matches!(cond, Condition::Reward { .. }));
+        
+        let needs_slot_leaders = enabled_filters.iter()
+            .flat_map(|f| f.conditions.iter())
+            .any(|cond| matches!(cond, Condition::SlotLeader { .. }));
+        
+        let supply_mints: Vec<String> = enabled_filters.iter()
+            .flat_map(|f| f.conditions.iter())
+            .filter_map(|cond| match cond {
+                Condition::SupplyPercentage { mint, .. } => Some(mint.clone()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        
+        let seen_accounts = if tracked_mints.is_empty() && fee_payer_mints.is_empty() {
+            None
+        } else {
+            let path = std::env::var("SEEN_ACCOUNTS_FILE")
+                .unwrap_or_else(|_| "seen_accounts.json".to_string());
+            match SeenAccountSet::load(&path) {
+                Ok(set) => Some(Mutex::new(set)),
+                Err(e) => {
+                    warn!("Failed to load seen accounts from {}: {} (tracking in memory only)", path, e);
+                    Some(Mutex::new(SeenAccountSet::default()))
+                }
+            }
+        };
+        
         info!("Initialized filter engine with {} active filters", enabled_filters.len());
-        Self { filters: enabled_filters }
+        let default_bridge_programs = std::env::var("BRIDGE_PROGRAM_IDS")
+            .ok()
+            .map(|ids| {
+                ids.split(',')
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|ids| !ids.is_empty())
+            .unwrap_or_else(|| LAYERZERO_BRIDGE_PROGRAMS.iter().map(|id| id.to_string()).collect());
+        
+        Self {
+            filters: enabled_filters,
+            default_bridge_programs,
+            tracked_mints,
+            fee_payer_mints,
+            seen_accounts,
+            needs_account_owners,
+            supply_mints,
+            needs_rewards,
+            needs_slot_leaders,
+            signer_windows,
+            signer_activity,
+            velocity_windows,
+            transfer_activity,
+            volume_specs,
+            volume_windows,
+            quiet_periods,
+            last_activity: Mutex::new(HashMap::new()),
+            log_regexes,
+            first_match_only: std::env::var("FIRST_MATCH_ONLY")
+                .is_ok_and(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")),
+        }
+    }
+    
+    /// Return at most one match per transaction: the highest-priority filter that matches.
+    /// Later filters are not evaluated at all. Defaults to `FIRST_MATCH_ONLY`.
+    pub fn with_first_match_only(mut self, first_match_only: bool) -> Self {
+        self.first_match_only = first_match_only;
+        self
     }
     
-    pub fn from_json_file(path: &str) -> Result<Self> {
+    pub fn first_match_only(&self) -> bool {
+        self.first_match_only
+    }
+    
+    /// True when some enabled filter uses `AccountOwnedBy`; callers resolve
+    /// `ExtractedTransaction::account_owners` before evaluating only in that case
+    pub fn needs_account_owners(&self) -> bool {
+        self.needs_account_owners
+    }
+    
+    /// True when some enabled filter uses `Reward`; extractors then request block rewards
+    pub fn needs_rewards(&self) -> bool {
+        self.needs_rewards
+    }
+    
+    /// True when some enabled filter uses `SlotLeader`; callers fill in
+    /// `ExtractedTransaction::slot_leader` before evaluating only in that case
+    pub fn needs_slot_leaders(&self) -> bool {
+        self.needs_slot_leaders
+    }
+    
+    /// Mints whose supply `SupplyPercentage` conditions compare against; callers fill in
+    /// `ExtractedTransaction::mint_supplies` for these before evaluating
+    pub fn supply_mints(&self) -> &[String] {
+        &self.supply_mints
+    }
+    
+    pub fn from_json_file(path: &str, strict: bool) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .context("Failed to read filter configuration file")?;
         let filters: Vec<FilterConfig> = serde_json::from_str(&content)
             .context("Failed to parse filter configuration")?;
-        Ok(Self::new(filters))
+        
+        let duplicates = find_duplicate_filter_ids(&filters);
+        if strict && !duplicates.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Duplicate filter ids in {}: {}",
+                path,
+                duplicates.join(", ")
+            ));
+        }
+        
+        Self::try_new(filters).with_context(|| format!("Invalid filter configuration {}", path))
+    }
+    
+    /// Active filters (enabled, first enabled definition of each id)
+    pub fn filters(&self) -> &[FilterConfig] {
+        &self.filters
     }
     
+    /// Traced as a debug-level `filter_evaluate` span, one per transaction
+    #[tracing::instrument(name = "filter_evaluate", level = "debug", skip_all, fields(signature = %transaction.signature))]
     pub fn evaluate_transaction(&self, transaction: &ExtractedTransaction) -> Vec<MatchedFilter> {
         let mut matched_filters = Vec::new();
         
         for filter in &self.filters {
-            if self.evaluate_condition_set(&filter.conditions, transaction) {
+            if self.evaluate_condition_set(&filter.conditions, transaction) && self.quiet_period_elapsed(&filter.id, transaction) {
                 debug!("Transaction {} matched filter: {}", transaction.signature, filter.name);
                 matched_filters.push(MatchedFilter {
                     filter_id: filter.id.clone(),
                     filter_name: filter.name.clone(),
                     actions: filter.actions.clone(),
+                    cooldown_secs: filter.cooldown_secs,
+                    context: self.match_context(&filter.conditions, transaction),
                 });
+                if self.first_match_only {
+                    break;
+                }
             }
         }
         
+        // Recorded after every filter ran so they all see the same "before" state
+        self.record_seen_accounts(transaction);
+        self.record_signer_activity(transaction);
+        self.record_transfer_activity(transaction);
+        self.record_volumes(transaction);
+        
         matched_filters
     }
     
+    /// Per-filter, per-condition evaluation results for diagnostics. Read-only: unlike
+    /// `evaluate_transaction` it does not update the seen-set or `TimeSinceLastMatch` activity,
+    /// but a quiet period that hasn't passed shows as that condition failing.
+    pub fn explain_transaction(&self, transaction: &ExtractedTransaction) -> Vec<FilterExplanation> {
+        self.filters.iter()
+            .map(|filter| {
+                let quiet_period_over = self.quiet_period_would_elapse(&filter.id, transaction);
+                let groups = [
+                    ("all_of", &filter.conditions.all_of),
+                    ("any_of", &filter.conditions.any_of),
+                    ("none_of", &filter.conditions.none_of),
+                ];
+                let conditions = groups.iter()
+                    .flat_map(|(group, entries)| entries.iter().flatten().map(move |entry| (*group, entry)))
+                    .filter(|(_, entry)| entry.is_enabled())
+                    .map(|(group, entry)| ConditionExplanation {
+                        group,
+                        condition: entry.condition.clone(),
+                        matched: match entry.condition {
+                            // Only gates in all_of, see `quiet_periods`
+                            Condition::TimeSinceLastMatch { .. } if group == "all_of" => quiet_period_over,
+                            _ => self.evaluate_condition(&entry.condition, transaction),
+                        },
+                    })
+                    .collect();
+                
+                FilterExplanation {
+                    filter_id: filter.id.clone(),
+                    filter_name: filter.name.clone(),
+                    matched: self.evaluate_condition_set(&filter.conditions, transaction) && quiet_period_over,
+                    conditions,
+                }
+            })
+            .collect()
+    }
+    
+    /// For filters gated by `TimeSinceLastMatch`, whether the quiet period passed since the last
+    /// transaction their other conditions matched; records this one as the new last activity.
+    /// Filters without a gate always pass. Slots processed out of order never move it back.
+    fn quiet_period_elapsed(&self, filter_id: &str, transaction: &ExtractedTransaction) -> bool {
+        let Some(quiet_secs) = self.quiet_periods.get(filter_id) else {
+            return true;
+        };
+        
+        let at = block_time_or_now(transaction);
+        let mut last_activity = self.last_activity.lock().unwrap();
+        let previous = last_activity.insert(filter_id.to_string(), at);
+        if let Some(previous) = previous.filter(|previous| *previous > at) {
+            last_activity.insert(filter_id.to_string(), previous);
+        }
+        is_quiet_period_over(previous, at, *quiet_secs)
+    }
+    
+    /// `quiet_period_elapsed` without recording the transaction, for `explain_transaction`
+    fn quiet_period_would_elapse(&self, filter_id: &str, transaction: &ExtractedTransaction) -> bool {
+        let Some(quiet_secs) = self.quiet_periods.get(filter_id) else {
+            return true;
+        };
+        let previous = self.last_activity.lock().unwrap().get(filter_id).copied();
+        is_quiet_period_over(previous, block_time_or_now(transaction), *quiet_secs)
+    }
+    
+    /// Add holders of tracked mints and fee payers of `FreshFeePayer` mints to the seen-set
+    fn record_seen_accounts(&self, transaction: &ExtractedTransaction) {
+        let Some(seen_accounts) = &self.seen_accounts else {
+            return;
+        };
+        
+        let mut seen = seen_accounts.lock().unwrap();
+        for change in &transaction.token_balance_changes {
+            if self.tracked_mints.contains(&change.mint) {
+                seen.insert(&change.mint, &change.account);
+            }
+        }
+        
+        if let Some(payer) = fee_payer(transaction) {
+            for mint in &self.fee_payer_mints {
+                if touches_mint(transaction, mint) {
+                    seen.record_fee_payer(mint, payer, transaction.slot);
+                }
+            }
+        }
+    }
+    
+    /// Slot the fee payer was first seen with `mint` (this slot if never before), or None when
+    /// the transaction doesn't touch the mint
+    fn fee_payer_first_seen(&self, transaction: &ExtractedTransaction, mint: &str) -> Option<u64> {
+        let seen_accounts = self.seen_accounts.as_ref()?;
+        let payer = fee_payer(transaction)?;
+        if !touches_mint(transaction, mint) {
+            return None;
+        }
+        
+        let first_seen = seen_accounts.lock().unwrap().fee_payer_first_seen(mint, payer);
+        Some(first_seen.map_or(transaction.slot, |slot| slot.min(transaction.slot)))
+    }
+    
+    /// Add this transaction to the fee payer's activity for each tracked mint it touches
+    fn record_signer_activity(&self, transaction: &ExtractedTransaction) {
+        let (Some(signer_activity), Some(signer)) = (&self.signer_activity, fee_payer(transaction)) else {
+            return;
+        };
+        
+        let at = block_time_or_now(transaction);
+        let mut activity = signer_activity.lock().unwrap();
+        for mint in self.signer_windows.keys() {
+            if touches_mint(transaction, mint) {
+                activity.record(mint, signer, at);
+            }
+        }
+    }
+    
+    /// Fee payer's transactions on `mint` within the window, counting this one, or None when
+    /// the transaction doesn't touch the mint
+    fn repeated_signer_count(&self, transaction: &ExtractedTransaction, mint: &str, window_secs: u64) -> Option<usize> {
+        let signer_activity = self.signer_activity.as_ref()?;
+        let signer = fee_payer(transaction)?;
+        if !touches_mint(transaction, mint) {
+            return None;
+        }
+        
+        let previous = signer_activity.lock().unwrap()
+            .count(mint, signer, block_time_or_now(transaction), window_secs);
+        Some(previous + 1)
+    }
+    
+    /// Add this transaction's balance changes of `TransferVelocity` mints to their accounts' flows
+    fn record_transfer_activity(&self, transaction: &ExtractedTransaction) {
+        let Some(transfer_activity) = &self.transfer_activity else {
+            return;
+        };
+        
+        let at = block_time_or_now(transaction);
+        let mut activity = transfer_activity.lock().unwrap();
+        for change in &transaction.token_balance_changes {
+            if change.change != 0.0 && self.velocity_windows.contains_key(&change.mint) {
+                activity.record(&change.mint, &change.account, at, change.change);
+            }
+        }
+    }
+    
+    /// Add this transaction's amount to every tracked volume it contributes to
+    fn record_volumes(&self, transaction: &ExtractedTransaction) {
+        let Some(volume_windows) = &self.volume_windows else {
+            return;
+        };
+        
+        let at = block_time_or_now(transaction);
+        let mut windows = volume_windows.lock().unwrap();
+        for (key, spec) in &self.volume_specs {
+            let amount = transaction_volume(transaction, &spec.mint, spec.account.as_deref(), spec.measure);
+            if amount > 0.0 {
+                windows.record(key, transaction.slot, at, amount, &spec.windows);
+            }
+        }
+    }
+    
+    /// Total volume within the window, counting this transaction, or None when the condition's
+    /// window is invalid or the transaction adds nothing to the volume
+    fn cumulative_volume(
+        &self,
+        transaction: &ExtractedTransaction,
+        mint: &str,
+        account: Option<&str>,
+        measure: VolumeMeasure,
+        window: Option<VolumeWindow>,
+    ) -> Option<f64> {
+        let volume_windows = self.volume_windows.as_ref()?;
+        let window = window?;
+        let amount = transaction_volume(transaction, mint, account, measure);
+        if amount <= 0.0 {
+            return None;
+        }
+        
+        let previous = volume_windows.lock().unwrap()
+            .total(&volume_key(mint, account, measure), window, transaction.slot, block_time_or_now(transaction));
+        Some(previous + amount)
+    }
+    
+    /// First token account of `mint` changed by the transaction that cycled the token both ways
+    /// within the window with at least `min_turnover`, counting this transaction, and its turnover
+    fn velocity_account(&self, transaction: &ExtractedTransaction, mint: &str, min_turnover: f64, window_secs: u64) -> Option<(String, f64)> {
+        let transfer_activity = self.transfer_activity.as_ref()?;
+        let at = block_time_or_now(transaction);
+        let activity = transfer_activity.lock().unwrap();
+        
+        transaction.token_balance_changes.iter()
+            .filter(|change| change.mint == mint && change.change != 0.0)
+            .find_map(|change| {
+                let (mut inflow, mut outflow) = activity.turnover(mint, &change.account, at, window_secs);
+                if change.change > 0.0 { inflow += change.change } else { outflow -= change.change }
+                let turnover = inflow + outflow;
+                (inflow > 0.0 && outflow > 0.0 && turnover >= min_turnover)
+                    .then(|| (change.account.clone(), turnover))
+            })
+    }
+    
+    /// Details from stateful conditions that held for a matched filter, for alerts
+    fn match_context(&self, conditions: &ConditionSet, transaction: &ExtractedTransaction) -> BTreeMap<String, String> {
+        let mut context = BTreeMap::new();
+        for cond in conditions.iter() {
+            if let Condition::RepeatedSigner { mint, min_transactions, window_secs } = cond {
+                let count = self.repeated_signer_count(transaction, mint, *window_secs).unwrap_or(0);
+                if count >= *min_transactions {
+                    if let Some(signer) = fee_payer(transaction) {
+                        context.insert("repeated_signer".to_string(), signer.to_string());
+                        context.insert("signer_transactions".to_string(), format!("{} in {}s", count, window_secs));
+                    }
+                }
+            }
+            if let Condition::TransferVelocity { mint, min_turnover, window_secs } = cond {
+                if let Some((account, turnover)) = self.velocity_account(transaction, mint, *min_turnover, *window_secs) {
+                    context.insert("velocity_account".to_string(), account);
+                    context.insert("turnover".to_string(), format!("{} in {}s", turnover, window_secs));
+                }
+            }
+            if let Condition::LogMatchesRegex { pattern } = cond {
+                let regex = self.log_regexes.get(pattern);
+                let captures = regex.and_then(|regex| {
+                    transaction.log_messages.iter().find_map(|log| regex.captures(log).map(|caps| (regex, caps)))
+                });
+                if let Some((regex, caps)) = captures {
+                    for (index, name) in regex.capture_names().enumerate().skip(1) {
+                        if let Some(value) = caps.get(index) {
+                            let key = name.map_or_else(|| format!("log_{}", index), |name| format!("log_{}", name));
+                            context.insert(key, value.as_str().to_string());
+                        }
+                    }
+                }
+            }
+            if let Condition::CumulativeVolume { mint, account, measure, window_slots, window_secs, operator, amount } = cond {
+                let window = volume_window(*window_slots, *window_secs);
+                let total = self.cumulative_volume(transaction, mint, account.as_deref(), *measure, window);
+                if let (Some(total), Some(window)) = (total, window) {
+                    if self.compare_f64(total, *amount, operator) {
+                        let window = match window {
+                            VolumeWindow::Slots(slots) => format!("{} slots", slots),
+                            VolumeWindow::Secs(secs) => format!("{}s", secs),
+                        };
+                        context.insert("cumulative_volume".to_string(), format!("{} {:?} in {}", total, measure, window));
+                    }
+                }
+            }
+            if let Condition::FreshFeePayer { mint, within_slots } = cond {
+                let first_seen = self.fee_payer_first_seen(transaction, mint);
+                if let (Some(first_seen), Some(payer)) = (first_seen, fee_payer(transaction)) {
+                    if transaction.slot - first_seen <= *within_slots {
+                        context.insert("fresh_fee_payer".to_string(), payer.to_string());
+                        context.insert("fee_payer_first_seen_slot".to_string(), first_seen.to_string());
+                    }
+                }
+            }
+        }
+        context
+    }
+    
+    /// Persist stateful condition data (e.g. the seen-set) to disk
+    pub fn save_state(&self) -> Result<()> {
+        if let Some(seen_accounts) = &self.seen_accounts {
+            seen_accounts.lock().unwrap().save()?;
+        }
+        if let Some(volume_windows) = &self.volume_windows {
+            volume_windows.lock().unwrap().save()?;
+        }
+        Ok(())
+    }
+    
     fn evaluate_condition_set(&self, conditions: &ConditionSet, transaction: &ExtractedTransaction) -> bool {
         let mut result = true;
         
-        // Check all_of conditions (AND logic)
-        if let Some(all_conditions) = &conditions.all_of {
+        // Disabled conditions are treated as absent; a group whose conditions are all disabled is skipped
+        let enabled = |group: &Option<Vec<ConditionEntry>>| -> Option<Vec<&Condition>> {
+            let entries = group.as_ref()?;
+            let active: Vec<&Condition> = entries.iter()
+                .filter(|entry| entry.is_enabled())
+                .map(|entry| &entry.condition)
+                .collect();
+            if active.is_empty() && !entries.is_empty() { None } else { Some(active) }
+        };
+        
+        // Check all_of conditions (AND logic); empty passes, like an omitted group
+        if let Some(all_conditions) = enabled(&conditions.all_of) {
             result = all_conditions.iter()
                 .all(|cond| self.evaluate_condition(cond, transaction));
         }
         
-        // Check any_of conditions (OR logic)
-        if let Some(any_conditions) = &conditions.any_of {
+        // Check any_of conditions (OR logic); empty never passes and is rejected by `try_new`
+        if let Some(any_conditions) = enabled(&conditions.any_of) {
             let any_match = any_conditions.iter()
                 .any(|cond| self.evaluate_condition(cond, transaction));
             result = result && any_match;
         }
         
-        // Check none_of conditions (NOT logic)
-        if let Some(none_conditions) = &conditions.none_of {
+        // Check none_of conditions (NOT logic); empty excludes nothing
+        if let Some(none_conditions) = enabled(&conditions.none_of) {
             let none_match = !none_conditions.iter()
                 .any(|cond| self.evaluate_condition(cond, transaction));
             result = result && none_match;
@@ -176,17 +1148,12 @@ impl FilterEngine {
     fn evaluate_condition(&self, condition: &Condition, transaction: &ExtractedTransaction) -> bool {
         match condition {
             Condition::ProgramInvoked { program_id } => {
-                // Check both top-level instructions and inner instructions
-                let in_main_instructions = transaction.instructions.iter()
-                    .any(|inst| inst.program_id == *program_id);
-                    
-                let in_inner_instructions = transaction.inner_instructions.iter()
-                    .any(|inner_set| {
-                        inner_set.instructions.iter()
-                            .any(|inst| inst.program_id == *program_id)
-                    });
-                    
-                in_main_instructions || in_inner_instructions
+                is_program_invoked(transaction, program_id)
+            },
+            
+            Condition::ProgramInvokedAny { program_ids } => {
+                let invoked = invoked_programs(transaction);
+                program_ids.iter().any(|program_id| invoked.contains(program_id.as_str()))
             },
             
             Condition::TokenTransfer { mint, operator, amount } => {
@@ -198,6 +1165,34 @@ impl FilterEngine {
                     })
             },
             
+            Condition::BridgeTransfer { mint, operator, amount, programs } => {
+                let programs = programs.as_ref().unwrap_or(&self.default_bridge_programs);
+                let bridge_invoked = programs.iter()
+                    .any(|program_id| is_program_invoked(transaction, program_id));
+                
+                bridge_invoked && transaction.token_balance_changes.iter()
+                    .any(|change| {
+                        let mint_match = mint.as_ref().map_or(true, |m| change.mint == *m);
+                        mint_match && change.change != 0.0
+                            && self.compare_f64(change.change.abs(), *amount, operator)
+                    })
+            },
+            
+            Condition::DepositToProgram { mint, program_id, operator, amount } => {
+                transaction.token_balance_changes.iter()
+                    .any(|change| {
+                        change.mint == *mint
+                            && change.change > 0.0
+                            && change.owner.as_deref() == Some(program_id.as_str())
+                            && self.compare_f64(change.change, *amount, operator)
+                    })
+            },
+            
+            Condition::ComputeUnitLimit { operator, units } => {
+                requested_compute_unit_limit(transaction)
+                    .is_some_and(|limit| self.compare_u64(limit as u64, *units as u64, operator))
+            },
+            
             Condition::TokenMint { mint, operator, amount } => {
                 // Check for mint operations (tokens created from nothing)
                 let result = transaction.token_balance_changes.iter()
@@ -212,15 +1207,11 @@ impl FilterEngine {
                         // 1. instruction type contains "mint", OR
                         // 2. before amount was 0 (new token account), OR  
                         // 3. logs contain "MintTo" or "mint"
-                        let has_mint_instruction = transaction.instructions.iter().any(|inst| {
-                            inst.instruction_type.as_ref()
-                                .map_or(false, |t| t.contains("mint"))
-                        });
+                        let has_mint_instruction = self.has_mint_instruction(transaction);
                         
                         let is_new_account = change.before.ui_amount.unwrap_or(0.0) == 0.0;
                         
-                        let has_mint_log = transaction.log_messages.iter()
-                            .any(|log| log.contains("MintTo") || log.contains("mint"));
+                        let has_mint_log = self.has_mint_log(transaction);
                         
                         let is_mint = change.change > 0.0 && (has_mint_instruction || is_new_account || has_mint_log);
                         
@@ -237,7 +1228,55 @@ impl FilterEngine {
                     debug!("TokenMint condition failed for mint {} despite having balance changes", mint);
                 }
                 
-                result
+                result
+            },
+            
+            Condition::MintToNewAccounts { mint, min_new_recipients } => {
+                // Only an explicit mint counts here; the "new account" mint heuristic would be circular
+                if !(self.has_mint_instruction(transaction) || self.has_mint_log(transaction)) {
+                    return false;
+                }
+                
+                let Some(seen_accounts) = &self.seen_accounts else {
+                    return false;
+                };
+                let seen = seen_accounts.lock().unwrap();
+                
+                let new_recipients = transaction.token_balance_changes.iter()
+                    .filter(|change| change.mint == *mint && change.change > 0.0)
+                    .filter(|change| is_first_holding(change) && !seen.contains(mint, &change.account))
+                    .count();
+                
+                debug!("MintToNewAccounts: {} new recipients of {} (need {})",
+                    new_recipients, mint, min_new_recipients);
+                
+                new_recipients > 0 && new_recipients >= *min_new_recipients
+            },
+            
+            Condition::MintAndBurn { mint } => {
+                let (minted, burned) = token_supply_instructions(transaction, mint);
+                debug!("MintAndBurn {}: minted={}, burned={}", mint, minted, burned);
+                minted && burned
+            },
+            
+            Condition::RepeatedSigner { mint, min_transactions, window_secs } => {
+                let count = self.repeated_signer_count(transaction, mint, *window_secs);
+                debug!("RepeatedSigner {}: {:?} transactions in {}s (need {})",
+                    mint, count, window_secs, min_transactions);
+                count.is_some_and(|count| count >= *min_transactions)
+            },
+            
+            Condition::CumulativeVolume { mint, account, measure, window_slots, window_secs, operator, amount } => {
+                let window = volume_window(*window_slots, *window_secs);
+                let total = self.cumulative_volume(transaction, mint, account.as_deref(), *measure, window);
+                debug!("CumulativeVolume {} {:?}: {:?} in {:?} (threshold {})", mint, measure, total, window, amount);
+                total.is_some_and(|total| self.compare_f64(total, *amount, operator))
+            },
+            
+            Condition::TransferVelocity { mint, min_turnover, window_secs } => {
+                let account = self.velocity_account(transaction, mint, *min_turnover, *window_secs);
+                debug!("TransferVelocity {}: {:?} (need {} in {}s)", mint, account, min_turnover, window_secs);
+                account.is_some()
             },
             
             Condition::TokenBurn { mint, operator, amount } => {
@@ -300,14 +1339,91 @@ impl FilterEngine {
                 transaction.success == *success
             },
             
-            Condition::FeeAmount { operator, amount } => {
-                self.compare_u64(transaction.fee, *amount, operator)
+            Condition::AccountSolFlow { account, direction, operator, lamports } => {
+                let Some(net) = net_sol_flow(transaction, account) else {
+                    return false;
+                };
+                let direction_match = match direction {
+                    FlowDirection::In => net > 0,
+                    FlowDirection::Out => net < 0,
+                    FlowDirection::Either => net != 0,
+                };
+                direction_match && self.compare_u64(net.unsigned_abs(), *lamports, operator)
+            },
+            
+            Condition::FailedWithMint { mint } => {
+                !transaction.success && mint_in_intended_accounts(transaction, mint)
+            },
+            
+            Condition::FeeAmount { operator, amount, unit } => {
+                self.compare_u64(transaction.fee, unit.to_lamports(*amount), operator)
             },
             
             Condition::InstructionCount { operator, count } => {
                 self.compare_usize(transaction.instructions.len(), *count, operator)
             },
             
+            Condition::DistinctProgramCount { operator, count } => {
+                self.compare_usize(distinct_program_count(transaction), *count, operator)
+            },
+            
+            Condition::CpiFrom { parent_program, child_program } => {
+                is_cpi_from(transaction, parent_program, child_program)
+            },
+            
+            Condition::TransactionSizeBytes { operator, bytes } => {
+                self.compare_usize(transaction.approx_size_bytes, *bytes, operator)
+            },
+            
+            Condition::TransactionVersion { version } => match version {
+                Some(version) => transaction.version == version.to_string(),
+                None => transaction.version == "legacy",
+            },
+            
+            Condition::AccountOwnedBy { owner_program } => {
+                transaction.account_owners.values().any(|owner| owner == owner_program)
+            },
+            
+            Condition::SlotLeader { leaders } => {
+                transaction.slot_leader.as_ref().is_some_and(|leader| leaders.contains(leader))
+            },
+            
+            Condition::MultiMint { mints, require_all, operator, amount } => {
+                let moved = |mint: &String| transaction.token_balance_changes.iter()
+                    .any(|change| change.mint == *mint
+                        && change.change != 0.0
+                        && self.compare_f64(change.change.abs(), *amount, operator));
+                if *require_all {
+                    !mints.is_empty() && mints.iter().all(moved)
+                } else {
+                    mints.iter().any(moved)
+                }
+            },
+            
+            Condition::FreshFeePayer { mint, within_slots } => {
+                self.fee_payer_first_seen(transaction, mint)
+                    .is_some_and(|first_seen| transaction.slot - first_seen <= *within_slots)
+            },
+            
+            // Needs the filter's history, so it is applied per filter in `evaluate_transaction`
+            Condition::TimeSinceLastMatch { .. } => true,
+            
+            Condition::Reward { pubkey, operator, lamports } => {
+                transaction.block_rewards.iter()
+                    .filter(|reward| pubkey.as_ref().is_none_or(|pubkey| reward.pubkey == *pubkey))
+                    .any(|reward| self.compare_f64(reward.lamports as f64, *lamports as f64, operator))
+            },
+            
+            Condition::SupplyPercentage { mint, operator, percent } => {
+                match transaction.mint_supplies.get(mint) {
+                    Some(supply) if *supply > 0.0 => {
+                        let moved = moved_amount(transaction, mint);
+                        moved > 0.0 && self.compare_f64(moved / supply * 100.0, *percent, operator)
+                    },
+                    _ => false,
+                }
+            },
+            
             Condition::AccountInvolved { account } => {
                 transaction.accounts.iter()
                     .any(|acc| acc.pubkey == *account)
@@ -323,9 +1439,56 @@ impl FilterEngine {
                         .any(|log| log.to_lowercase().contains(&pattern_lower))
                 }
             },
+            
+            Condition::InstructionData { program_id, discriminator, encoding, anchor_instruction } => {
+                let mut prefix = anchor_instruction.as_deref().map(anchor_discriminator).unwrap_or_default();
+                if let Some(discriminator) = discriminator {
+                    match decode_data(discriminator, *encoding) {
+                        Some(bytes) => prefix.extend(bytes),
+                        // Reported once at load time
+                        None => return false,
+                    }
+                }
+                has_instruction_data(transaction, program_id, &prefix)
+            },
+            
+            Condition::LogMatchesRegex { pattern } => {
+                self.log_regexes.get(pattern)
+                    .is_some_and(|regex| transaction.log_messages.iter().any(|log| regex.is_match(log)))
+            },
+            
+            Condition::ProgramDataContains { program_id, decoded_contains_hex } => {
+                let needle = match decoded_contains_hex {
+                    Some(hex) => match decode_hex(hex) {
+                        Some(bytes) => bytes,
+                        // Reported once at load time
+                        None => return false,
+                    },
+                    None => Vec::new(),
+                };
+                
+                program_data_payloads(&transaction.log_messages)
+                    .into_iter()
+                    .filter(|(emitter, _)| {
+                        program_id.as_ref().map_or(true, |id| emitter.as_deref() == Some(id.as_str()))
+                    })
+                    .any(|(_, data)| contains_bytes(&data, &needle))
+            },
         }
     }
     
+    fn has_mint_instruction(&self, transaction: &ExtractedTransaction) -> bool {
+        transaction.instructions.iter().any(|inst| {
+            inst.instruction_type.as_ref()
+                .map_or(false, |t| t.contains("mint"))
+        })
+    }
+    
+    fn has_mint_log(&self, transaction: &ExtractedTransaction) -> bool {
+        transaction.log_messages.iter()
+            .any(|log| log.contains("MintTo") || log.contains("mint"))
+    }
+    
     fn compare_f64(&self, value: f64, target: f64, operator: &ComparisonOperator) -> bool {
         match operator {
             ComparisonOperator::GreaterThan => value > target,
@@ -371,11 +1534,295 @@ impl FilterEngine {
     }
 }
 
+/// Whether activity at `at` comes `quiet_secs` or more after the `previous` one; activity older
+/// than the previous one (a slot processed late) never does
+fn is_quiet_period_over(previous: Option<i64>, at: i64, quiet_secs: u64) -> bool {
+    match previous {
+        Some(previous) if previous > at => false,
+        Some(previous) => at - previous >= quiet_secs as i64,
+        None => true,
+    }
+}
+
+/// Whether the account held none of the token before this transaction
+fn is_first_holding(change: &TokenBalanceChange) -> bool {
+    change.before.ui_amount.unwrap_or(0.0) == 0.0
+}
+
+/// Units from the transaction's `setComputeUnitLimit` instruction; the runtime only honours
+/// top-level ComputeBudget instructions, so CPIs are ignored
+fn requested_compute_unit_limit(transaction: &ExtractedTransaction) -> Option<u32> {
+    transaction.instructions.iter()
+        .filter(|inst| inst.program_id == COMPUTE_BUDGET_PROGRAM_ID)
+        .filter_map(|inst| inst.parsed.as_ref())
+        .find(|parsed| parsed.instruction_type == "setComputeUnitLimit")
+        .and_then(|parsed| parsed.info.get("units"))
+        .and_then(|units| units.as_u64())
+        .map(|units| units as u32)
+}
+
+/// Whether `program_id` ran in the transaction, either top-level or through a CPI
+fn is_program_invoked(transaction: &ExtractedTransaction, program_id: &str) -> bool {
+    // Check both top-level instructions and inner instructions
+    let in_main_instructions = transaction.instructions.iter()
+        .any(|inst| inst.program_id == program_id);
+        
+    let in_inner_instructions = transaction.inner_instructions.iter()
+        .any(|inner_set| {
+            inner_set.instructions.iter()
+                .any(|inst| inst.program_id == program_id)
+        });
+        
+    in_main_instructions || in_inner_instructions
+}
+
+/// Programs invoked by the transaction, top-level and inner
+fn invoked_programs(transaction: &ExtractedTransaction) -> HashSet<&str> {
+    transaction.instructions.iter()
+        .chain(transaction.inner_instructions.iter().flat_map(|inner_set| inner_set.instructions.iter()))
+        .map(|inst| inst.program_id.as_str())
+        .collect()
+}
+
+/// Programs invoked by the transaction, top-level and inner, each counted once
+fn distinct_program_count(transaction: &ExtractedTransaction) -> usize {
+    invoked_programs(transaction).len()
+}
+
+/// Whether an inner instruction set belonging to a top-level `parent_program` instruction calls
+/// `child_program`. Each set's `index` is the position of the top-level instruction it ran under.
+fn is_cpi_from(transaction: &ExtractedTransaction, parent_program: &str, child_program: &str) -> bool {
+    transaction.inner_instructions.iter()
+        .filter(|inner_set| transaction.instructions.get(inner_set.index as usize)
+            .is_some_and(|parent| parent.program_id == parent_program))
+        .any(|inner_set| inner_set.instructions.iter().any(|inst| inst.program_id == child_program))
+}
+
+/// Lamport change of `account` excluding the fee, or None if the account isn't in the transaction
+fn net_sol_flow(transaction: &ExtractedTransaction, account: &str) -> Option<i64> {
+    let change = transaction.balance_changes.get(account)?.change;
+    let fee = if fee_payer(transaction) == Some(account) { transaction.fee as i64 } else { 0 };
+    Some(change + fee)
+}
+
+/// First account key, which pays the fee and always signs
+fn fee_payer(transaction: &ExtractedTransaction) -> Option<&str> {
+    transaction.account_keys.first().map(String::as_str)
+}
+
+fn touches_mint(transaction: &ExtractedTransaction, mint: &str) -> bool {
+    transaction.token_balance_changes.iter().any(|change| change.mint == mint)
+}
+
+/// Amount of `mint` that changed hands: the larger of the summed increases and the summed
+/// decreases, so a transfer isn't counted twice and a mint or burn still counts in full
+fn moved_amount(tx: &ExtractedTransaction, mint: &str) -> f64 {
+    let (increase, decrease) = tx.token_balance_changes.iter()
+        .filter(|change| change.mint == mint)
+        .fold((0.0, 0.0), |(inc, dec), change| {
+            if change.change > 0.0 { (inc + change.change, dec) } else { (inc, dec - change.change) }
+        });
+    f64::max(increase, decrease)
+}
+
+/// What the transaction adds to a `CumulativeVolume` of `mint`, optionally only counting
+/// balance changes of `account` or of token accounts it owns
+fn transaction_volume(transaction: &ExtractedTransaction, mint: &str, account: Option<&str>, measure: VolumeMeasure) -> f64 {
+    let (increase, decrease) = transaction.token_balance_changes.iter()
+        .filter(|change| change.mint == mint)
+        .filter(|change| account.is_none_or(|account| change.account == account || change.owner.as_deref() == Some(account)))
+        .fold((0.0, 0.0), |(inc, dec), change| {
+            if change.change > 0.0 { (inc + change.change, dec) } else { (inc, dec - change.change) }
+        });
+    match measure {
+        VolumeMeasure::Minted => f64::max(increase - decrease, 0.0),
+        VolumeMeasure::Burned => f64::max(decrease - increase, 0.0),
+        VolumeMeasure::Moved => f64::max(increase, decrease),
+    }
+}
+
+/// Windows are measured in block time so replays count like live runs; blocks without a
+/// timestamp fall back to the wall clock
+fn block_time_or_now(transaction: &ExtractedTransaction) -> i64 {
+    transaction.block_time.unwrap_or_else(|| chrono::Utc::now().timestamp())
+}
+
+/// Whether `mint` appears in what the transaction meant to touch rather than in what changed:
+/// account keys, parsed instruction `mint` fields and raw pre/post token balances (the latter are
+/// empty in lean extraction mode)
+fn mint_in_intended_accounts(transaction: &ExtractedTransaction, mint: &str) -> bool {
+    let in_account_keys = transaction.account_keys.iter().any(|key| key == mint);
+    
+    let in_instructions = transaction.instructions.iter()
+        .chain(transaction.inner_instructions.iter().flat_map(|set| set.instructions.iter()))
+        .filter_map(|inst| inst.parsed.as_ref())
+        .any(|parsed| parsed.info.get("mint").and_then(|m| m.as_str()) == Some(mint));
+    
+    let in_token_balances = transaction.pre_token_balances.iter()
+        .chain(transaction.post_token_balances.iter())
+        .any(|balance| balance.mint == mint);
+    
+    in_account_keys || in_instructions || in_token_balances
+}
+
+/// Whether the transaction has parsed SPL Token (or Token-2022) mint and burn instructions for
+/// `mint`, looking through CPIs as well
+fn token_supply_instructions(transaction: &ExtractedTransaction, mint: &str) -> (bool, bool) {
+    let mut minted = false;
+    let mut burned = false;
+    
+    let instructions = transaction.instructions.iter()
+        .chain(transaction.inner_instructions.iter().flat_map(|set| set.instructions.iter()));
+    for inst in instructions {
+        if !matches!(inst.program_name.as_deref(), Some("spl-token" | "spl-token-2022")) {
+            continue;
+        }
+        let Some(parsed) = &inst.parsed else { continue };
+        if parsed.info.get("mint").and_then(|m| m.as_str()) != Some(mint) {
+            continue;
+        }
+        match parsed.instruction_type.as_str() {
+            "mintTo" | "mintToChecked" => minted = true,
+            "burn" | "burnChecked" => burned = true,
+            _ => {}
+        }
+    }
+    
+    (minted, burned)
+}
+
+/// Decoded `Program data:` payloads paired with the program that emitted them, tracked through
+/// the `invoke`/`success`/`failed` lines of the log
+fn program_data_payloads(logs: &[String]) -> Vec<(Option<String>, Vec<u8>)> {
+    use base64::Engine;
+    
+    let mut stack: Vec<&str> = Vec::new();
+    let mut payloads = Vec::new();
+    
+    for log in logs {
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            // A line may carry several space-separated base64 chunks; match against them joined
+            let mut bytes = Vec::new();
+            for chunk in data.split_whitespace() {
+                if let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(chunk) {
+                    bytes.extend(decoded);
+                }
+            }
+            payloads.push((stack.last().map(|id| id.to_string()), bytes));
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            if let (Some(id), Some(event)) = (parts.next(), parts.next()) {
+                match event {
+                    "invoke" => stack.push(id),
+                    "success" | "failed:" | "failed" => {
+                        stack.pop();
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+    
+    payloads
+}
+
+/// Parse a hex string, allowing an optional `0x` prefix and whitespace between bytes
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let digits: String = hex.trim()
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if !digits.is_ascii() || digits.len() % 2 != 0 {
+        return None;
+    }
+    
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn decode_data(data: &str, encoding: DataEncoding) -> Option<Vec<u8>> {
+    use base64::Engine;
+    
+    match encoding {
+        DataEncoding::Base58 => bs58::decode(data.trim()).into_vec().ok(),
+        DataEncoding::Base64 => base64::engine::general_purpose::STANDARD.decode(data.trim()).ok(),
+        DataEncoding::Hex => decode_hex(data),
+    }
+}
+
+/// First 8 bytes of `sha256("global:<name>")`, which Anchor puts in front of an instruction's arguments
+fn anchor_discriminator(name: &str) -> Vec<u8> {
+    use sha2::{Digest, Sha256};
+    
+    Sha256::digest(format!("global:{}", name.trim()).as_bytes())[..8].to_vec()
+}
+
+/// Whether an unparsed instruction of `program_id`, top-level or inner, has data starting with `prefix`
+fn has_instruction_data(transaction: &ExtractedTransaction, program_id: &str, prefix: &[u8]) -> bool {
+    transaction.instructions.iter()
+        .chain(transaction.inner_instructions.iter().flat_map(|set| set.instructions.iter()))
+        // Parsed instructions keep the RPC's JSON in `data` rather than the raw bytes
+        .filter(|inst| inst.program_id == program_id && inst.program_name.is_none())
+        .filter_map(|inst| bs58::decode(&inst.data).into_vec().ok())
+        .any(|data| data.starts_with(prefix))
+}
+
+fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty() || haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Ids of enabled filters whose `any_of` is an explicit empty array
+pub fn find_empty_any_of(filters: &[FilterConfig]) -> Vec<String> {
+    filters.iter()
+        .filter(|f| f.enabled)
+        .filter(|f| f.conditions.any_of.as_ref().is_some_and(|any_of| any_of.is_empty()))
+        .map(|f| f.id.clone())
+        .collect()
+}
+
+/// Return every filter id that appears more than once, in order of first appearance
+pub fn find_duplicate_filter_ids(filters: &[FilterConfig]) -> Vec<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut duplicates = Vec::new();
+    
+    for filter in filters {
+        let count = counts.entry(filter.id.as_str()).or_insert(0);
+        *count += 1;
+        if *count == 2 {
+            duplicates.push(filter.id.clone());
+        }
+    }
+    
+    duplicates
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterExplanation {
+    pub filter_id: String,
+    pub filter_name: String,
+    pub matched: bool,
+    pub conditions: Vec<ConditionExplanation>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConditionExplanation {
+    /// `all_of`, `any_of` or `none_of`
+    pub group: &'static str,
+    pub condition: Condition,
+    pub matched: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct MatchedFilter {
     pub filter_id: String,
     pub filter_name: String,
     pub actions: Vec<Action>,
+    pub cooldown_secs: Option<u64>,
+    /// Extra details from stateful conditions (e.g. `repeated_signer`), shown in alerts
+    pub context: BTreeMap<String, String>,
 }
 
 // Helper function to create default YUYA mint filters
@@ -392,7 +1839,7 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                         mint: yuya_mint_address.to_string(),
                         operator: ComparisonOperator::GreaterThanOrEqual,
                         amount: 30_000_000.0,
-                    },
+                    }.into(),
                 ]),
                 any_of: None,
                 none_of: None,
@@ -406,6 +1853,8 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                     collection: "critical_mints".to_string(),
                 },
             ],
+            cooldown_secs: None,
+            priority: 0,
         },
         FilterConfig {
             id: "yuya_mint_10m".to_string(),
@@ -417,7 +1866,7 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                         mint: yuya_mint_address.to_string(),
                         operator: ComparisonOperator::GreaterThanOrEqual,
                         amount: 10_000_000.0,
-                    },
+                    }.into(),
                 ]),
                 any_of: None,
                 none_of: None,
@@ -431,6 +1880,8 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                     collection: "large_mints".to_string(),
                 },
             ],
+            cooldown_secs: None,
+            priority: 0,
         },
         FilterConfig {
             id: "yuya_mint_1m".to_string(),
@@ -442,7 +1893,7 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                         mint: yuya_mint_address.to_string(),
                         operator: ComparisonOperator::GreaterThanOrEqual,
                         amount: 1_000_000.0,
-                    },
+                    }.into(),
                 ]),
                 any_of: None,
                 none_of: None,
@@ -456,6 +1907,8 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                     collection: "medium_mints".to_string(),
                 },
             ],
+            cooldown_secs: None,
+            priority: 0,
         },
         // Burn filters
         FilterConfig {
@@ -468,7 +1921,7 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                         mint: yuya_mint_address.to_string(),
                         operator: ComparisonOperator::GreaterThanOrEqual,
                         amount: 10_000_000.0,
-                    },
+                    }.into(),
                 ]),
                 any_of: None,
                 none_of: None,
@@ -482,6 +1935,8 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                     collection: "large_burns".to_string(),
                 },
             ],
+            cooldown_secs: None,
+            priority: 0,
         },
         FilterConfig {
             id: "yuya_burn_1m".to_string(),
@@ -493,7 +1948,7 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                         mint: yuya_mint_address.to_string(),
                         operator: ComparisonOperator::GreaterThanOrEqual,
                         amount: 1_000_000.0,
-                    },
+                    }.into(),
                 ]),
                 any_of: None,
                 none_of: None,
@@ -507,6 +1962,578 @@ pub fn create_yuya_mint_filters(yuya_mint_address: &str) -> Vec<FilterConfig> {
                     collection: "medium_burns".to_string(),
                 },
             ],
+            cooldown_secs: None,
+            priority: 0,
         },
     ]
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::transaction_extractor::{ExtractedInstruction, InnerInstructionSet, TokenAmount};
+    
+    #[test]
+    fn test_program_data_payloads_attributes_emitting_program() {
+        let logs: Vec<String> = [
+            "Program Outer111 invoke [1]",
+            "Program Inner222 invoke [2]",
+            "Program data: 3q2+7w==",
+            "Program Inner222 consumed 1200 of 200000 compute units",
+            "Program Inner222 success",
+            "Program data: AQID",
+            "Program Outer111 success",
+        ].iter().map(|s| s.to_string()).collect();
+        
+        let payloads = program_data_payloads(&logs);
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0], (Some("Inner222".to_string()), vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(payloads[1], (Some("Outer111".to_string()), vec![1, 2, 3]));
+    }
+    
+    #[test]
+    fn test_decode_hex_and_contains_bytes() {
+        assert_eq!(decode_hex("0xdead BEEF"), Some(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert!(contains_bytes(&[1, 2, 3, 4], &[2, 3]));
+        assert!(!contains_bytes(&[1, 2, 3, 4], &[3, 2]));
+        assert!(contains_bytes(&[1], &[]));
+    }
+    
+    /// A successful transaction with a 5000 lamport fee that touches nothing; tests fill in
+    /// the fields they look at
+    pub(crate) fn test_transaction() -> ExtractedTransaction {
+        serde_json::from_value(serde_json::json!({
+            "signature": "5igTestSignature",
+            "slot": 1,
+            "success": true,
+            "fee": 5000,
+            "accounts": [],
+            "account_keys": [],
+            "static_account_keys": [],
+            "writable_account_indices": [],
+            "readonly_account_indices": [],
+            "pre_balances": [],
+            "post_balances": [],
+            "balance_changes": {},
+            "token_balance_changes": [],
+            "instructions": [],
+            "inner_instructions": [],
+            "log_messages": [],
+            "address_table_lookups": [],
+            "version": "0",
+            "recent_blockhash": "",
+            "loaded_addresses": { "writable": [], "readonly": [] }
+        })).unwrap()
+    }
+    
+    /// A top-level call of `program_id` without accounts, data or parsed info
+    pub(crate) fn test_instruction(program_id: &str) -> ExtractedInstruction {
+        ExtractedInstruction {
+            program_id: program_id.to_string(),
+            program_name: None,
+            instruction_type: None,
+            accounts: vec![],
+            data: String::new(),
+            parsed: None,
+            stack_height: None,
+        }
+    }
+    
+    /// `change` whole tokens of `mint` (no decimals) moving in or out of `account`
+    pub(crate) fn token_change(account: &str, mint: &str, change: f64) -> TokenBalanceChange {
+        let amount = TokenAmount { amount: "0".to_string(), decimals: 0, ui_amount: Some(0.0) };
+        TokenBalanceChange {
+            account: account.to_string(),
+            mint: mint.to_string(),
+            owner: None,
+            before: amount.clone(),
+            after: amount,
+            change,
+        }
+    }
+    
+    fn filter_with(conditions: serde_json::Value) -> FilterConfig {
+        serde_json::from_value(serde_json::json!({
+            "id": "test",
+            "name": "Test",
+            "enabled": true,
+            "conditions": conditions,
+            "actions": []
+        })).unwrap()
+    }
+    
+    #[test]
+    fn test_empty_condition_groups() {
+        let transaction = test_transaction();
+        let fee_matches = serde_json::json!([{ "type": "FeeAmount", "operator": "Equal", "amount": 5000 }]);
+        let matches = |conditions: serde_json::Value| {
+            !FilterEngine::new(vec![filter_with(conditions)]).evaluate_transaction(&transaction).is_empty()
+        };
+        
+        // Empty all_of and none_of impose no constraint
+        assert!(matches(serde_json::json!({ "all_of": [] })));
+        assert!(matches(serde_json::json!({ "none_of": [] })));
+        assert!(matches(serde_json::json!({ "all_of": [], "any_of": fee_matches })));
+        
+        // Empty any_of can never be satisfied
+        assert!(!matches(serde_json::json!({ "any_of": [] })));
+        assert!(!matches(serde_json::json!({ "all_of": fee_matches, "any_of": [] })));
+    }
+    
+    #[test]
+    fn test_disabled_duplicate_does_not_shadow_enabled_filter() {
+        let mut disabled = filter_with(serde_json::json!({ "all_of": [{ "type": "TransactionStatus", "success": false }] }));
+        disabled.enabled = false;
+        let mut enabled = filter_with(serde_json::json!({}));
+        enabled.name = "Enabled".to_string();
+        
+        let engine = FilterEngine::new(vec![disabled, enabled]);
+        assert_eq!(engine.filters().len(), 1);
+        assert_eq!(engine.filters()[0].name, "Enabled");
+        assert_eq!(engine.evaluate_transaction(&test_transaction()).len(), 1);
+    }
+    
+    #[test]
+    fn test_empty_any_of_is_rejected_at_load() {
+        let filters = vec![
+            filter_with(serde_json::json!({ "any_of": [] })),
+            filter_with(serde_json::json!({ "all_of": [], "none_of": [] })),
+        ];
+        assert_eq!(find_empty_any_of(&filters), vec!["test".to_string()]);
+        assert!(FilterEngine::try_new(filters.clone()).is_err());
+        assert!(FilterEngine::try_new(filters[1..].to_vec()).is_ok());
+        
+        let path = std::env::temp_dir().join(format!("empty_any_of_{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(&filters[..1]).unwrap()).unwrap();
+        let result = FilterEngine::from_json_file(path.to_str().unwrap(), false);
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_err());
+    }
+    
+    #[test]
+    fn test_mint_and_burn_needs_both_instructions() {
+        let token_instruction = |instruction_type: &str, mint: &str| -> ExtractedInstruction {
+            serde_json::from_value(serde_json::json!({
+                "program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "program_name": "spl-token",
+                "instruction_type": instruction_type,
+                "accounts": [],
+                "data": "",
+                "parsed": { "instruction_type": instruction_type, "info": { "mint": mint } },
+                "stack_height": null
+            })).unwrap()
+        };
+        let matches = |transaction: &ExtractedTransaction| {
+            let filter = filter_with(serde_json::json!({ "all_of": [{ "type": "MintAndBurn", "mint": "YU" }] }));
+            !FilterEngine::new(vec![filter]).evaluate_transaction(transaction).is_empty()
+        };
+        
+        let mut transaction = test_transaction();
+        transaction.instructions.push(token_instruction("mintTo", "YU"));
+        assert!(!matches(&transaction));
+        
+        transaction.inner_instructions.push(InnerInstructionSet {
+            index: 0,
+            instructions: vec![token_instruction("burnChecked", "OTHER")],
+        });
+        assert!(!matches(&transaction));
+        
+        transaction.inner_instructions[0].instructions.push(token_instruction("burnChecked", "YU"));
+        assert!(matches(&transaction));
+    }
+    
+    #[test]
+    fn test_distinct_program_count_includes_inner_instructions() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "DistinctProgramCount", "operator": "GreaterThanOrEqual", "count": 3 }]
+        }))]);
+        
+        let mut transaction = test_transaction();
+        transaction.instructions = vec![test_instruction("Router"), test_instruction("Router")];
+        transaction.inner_instructions.push(InnerInstructionSet {
+            index: 0,
+            instructions: vec![test_instruction("DexA"), test_instruction("Router")],
+        });
+        assert!(engine.evaluate_transaction(&transaction).is_empty(), "Router counts once");
+        
+        transaction.inner_instructions[0].instructions.push(test_instruction("DexB"));
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+    }
+    
+    #[test]
+    fn test_program_invoked_any_matches_inner_instructions() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "ProgramInvokedAny", "program_ids": ["Raydium", "Orca", "Meteora"] }]
+        }))]);
+        
+        let mut transaction = test_transaction();
+        transaction.instructions = vec![test_instruction("Jupiter")];
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+        
+        transaction.inner_instructions = vec![InnerInstructionSet { index: 0, instructions: vec![test_instruction("Orca")] }];
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+    }
+    
+    #[test]
+    fn test_cpi_from_matches_child_under_parent_only() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "CpiFrom", "parent_program": "Jupiter", "child_program": "Raydium" }]
+        }))]);
+        
+        // Raydium is called, but from the other top-level instruction
+        let mut transaction = test_transaction();
+        transaction.instructions = vec![test_instruction("Jupiter"), test_instruction("Other")];
+        transaction.inner_instructions = vec![
+            InnerInstructionSet { index: 0, instructions: vec![test_instruction("Orca")] },
+            InnerInstructionSet { index: 1, instructions: vec![test_instruction("Raydium")] },
+        ];
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+        
+        transaction.inner_instructions[0].instructions.push(test_instruction("Raydium"));
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+        
+        // A set pointing past the top-level instructions never matches
+        transaction.inner_instructions = vec![InnerInstructionSet { index: 5, instructions: vec![test_instruction("Raydium")] }];
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+    }
+    
+    #[test]
+    fn test_repeated_signer_counts_within_window() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "RepeatedSigner", "mint": "YU", "min_transactions": 3, "window_secs": 60 }]
+        }))]);
+        let transaction_at = |signer: &str, block_time: i64| {
+            let mut transaction = test_transaction();
+            transaction.account_keys = vec![signer.to_string()];
+            transaction.block_time = Some(block_time);
+            transaction.token_balance_changes = vec![serde_json::from_value(serde_json::json!({
+                "account": "holder",
+                "mint": "YU",
+                "before": { "amount": "0", "decimals": 6, "ui_amount": 0.0 },
+                "after": { "amount": "1000000", "decimals": 6, "ui_amount": 1.0 },
+                "change": 1.0
+            })).unwrap()];
+            transaction
+        };
+        
+        assert!(engine.evaluate_transaction(&transaction_at("bot", 1000)).is_empty());
+        assert!(engine.evaluate_transaction(&transaction_at("other", 1010)).is_empty());
+        assert!(engine.evaluate_transaction(&transaction_at("bot", 1020)).is_empty());
+        
+        let matched = engine.evaluate_transaction(&transaction_at("bot", 1030));
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].context.get("repeated_signer").map(String::as_str), Some("bot"));
+        
+        // The first transaction has left the window by now
+        assert!(engine.evaluate_transaction(&transaction_at("bot", 1085)).is_empty());
+    }
+    
+    #[test]
+    fn test_transfer_velocity_needs_both_directions() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "TransferVelocity", "mint": "YU", "min_turnover": 100.0, "window_secs": 60 }]
+        }))]);
+        let transaction_at = |change: f64, block_time: i64| {
+            let mut transaction = test_transaction();
+            transaction.block_time = Some(block_time);
+            transaction.token_balance_changes = vec![serde_json::from_value(serde_json::json!({
+                "account": "cycler",
+                "mint": "YU",
+                "before": { "amount": "0", "decimals": 0, "ui_amount": 0.0 },
+                "after": { "amount": "0", "decimals": 0, "ui_amount": 0.0 },
+                "change": change
+            })).unwrap()];
+            transaction
+        };
+        
+        assert!(engine.evaluate_transaction(&transaction_at(80.0, 1000)).is_empty());
+        assert!(engine.evaluate_transaction(&transaction_at(30.0, 1010)).is_empty(), "inflow only");
+        
+        let matched = engine.evaluate_transaction(&transaction_at(-60.0, 1020));
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].context.get("velocity_account").map(String::as_str), Some("cycler"));
+        
+        // Only the outflow and a new inflow are left in the window
+        assert!(engine.evaluate_transaction(&transaction_at(10.0, 1075)).is_empty());
+    }
+    
+    #[test]
+    fn test_failed_with_mint_uses_intended_accounts() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "FailedWithMint", "mint": "YU" }]
+        }))]);
+        
+        let mut transaction = test_transaction();
+        transaction.account_keys = vec!["payer".to_string(), "YU".to_string()];
+        assert!(engine.evaluate_transaction(&transaction).is_empty(), "successful transactions never match");
+        
+        transaction.success = false;
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+        
+        transaction.account_keys = vec!["payer".to_string()];
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+    }
+    
+    #[test]
+    fn test_account_sol_flow_excludes_fee() {
+        let outflow = |lamports: u64| {
+            filter_with(serde_json::json!({ "all_of": [{
+                "type": "AccountSolFlow", "account": "hot", "direction": "Out",
+                "operator": "GreaterThanOrEqual", "lamports": lamports
+            }] }))
+        };
+        
+        let mut transaction = test_transaction();
+        transaction.account_keys = vec!["hot".to_string()];
+        transaction.balance_changes.insert("hot".to_string(), serde_json::from_value(serde_json::json!({
+            "account": "hot", "before": 1_000_000, "after": 895_000, "change": -105_000
+        })).unwrap());
+        
+        // 105,000 lamports left the wallet, 5,000 of them as the fee
+        assert!(!FilterEngine::new(vec![outflow(100_000)]).evaluate_transaction(&transaction).is_empty());
+        assert!(FilterEngine::new(vec![outflow(100_001)]).evaluate_transaction(&transaction).is_empty());
+    }
+    
+    #[test]
+    fn test_supply_percentage_counts_a_transfer_once() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [{
+            "type": "SupplyPercentage", "mint": "YU", "operator": "GreaterThanOrEqual", "percent": 5.0
+        }] }))]);
+        assert_eq!(engine.supply_mints(), ["YU".to_string()]);
+        
+        let mut transaction = test_transaction();
+        transaction.token_balance_changes = vec![token_change("sender", "YU", -60.0), token_change("receiver", "YU", 60.0)];
+        assert!(engine.evaluate_transaction(&transaction).is_empty(), "no supply known yet");
+        
+        transaction.mint_supplies.insert("YU".to_string(), 1_000.0);
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+        
+        transaction.mint_supplies.insert("YU".to_string(), 1_500.0);
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+    }
+    
+    #[test]
+    fn test_multi_mint_requires_every_mint_to_move() {
+        let change = |mint: &str, amount: f64| token_change(&format!("{}-account", mint), mint, amount);
+        let basket = |require_all: bool| FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [{
+            "type": "MultiMint", "mints": ["YU", "USDC"], "require_all": require_all,
+            "operator": "GreaterThanOrEqual", "amount": 100.0
+        }] }))]);
+        
+        let mut transaction = test_transaction();
+        transaction.token_balance_changes = vec![change("YU", -500.0), change("USDC", 50.0)];
+        assert!(basket(true).evaluate_transaction(&transaction).is_empty(), "USDC leg below threshold");
+        assert_eq!(basket(false).evaluate_transaction(&transaction).len(), 1);
+        
+        transaction.token_balance_changes.push(change("USDC", 450.0));
+        assert_eq!(basket(true).evaluate_transaction(&transaction).len(), 1);
+    }
+    
+    #[test]
+    fn test_time_since_last_match_fires_after_quiet_period() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [
+            { "type": "FeeAmount", "operator": "Equal", "amount": 5000 },
+            { "type": "TimeSinceLastMatch", "quiet_secs": 3600 }
+        ] }))]);
+        let at = |block_time: i64| {
+            let mut transaction = test_transaction();
+            transaction.block_time = Some(block_time);
+            engine.evaluate_transaction(&transaction).len()
+        };
+        
+        assert_eq!(at(1_000), 1, "first activity since startup fires");
+        assert_eq!(at(1_600), 0, "10 minutes later is not a lull");
+        assert_eq!(at(5_100), 0, "quiet period restarts at every activity, not at the last alert");
+        assert_eq!(at(8_700), 1, "an hour without activity");
+        assert_eq!(at(8_000), 0, "an older slot processed late never counts as quiet");
+        assert_eq!(at(12_300), 1);
+    }
+    
+    #[test]
+    fn test_explain_reports_quiet_period_without_recording() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [
+            { "type": "FeeAmount", "operator": "Equal", "amount": 5000 },
+            { "type": "TimeSinceLastMatch", "quiet_secs": 3600 }
+        ] }))]);
+        let at = |block_time: i64| {
+            let mut transaction = test_transaction();
+            transaction.block_time = Some(block_time);
+            transaction
+        };
+        assert_eq!(engine.evaluate_transaction(&at(1_000)).len(), 1);
+        
+        let explanation = engine.explain_transaction(&at(1_600)).remove(0);
+        assert!(!explanation.matched, "the quiet period hasn't passed");
+        let matched: Vec<bool> = explanation.conditions.iter().map(|c| c.matched).collect();
+        assert_eq!(matched, vec![true, false]);
+        
+        assert!(engine.explain_transaction(&at(4_700)).remove(0).matched);
+        assert_eq!(engine.evaluate_transaction(&at(4_700)).len(), 1, "explaining 1,600 didn't count as activity");
+    }
+    
+    #[test]
+    fn test_log_matches_regex_adds_captures_to_context() {
+        let engine = FilterEngine::new(vec![
+            filter_with(serde_json::json!({ "all_of": [
+                { "type": "LogMatchesRegex", "pattern": r"Instruction: (?P<kind>MintTo|Burn) amount: (\d+)" }
+            ] })),
+            FilterConfig { id: "invalid".to_string(), ..filter_with(serde_json::json!({ "all_of": [
+                { "type": "LogMatchesRegex", "pattern": "(unclosed" }
+            ] })) },
+        ]);
+        
+        let mut transaction = test_transaction();
+        transaction.log_messages = vec![
+            "Program log: Instruction: Transfer amount: 5".to_string(),
+            "Program log: Instruction: Burn amount: 1200".to_string(),
+        ];
+        let matched = engine.evaluate_transaction(&transaction);
+        assert_eq!(matched.len(), 1, "an invalid pattern never matches");
+        assert_eq!(matched[0].context["log_kind"], "Burn");
+        assert_eq!(matched[0].context["log_2"], "1200");
+        
+        transaction.log_messages.pop();
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+    }
+    
+    #[test]
+    fn test_instruction_data_matches_anchor_discriminator_and_prefix() {
+        let withdraw = anchor_discriminator("withdraw");
+        let mut data = withdraw.clone();
+        data.extend([7, 0, 0, 0]);
+        let mut transaction = test_transaction();
+        let mut instruction = test_instruction("Vault111");
+        instruction.data = bs58::encode(&data).into_string();
+        transaction.instructions = vec![instruction];
+        let matches = |condition: serde_json::Value| {
+            !FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [condition] }))])
+                .evaluate_transaction(&transaction)
+                .is_empty()
+        };
+        
+        assert!(matches(serde_json::json!({ "type": "InstructionData", "program_id": "Vault111", "anchor_instruction": "withdraw" })));
+        assert!(matches(serde_json::json!({
+            "type": "InstructionData", "program_id": "Vault111", "anchor_instruction": "withdraw",
+            "discriminator": "BwAAAA==", "encoding": "Base64"
+        })));
+        assert!(matches(serde_json::json!({
+            "type": "InstructionData", "program_id": "Vault111",
+            "discriminator": bs58::encode(&withdraw).into_string()
+        })));
+        assert!(!matches(serde_json::json!({ "type": "InstructionData", "program_id": "Vault111", "anchor_instruction": "deposit" })));
+        assert!(!matches(serde_json::json!({ "type": "InstructionData", "program_id": "Other111", "anchor_instruction": "withdraw" })));
+        assert!(!matches(serde_json::json!({ "type": "InstructionData", "program_id": "Vault111", "discriminator": "zz", "encoding": "Hex" })));
+    }
+    
+    #[test]
+    fn test_cumulative_volume_sums_mints_within_slot_window() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [{
+            "type": "CumulativeVolume", "mint": "YU", "measure": "Minted",
+            "window_slots": 1000, "operator": "GreaterThan", "amount": 50.0
+        }] }))]);
+        
+        let mint = |slot: u64, amount: f64| {
+            let mut transaction = test_transaction();
+            transaction.slot = slot;
+            transaction.token_balance_changes = serde_json::from_value(serde_json::json!([{
+                "account": "holder", "mint": "YU",
+                "before": { "amount": "0", "decimals": 0, "ui_amount": 0.0 },
+                "after": { "amount": "0", "decimals": 0, "ui_amount": amount },
+                "change": amount
+            }])).unwrap();
+            engine.evaluate_transaction(&transaction)
+        };
+        
+        assert!(mint(100, 30.0).is_empty());
+        let matched = mint(600, 30.0);
+        assert_eq!(matched.len(), 1, "30 + 30 minted within 1000 slots");
+        assert_eq!(matched[0].context["cumulative_volume"], "60 Minted in 1000 slots");
+        assert!(mint(1_200, 30.0).is_empty(), "slot 100 left the window");
+        assert!(mint(1_300, -30.0).is_empty(), "burns add nothing to the minted volume");
+    }
+    
+    #[test]
+    fn test_slot_leader_matches_listed_validators() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({
+            "all_of": [{ "type": "SlotLeader", "leaders": ["validator-a", "validator-b"] }]
+        }))]);
+        assert!(engine.needs_slot_leaders());
+        
+        let mut transaction = test_transaction();
+        assert!(engine.evaluate_transaction(&transaction).is_empty(), "unknown leader never matches");
+        transaction.slot_leader = Some("validator-c".to_string());
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+        transaction.slot_leader = Some("validator-b".to_string());
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+    }
+    
+    #[test]
+    fn test_reward_compares_signed_lamports() {
+        let engine = FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [{
+            "type": "Reward", "pubkey": "leader", "operator": "GreaterThan", "lamports": 1_000_000
+        }] }))]);
+        assert!(engine.needs_rewards());
+        
+        let reward = |pubkey: &str, lamports: i64| -> crate::transaction_extractor::BlockReward {
+            serde_json::from_value(serde_json::json!({
+                "pubkey": pubkey, "lamports": lamports, "post_balance": 0, "reward_type": "fee"
+            })).unwrap()
+        };
+        let mut transaction = test_transaction();
+        transaction.block_rewards = vec![reward("leader", 900_000), reward("other", 5_000_000)].into();
+        assert!(engine.evaluate_transaction(&transaction).is_empty());
+        
+        transaction.block_rewards = vec![reward("leader", 2_000_000)].into();
+        assert_eq!(engine.evaluate_transaction(&transaction).len(), 1);
+    }
+    
+    #[test]
+    fn test_fee_unit_defaults_to_lamports() {
+        let lamports: Condition = serde_json::from_str(
+            r#"{"type":"FeeAmount","operator":"GreaterThan","amount":5000}"#
+        ).unwrap();
+        let sol: Condition = serde_json::from_str(
+            r#"{"type":"FeeAmount","operator":"GreaterThan","amount":0.01,"unit":"Sol"}"#
+        ).unwrap();
+        
+        let Condition::FeeAmount { amount, unit, .. } = lamports else { panic!("expected FeeAmount") };
+        assert_eq!(unit.to_lamports(amount), 5_000);
+        let Condition::FeeAmount { amount, unit, .. } = sol else { panic!("expected FeeAmount") };
+        assert_eq!(unit.to_lamports(amount), 10_000_000);
+    }
+
+    #[test]
+    fn test_priority_orders_matches_and_first_match_only_stops() {
+        let filter = |id: &str, priority: i32| -> FilterConfig {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "name": id, "enabled": true, "conditions": {}, "actions": [], "priority": priority
+            })).unwrap()
+        };
+        let filters = vec![filter("low", 0), filter("high", 10), filter("also_low", 0)];
+        let transaction = test_transaction();
+
+        let ids = |engine: &FilterEngine| -> Vec<String> {
+            engine.evaluate_transaction(&transaction).into_iter().map(|m| m.filter_id).collect()
+        };
+        assert_eq!(ids(&FilterEngine::new(filters.clone()).with_first_match_only(false)), vec!["high", "low", "also_low"]);
+        assert_eq!(ids(&FilterEngine::new(filters).with_first_match_only(true)), vec!["high"]);
+    }
+
+
+    #[test]
+    fn test_transaction_version_tells_v0_from_legacy() {
+        let matches = |condition: serde_json::Value, transaction: &ExtractedTransaction| {
+            !FilterEngine::new(vec![filter_with(serde_json::json!({ "all_of": [condition] }))])
+                .evaluate_transaction(transaction).is_empty()
+        };
+        let mut transaction = test_transaction();
+        assert!(matches(serde_json::json!({ "type": "TransactionVersion", "version": 0 }), &transaction));
+        assert!(!matches(serde_json::json!({ "type": "TransactionVersion" }), &transaction));
+
+        transaction.version = "legacy".to_string();
+        assert!(matches(serde_json::json!({ "type": "TransactionVersion" }), &transaction));
+    }
+
+}
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use crate::filter_engine::FilterEngine;
+use crate::transaction_extractor::ExtractedTransaction;
+
+/// One regression case for the filter set: a transaction and the ids of exactly the filters
+/// that must match it. Stored as `<name>.json` in a fixtures directory.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FilterFixture {
+    #[serde(default)]
+    pub description: Option<String>,
+    pub transaction: ExtractedTransaction,
+    /// Empty means the transaction must not match any filter
+    #[serde(default)]
+    pub expected: Vec<String>,
+}
+
+/// How the engine's matches for a fixture differ from its expectation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixtureMismatch {
+    /// Expected but not matched
+    pub missing: Vec<String>,
+    /// Matched but not expected
+    pub unexpected: Vec<String>,
+}
+
+impl FixtureMismatch {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+/// Every `*.json` fixture in `dir`, sorted by file name
+pub fn load_fixtures(dir: impl AsRef<Path>) -> Result<Vec<(PathBuf, FilterFixture)>> {
+    let dir = dir.as_ref();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read fixtures directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+
+    paths.into_iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+            let fixture = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse fixture {}", path.display()))?;
+            Ok((path, fixture))
+        })
+        .collect()
+}
+
+/// Evaluate the fixture's transaction and compare the matched filter ids with the expected
+/// ones. Evaluation updates the engine's state like live processing does, so stateful
+/// conditions see earlier fixtures of the same run.
+pub fn check_fixture(engine: &FilterEngine, fixture: &FilterFixture) -> FixtureMismatch {
+    let matched: BTreeSet<String> = engine.evaluate_transaction(&fixture.transaction)
+        .into_iter()
+        .map(|matched| matched.filter_id)
+        .collect();
+    let expected: BTreeSet<String> = fixture.expected.iter().cloned().collect();
+
+    FixtureMismatch {
+        missing: expected.difference(&matched).cloned().collect(),
+        unexpected: matched.difference(&expected).cloned().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_engine::tests::test_transaction;
+
+    #[test]
+    fn test_fixture_reports_missing_and_unexpected_filters() {
+        let filters = serde_json::from_value(serde_json::json!([
+            { "id": "any", "name": "Any", "enabled": true, "conditions": {}, "actions": [] },
+            { "id": "failed", "name": "Failed", "enabled": true,
+              "conditions": { "all_of": [{ "type": "TransactionStatus", "success": false }] }, "actions": [] }
+        ])).unwrap();
+        let engine = FilterEngine::new(filters);
+
+        let fixture: FilterFixture = serde_json::from_value(serde_json::json!({
+            "transaction": test_transaction(),
+            "expected": ["failed"]
+        })).unwrap();
+
+        let mismatch = check_fixture(&engine, &fixture);
+        assert_eq!(mismatch.missing, vec!["failed"]);
+        assert_eq!(mismatch.unexpected, vec!["any"]);
+        assert!(!mismatch.is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use crate::checkpoint::write_atomic;
+
+/// Number of newly recorded accounts after which the seen-set is written back to disk
+const SEEN_ACCOUNTS_FLUSH_EVERY: usize = 100;
+/// Recorded transactions between sweeps that drop signers (or token accounts) with no activity
+/// inside the window
+const SIGNER_ACTIVITY_SWEEP_EVERY: usize = 10_000;
+/// Default cap on token accounts tracked by `TransferActivity` (`TRANSFER_VELOCITY_MAX_ACCOUNTS`)
+pub const DEFAULT_TRANSFER_VELOCITY_MAX_ACCOUNTS: usize = 100_000;
+/// Where `CumulativeVolume` windows are kept unless `VOLUME_WINDOWS_FILE` says otherwise
+pub const DEFAULT_VOLUME_WINDOWS_FILE: &str = "volume_windows.json";
+
+/// Persistent record of the token accounts that have held each tracked mint, and of the slot
+/// each fee payer was first seen transacting with a tracked mint.
+///
+/// The set only knows about accounts observed while the monitor was running, so
+/// right after a fresh start nearly every recipient and fee payer looks new.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SeenAccountSet {
+    mints: HashMap<String, HashSet<String>>,
+    /// First-seen slot per mint and fee payer
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    fee_payers: HashMap<String, HashMap<String, u64>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    unsaved: usize,
+}
+
+impl SeenAccountSet {
+    /// Load the seen-set from a JSON file, starting empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut set = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .context("Failed to read seen accounts file")?;
+            serde_json::from_str::<SeenAccountSet>(&content)
+                .context("Failed to parse seen accounts file")?
+        } else {
+            Self::default()
+        };
+        
+        set.path = Some(path.to_path_buf());
+        info!("Loaded {} seen accounts from {:?}", set.len(), path);
+        Ok(set)
+    }
+    
+    pub fn contains(&self, mint: &str, account: &str) -> bool {
+        self.mints
+            .get(mint)
+            .map_or(false, |accounts| accounts.contains(account))
+    }
+    
+    /// Record an account as a holder of `mint`, returning true if it wasn't seen before
+    pub fn insert(&mut self, mint: &str, account: &str) -> bool {
+        let inserted = self.mints
+            .entry(mint.to_string())
+            .or_insert_with(HashSet::new)
+            .insert(account.to_string());
+        
+        if inserted {
+            self.count_unsaved();
+        }
+        
+        inserted
+    }
+    
+    /// Slot `payer` was first seen paying for a transaction with `mint`
+    pub fn fee_payer_first_seen(&self, mint: &str, payer: &str) -> Option<u64> {
+        self.fee_payers.get(mint)?.get(payer).copied()
+    }
+    
+    /// Record `payer` at `slot`, keeping the earliest slot since replays can run out of order
+    pub fn record_fee_payer(&mut self, mint: &str, payer: &str, slot: u64) {
+        let payers = self.fee_payers.entry(mint.to_string()).or_default();
+        match payers.get_mut(payer) {
+            Some(first_seen) => *first_seen = (*first_seen).min(slot),
+            None => {
+                payers.insert(payer.to_string(), slot);
+                self.count_unsaved();
+            },
+        }
+    }
+    
+    fn count_unsaved(&mut self) {
+        self.unsaved += 1;
+        if self.unsaved >= SEEN_ACCOUNTS_FLUSH_EVERY {
+            if let Err(e) = self.save() {
+                warn!("Failed to save seen accounts: {}", e);
+            }
+        }
+    }
+    
+    /// Write the seen-set back to its file (no-op for in-memory sets)
+    pub fn save(&mut self) -> Result<()> {
+        if let Some(path) = &self.path {
+            let json = serde_json::to_string(self)?;
+            write_atomic(path, json.as_bytes())
+                .with_context(|| format!("Failed to write seen accounts file {:?}", path))?;
+        }
+        self.unsaved = 0;
+        Ok(())
+    }
+    
+    pub fn len(&self) -> usize {
+        self.mints.values().map(|accounts| accounts.len()).sum::<usize>()
+            + self.fee_payers.values().map(|payers| payers.len()).sum::<usize>()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Recent transaction times (unix seconds) per mint and signer, for `RepeatedSigner`. Kept in
+/// memory only, so counts start from zero after a restart. Times older than the longest window
+/// are dropped as new ones arrive.
+#[derive(Debug, Default)]
+pub struct SignerActivity {
+    times: HashMap<(String, String), VecDeque<i64>>,
+    max_window_secs: i64,
+    recorded: usize,
+}
+
+impl SignerActivity {
+    pub fn new(max_window_secs: u64) -> Self {
+        Self {
+            max_window_secs: max_window_secs as i64,
+            ..Self::default()
+        }
+    }
+    
+    /// Recorded transactions by `signer` on `mint` in the `window_secs` up to and including `at`.
+    /// Replayed slots can arrive out of order, so times are compared rather than assumed sorted.
+    pub fn count(&self, mint: &str, signer: &str, at: i64, window_secs: u64) -> usize {
+        let start = at - window_secs as i64;
+        self.times
+            .get(&(mint.to_string(), signer.to_string()))
+            .map_or(0, |times| times.iter().filter(|&&t| t > start && t <= at).count())
+    }
+    
+    pub fn record(&mut self, mint: &str, signer: &str, at: i64) {
+        let cutoff = at - self.max_window_secs;
+        let times = self.times
+            .entry((mint.to_string(), signer.to_string()))
+            .or_default();
+        times.push_back(at);
+        times.retain(|&t| t > cutoff);
+        
+        self.recorded += 1;
+        if self.recorded % SIGNER_ACTIVITY_SWEEP_EVERY == 0 {
+            self.times.retain(|_, times| times.iter().any(|&t| t > cutoff));
+        }
+    }
+}
+
+/// Recent token balance changes (unix seconds, signed amount) per mint and token account, for
+/// `TransferVelocity`. In memory only, like `SignerActivity`: flows older than the longest window
+/// are dropped as new ones arrive, and at most `max_accounts` accounts are tracked, evicting the
+/// one idle longest when a new account would go over.
+#[derive(Debug, Default)]
+pub struct TransferActivity {
+    flows: HashMap<(String, String), VecDeque<(i64, f64)>>,
+    max_window_secs: i64,
+    max_accounts: usize,
+    recorded: usize,
+}
+
+impl TransferActivity {
+    pub fn new(max_window_secs: u64, max_accounts: usize) -> Self {
+        Self {
+            max_window_secs: max_window_secs as i64,
+            max_accounts: max_accounts.max(1),
+            ..Self::default()
+        }
+    }
+    
+    /// Inflow and outflow (both positive) of `account` on `mint` in the `window_secs` up to and
+    /// including `at`
+    pub fn turnover(&self, mint: &str, account: &str, at: i64, window_secs: u64) -> (f64, f64) {
+        let start = at - window_secs as i64;
+        self.flows
+            .get(&(mint.to_string(), account.to_string()))
+            .into_iter()
+            .flatten()
+            .filter(|(t, _)| *t > start && *t <= at)
+            .fold((0.0, 0.0), |(inflow, outflow), (_, change)| {
+                if *change > 0.0 { (inflow + change, outflow) } else { (inflow, outflow - change) }
+            })
+    }
+    
+    pub fn record(&mut self, mint: &str, account: &str, at: i64, change: f64) {
+        let cutoff = at - self.max_window_secs;
+        let key = (mint.to_string(), account.to_string());
+        if !self.flows.contains_key(&key) && self.flows.len() >= self.max_accounts {
+            self.flows.retain(|_, flows| flows.iter().any(|(t, _)| *t > cutoff));
+            if self.flows.len() >= self.max_accounts {
+                self.evict_idlest();
+            }
+        }
+        
+        let flows = self.flows.entry(key).or_default();
+        flows.push_back((at, change));
+        flows.retain(|(t, _)| *t > cutoff);
+        
+        self.recorded += 1;
+        if self.recorded % SIGNER_ACTIVITY_SWEEP_EVERY == 0 {
+            self.flows.retain(|_, flows| flows.iter().any(|(t, _)| *t > cutoff));
+        }
+    }
+    
+    pub fn tracked_accounts(&self) -> usize {
+        self.flows.len()
+    }
+    
+    fn evict_idlest(&mut self) {
+        let idlest = self.flows.iter()
+            .min_by_key(|(_, flows)| flows.iter().map(|(t, _)| *t).max().unwrap_or(i64::MIN))
+            .map(|(key, _)| key.clone());
+        if let Some(key) = idlest {
+            self.flows.remove(&key);
+        }
+    }
+}
+
+/// A sliding window ending at the transaction being evaluated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeWindow {
+    Slots(u64),
+    /// Block time, like the other windowed conditions
+    Secs(u64),
+}
+
+/// What one transaction contributed to a volume key
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct VolumeEntry {
+    slot: u64,
+    at: i64,
+    amount: f64,
+}
+
+impl VolumeEntry {
+    fn within(&self, window: VolumeWindow, slot: u64, at: i64) -> bool {
+        match window {
+            VolumeWindow::Slots(slots) => self.slot <= slot && self.slot.saturating_add(slots) > slot,
+            VolumeWindow::Secs(secs) => {
+                let secs = i64::try_from(secs).unwrap_or(i64::MAX);
+                self.at <= at && self.at.saturating_add(secs) > at
+            },
+        }
+    }
+}
+
+/// Per-transaction amounts for `CumulativeVolume`, by volume key (mint, optional account and
+/// measure). Unlike `SignerActivity` this is persisted, saved with the filter state next to the
+/// slot checkpoint, so a window spanning a restart keeps what came before it. Entries outside
+/// every window configured for their key are dropped as new ones arrive.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VolumeWindows {
+    entries: HashMap<String, VecDeque<VolumeEntry>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl VolumeWindows {
+    /// Load the windows from a JSON file, starting empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut windows = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .context("Failed to read volume windows file")?;
+            serde_json::from_str::<VolumeWindows>(&content)
+                .context("Failed to parse volume windows file")?
+        } else {
+            Self::default()
+        };
+        
+        windows.path = Some(path.to_path_buf());
+        info!("Loaded {} volume entries from {:?}", windows.len(), path);
+        Ok(windows)
+    }
+    
+    /// Amounts recorded for `key` within `window` up to and including `slot` / `at`. Replayed
+    /// slots can arrive out of order, so entries are filtered rather than assumed sorted.
+    pub fn total(&self, key: &str, window: VolumeWindow, slot: u64, at: i64) -> f64 {
+        self.entries
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.within(window, slot, at))
+            .map(|entry| entry.amount)
+            .sum()
+    }
+    
+    /// Add a transaction's amount, dropping entries of `key` outside all of `retention`
+    pub fn record(&mut self, key: &str, slot: u64, at: i64, amount: f64, retention: &[VolumeWindow]) {
+        let entries = self.entries.entry(key.to_string()).or_default();
+        entries.push_back(VolumeEntry { slot, at, amount });
+        entries.retain(|entry| {
+            retention.iter().any(|window| entry.within(*window, slot, at))
+                // Entries from slots ahead of this one (out-of-order replays) are kept
+                || entry.slot > slot
+        });
+    }
+    
+    /// Write the windows back to their file (no-op for in-memory windows)
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            let json = serde_json::to_string(self)?;
+            write_atomic(path, json.as_bytes())
+                .with_context(|| format!("Failed to write volume windows file {:?}", path))?;
+        }
+        Ok(())
+    }
+    
+    pub fn len(&self) -> usize {
+        self.entries.values().map(|entries| entries.len()).sum()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_fee_payer_keeps_earliest_slot() {
+        let mut seen = SeenAccountSet::default();
+        assert_eq!(seen.fee_payer_first_seen("YU", "payer"), None);
+        
+        seen.record_fee_payer("YU", "payer", 200);
+        seen.record_fee_payer("YU", "payer", 300);
+        seen.record_fee_payer("YU", "payer", 150);
+        
+        assert_eq!(seen.fee_payer_first_seen("YU", "payer"), Some(150));
+        assert_eq!(seen.fee_payer_first_seen("USDC", "payer"), None);
+        
+        // Sets written before fee payers were tracked still load
+        let old: SeenAccountSet = serde_json::from_str(r#"{"mints":{"YU":["account"]}}"#).unwrap();
+        assert!(old.contains("YU", "account"));
+    }
+    
+    #[test]
+    fn test_volume_windows_sum_within_slot_and_time_windows() {
+        let mut windows = VolumeWindows::default();
+        let retention = [VolumeWindow::Slots(100)];
+        windows.record("YU:Minted", 1_000, 10, 5.0, &retention);
+        windows.record("YU:Minted", 1_050, 20, 7.0, &retention);
+        windows.record("YU:Minted", 1_120, 30, 1.0, &retention);
+        
+        assert_eq!(windows.total("YU:Minted", VolumeWindow::Slots(100), 1_120, 30), 8.0);
+        assert_eq!(windows.total("YU:Minted", VolumeWindow::Secs(15), 1_120, 30), 8.0);
+        assert_eq!(windows.total("YU:Minted", VolumeWindow::Slots(1_000), 1_049, 19), 0.0, "slot 1000 fell out of retention");
+        assert_eq!(windows.total("YU:Burned", VolumeWindow::Slots(100), 1_120, 30), 0.0);
+        assert_eq!(windows.len(), 2);
+        
+        // Windows reaching past the end of time don't overflow
+        assert_eq!(windows.total("YU:Minted", VolumeWindow::Slots(u64::MAX), 1_120, 30), 8.0);
+        assert_eq!(windows.total("YU:Minted", VolumeWindow::Secs(u64::MAX), 1_120, 30), 8.0);
+    }
+    
+    #[test]
+    fn test_transfer_activity_evicts_idlest_account_at_capacity() {
+        let mut activity = TransferActivity::new(60, 2);
+        activity.record("YU", "a", 100, 5.0);
+        activity.record("YU", "a", 110, -4.0);
+        activity.record("YU", "b", 105, 1.0);
+        assert_eq!(activity.turnover("YU", "a", 110, 60), (5.0, 4.0));
+        
+        activity.record("YU", "c", 120, 2.0);
+        assert_eq!(activity.tracked_accounts(), 2);
+        assert_eq!(activity.turnover("YU", "b", 120, 60), (0.0, 0.0), "b was idle longest");
+        assert_eq!(activity.turnover("YU", "a", 120, 60), (5.0, 4.0));
+    }
+}
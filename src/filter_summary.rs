@@ -0,0 +1,269 @@
+use crate::filter_engine::{Action, ComparisonOperator, Condition, ConditionSet, FeeUnit, FilterConfig, FlowDirection};
+
+const DEFAULT_YU_MINT: &str = "YUYAiJo8KVbnc6Fb6h3MnH2VGND4uGWDH4iLnw7DLEu";
+
+/// One-line description of a filter, e.g. `YU mint >= 30M → Critical [telegram, database]; store → critical_mints`
+pub fn describe_filter(filter: &FilterConfig) -> String {
+    let mut line = describe_conditions(&filter.conditions);
+
+    let actions: Vec<String> = filter.actions.iter().map(describe_action).collect();
+    if !actions.is_empty() {
+        line.push_str(" → ");
+        line.push_str(&actions.join("; "));
+    }
+
+    if let Some(cooldown) = filter.cooldown_secs {
+        line.push_str(&format!(" (cooldown {}s)", cooldown));
+    }
+    if filter.priority != 0 {
+        line.push_str(&format!(" (priority {})", filter.priority));
+    }
+
+    line
+}
+
+pub fn describe_conditions(conditions: &ConditionSet) -> String {
+    let group = |entries: &Option<Vec<crate::filter_engine::ConditionEntry>>| -> Vec<String> {
+        entries.iter()
+            .flatten()
+            .filter(|entry| entry.is_enabled())
+            .map(|entry| describe_condition(&entry.condition))
+            .collect()
+    };
+
+    let mut parts = Vec::new();
+
+    let all = group(&conditions.all_of);
+    if !all.is_empty() {
+        parts.push(all.join(" AND "));
+    }
+
+    let any = group(&conditions.any_of);
+    match any.len() {
+        0 => {},
+        1 => parts.push(any[0].clone()),
+        _ => parts.push(format!("({})", any.join(" OR "))),
+    }
+
+    let none = group(&conditions.none_of);
+    if !none.is_empty() {
+        parts.push(format!("NOT ({})", none.join(" OR ")));
+    }
+
+    if parts.is_empty() {
+        "every transaction".to_string()
+    } else {
+        parts.join(" AND ")
+    }
+}
+
+pub fn describe_condition(condition: &Condition) -> String {
+    match condition {
+        Condition::ProgramInvoked { program_id } => {
+            format!("program {} invoked", short_address(program_id))
+        },
+        Condition::ProgramInvokedAny { program_ids } => {
+            let programs: Vec<String> = program_ids.iter().map(|id| short_address(id)).collect();
+            format!("any of programs {} invoked", programs.join(", "))
+        },
+        Condition::TokenTransfer { mint, operator, amount } => {
+            let token = mint.as_deref().map(mint_label).unwrap_or_else(|| "any token".to_string());
+            format!("{} transfer {} {}", token, symbol(operator), format_amount(*amount))
+        },
+        Condition::TokenMint { mint, operator, amount } => {
+            format!("{} mint {} {}", mint_label(mint), symbol(operator), format_amount(*amount))
+        },
+        Condition::TokenBurn { mint, operator, amount } => {
+            format!("{} burn {} {}", mint_label(mint), symbol(operator), format_amount(*amount))
+        },
+        Condition::BalanceChange { account, operator, amount } => {
+            let target = account.as_deref().map(short_address).unwrap_or_else(|| "any account".to_string());
+            format!("SOL change of {} {} {}", target, symbol(operator), format_amount(*amount))
+        },
+        Condition::TransactionStatus { success } => {
+            if *success { "succeeded".to_string() } else { "failed".to_string() }
+        },
+        Condition::AccountSolFlow { account, direction, operator, lamports } => {
+            let flow = match direction {
+                FlowDirection::In => "SOL in",
+                FlowDirection::Out => "SOL out",
+                FlowDirection::Either => "net SOL",
+            };
+            format!("{} {} {} {} lamports", short_address(account), flow, symbol(operator), lamports)
+        },
+        Condition::FailedWithMint { mint } => {
+            format!("failed tx touching {}", mint_label(mint))
+        },
+        Condition::FeeAmount { operator, amount, unit } => match unit {
+            FeeUnit::Lamports => format!("fee {} {} lamports", symbol(operator), amount),
+            FeeUnit::Sol => format!("fee {} {} SOL", symbol(operator), amount),
+        },
+        Condition::InstructionCount { operator, count } => {
+            format!("instructions {} {}", symbol(operator), count)
+        },
+        Condition::DistinctProgramCount { operator, count } => {
+            format!("distinct programs {} {}", symbol(operator), count)
+        },
+        Condition::CpiFrom { parent_program, child_program } => {
+            format!("{} called via CPI from {}", short_address(child_program), short_address(parent_program))
+        },
+        Condition::TransactionSizeBytes { operator, bytes } => {
+            format!("size {} {} bytes", symbol(operator), bytes)
+        },
+        Condition::TransactionVersion { version } => match version {
+            Some(version) => format!("v{} transaction", version),
+            None => "legacy transaction".to_string(),
+        },
+        Condition::AccountInvolved { account } => {
+            format!("involves {}", short_address(account))
+        },
+        Condition::RepeatedSigner { mint, min_transactions, window_secs } => {
+            format!("same signer >= {} {} txs in {}s", min_transactions, mint_label(mint), window_secs)
+        },
+        Condition::TransferVelocity { mint, min_turnover, window_secs } => {
+            format!("{} in+out >= {} per account in {}s", mint_label(mint), format_amount(*min_turnover), window_secs)
+        },
+        Condition::MintAndBurn { mint } => {
+            format!("{} mint + burn in one tx", mint_label(mint))
+        },
+        Condition::MultiMint { mints, require_all, operator, amount } => {
+            let labels: Vec<String> = mints.iter().map(|mint| mint_label(mint)).collect();
+            let joiner = if *require_all { " + " } else { " or " };
+            format!("{} each moved {} {}", labels.join(joiner), symbol(operator), format_amount(*amount))
+        },
+        Condition::FreshFeePayer { mint, within_slots } => {
+            format!("{} fee payer first seen <= {} slots ago", mint_label(mint), within_slots)
+        },
+        Condition::TimeSinceLastMatch { quiet_secs } => {
+            format!("first match after {}s quiet", quiet_secs)
+        },
+        Condition::Reward { pubkey, operator, lamports } => {
+            let target = pubkey.as_deref().map(short_address).unwrap_or_else(|| "any account".to_string());
+            format!("block reward to {} {} {} lamports", target, symbol(operator), lamports)
+        },
+        Condition::SupplyPercentage { mint, operator, percent } => {
+            format!("{} moved {} {}% of supply", mint_label(mint), symbol(operator), percent)
+        },
+        Condition::AccountOwnedBy { owner_program } => {
+            format!("writes account owned by {}", short_address(owner_program))
+        },
+        Condition::SlotLeader { leaders } => {
+            let leaders: Vec<String> = leaders.iter().map(|leader| short_address(leader)).collect();
+            format!("slot leader in [{}]", leaders.join(", "))
+        },
+        Condition::LogContains { pattern, case_sensitive } => {
+            let suffix = if *case_sensitive { "" } else { " (any case)" };
+            format!("log contains \"{}\"{}", pattern, suffix)
+        },
+        Condition::InstructionData { program_id, discriminator, encoding, anchor_instruction } => {
+            let mut line = format!("{} instruction", short_address(program_id));
+            if let Some(name) = anchor_instruction {
+                line.push_str(&format!(" {}", name));
+            }
+            if let Some(discriminator) = discriminator {
+                line.push_str(&format!(" with data {} ({:?})", discriminator, encoding));
+            }
+            line
+        },
+        Condition::CumulativeVolume { mint, account, measure, window_slots, window_secs, operator, amount } => {
+            let scope = account.as_deref().map(|account| format!(" by {}", short_address(account))).unwrap_or_default();
+            let window = match (window_slots, window_secs) {
+                (Some(slots), _) => format!("{} slots", slots),
+                (None, Some(secs)) => format!("{}s", secs),
+                (None, None) => "no window".to_string(),
+            };
+            format!("{:?} {}{} {} {} over {}", measure, mint_label(mint), scope, symbol(operator), amount, window)
+        },
+        Condition::LogMatchesRegex { pattern } => format!("log matches /{}/", pattern),
+        Condition::ProgramDataContains { program_id, decoded_contains_hex } => {
+            let source = program_id.as_deref()
+                .map(|id| format!(" from {}", short_address(id)))
+                .unwrap_or_default();
+            match decoded_contains_hex {
+                Some(hex) => format!("program data{} contains 0x{}", source, hex.trim_start_matches("0x")),
+                None => format!("program data{}", source),
+            }
+        },
+        Condition::BridgeTransfer { mint, operator, amount, programs } => {
+            let token = mint.as_deref().map(mint_label).unwrap_or_else(|| "any token".to_string());
+            let bridges = match programs {
+                Some(programs) => programs.iter().map(|p| short_address(p)).collect::<Vec<_>>().join("/"),
+                None => "default bridges".to_string(),
+            };
+            format!("{} bridge transfer via {} {} {}", token, bridges, symbol(operator), format_amount(*amount))
+        },
+        Condition::DepositToProgram { mint, program_id, operator, amount } => {
+            format!("{} deposit into {} {} {}", mint_label(mint), short_address(program_id), symbol(operator), format_amount(*amount))
+        },
+        Condition::ComputeUnitLimit { operator, units } => {
+            format!("CU limit {} {}", symbol(operator), units)
+        },
+        Condition::MintToNewAccounts { mint, min_new_recipients } => {
+            format!("{} minted to >= {} new accounts", mint_label(mint), min_new_recipients)
+        },
+    }
+}
+
+pub fn describe_action(action: &Action) -> String {
+    match action {
+        Action::Alert { severity, channels } => format!("{:?} [{}]", severity, channels.join(", ")),
+        Action::Store { collection } => format!("store → {}", collection),
+        // Webhook URLs usually embed a secret token, so only the host is shown
+        Action::Webhook { url, method, .. } => format!("{} webhook → {}", method, url_host(url)),
+        Action::Log { level, .. } => format!("log ({})", level),
+    }
+}
+
+fn symbol(operator: &ComparisonOperator) -> &'static str {
+    match operator {
+        ComparisonOperator::GreaterThan => ">",
+        ComparisonOperator::LessThan => "<",
+        ComparisonOperator::Equal => "==",
+        ComparisonOperator::GreaterThanOrEqual => ">=",
+        ComparisonOperator::LessThanOrEqual => "<=",
+        ComparisonOperator::NotEqual => "!=",
+    }
+}
+
+/// `YU` for the configured YU mint, otherwise a shortened address
+pub fn mint_label(mint: &str) -> String {
+    let yu_mint = std::env::var("YU_TOKEN_ADDRESS").unwrap_or_else(|_| DEFAULT_YU_MINT.to_string());
+    if mint == yu_mint {
+        "YU".to_string()
+    } else {
+        short_address(mint)
+    }
+}
+
+/// First and last four characters of long addresses, e.g. `YUYA…DLEu`
+pub fn short_address(address: &str) -> String {
+    if address.is_ascii() && address.len() > 12 {
+        format!("{}…{}", &address[..4], &address[address.len() - 4..])
+    } else {
+        address.to_string()
+    }
+}
+
+/// Compact amount with a k/M/B suffix, e.g. `1.5M`
+pub fn format_amount(amount: f64) -> String {
+    let abs = amount.abs();
+    let (value, suffix) = if abs >= 1_000_000_000.0 {
+        (amount / 1_000_000_000.0, "B")
+    } else if abs >= 1_000_000.0 {
+        (amount / 1_000_000.0, "M")
+    } else if abs >= 1_000.0 {
+        (amount / 1_000.0, "k")
+    } else {
+        (amount, "")
+    };
+
+    let formatted = format!("{:.2}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}", formatted, suffix)
+}
+
+/// Host part of a URL, for logging endpoints without exposing tokens in the path or query
+pub fn url_host(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    without_scheme.split('/').next().unwrap_or(without_scheme)
+}
@@ -3,70 +3,318 @@ use solana_client::rpc_client::RpcClient;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error, debug};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::filter_engine::{FilterEngine, FilterConfig, Action, AlertSeverity, create_yuya_mint_filters};
+use crate::filter_engine::{FilterEngine, FilterConfig, Action, AlertSeverity, Condition, create_yuya_mint_filters};
 use crate::telegram_notifier::TelegramNotifier;
 use crate::discord_notifier::DiscordNotifier;
 use crate::slack_notifier::SlackNotifier;
-use crate::transaction_extractor::{TransactionExtractor, ExtractedTransaction};
-use crate::notifications::NotificationManager;
-use crate::config_manager::ConfigManager;
+use crate::email_notifier::EmailNotifier;
+use crate::transaction_extractor::{TransactionExtractor, ExtractedTransaction, to_export_json, ExportWriter};
+use crate::notifications::{create_alert_from_transaction, Alert, NotificationChannel, NotificationManager, TransactionEvent};
+use crate::alert_digest::AlertDigest;
+use crate::config_manager::{AlertType, ConfigManager, RetentionPolicy};
+use crate::slot_leader_cache::SlotLeaderCache;
+use crate::account_owner_cache::AccountOwnerCache;
+use crate::mint_supply_cache::MintSupplyCache;
+use crate::result_sink::ResultSink;
+use crate::webhook_sender::{render_payload, WebhookSender};
+use crate::storage_backend::{configured_storage_backend, StorageBackend};
+use solana_commitment_config::CommitmentConfig;
+use solana_transaction_status::UiConfirmedBlock;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use crate::rpc_client_with_failover::RpcClientWithFailover;
 
 pub struct FilteredTransactionMonitor {
     rpc_client: Arc<RpcClient>,
-    pub filter_engine: Arc<FilterEngine>,
+    /// Swapped as a whole by `reload_config`; a slot keeps the engine it started with
+    filter_engine: std::sync::RwLock<Arc<FilterEngine>>,
     telegram_notifier: Option<Arc<TelegramNotifier>>,
     slack_notifier: Option<Arc<SlackNotifier>>,
+    discord_notifier: Option<Arc<DiscordNotifier>>,
+    email_notifier: Option<Arc<EmailNotifier>>,
+    /// Webhook actions to anything but Discord
+    webhook_sender: Arc<WebhookSender>,
     notification_manager: Arc<RwLock<NotificationManager>>,
+    /// Low/Medium Telegram and Slack alerts wait here when digest mode is on (`DIGEST_INTERVAL_MINS`)
+    alert_digest: Option<Arc<AlertDigest>>,
     transaction_extractor: Arc<TransactionExtractor>,
     storage: Arc<RwLock<TransactionStorage>>,
-    config_manager: Option<Arc<ConfigManager>>,
+    /// Swapped together with `filter_engine` by `reload_config`
+    config_manager: std::sync::RwLock<Option<Arc<ConfigManager>>>,
+    /// Config directory and strictness of `from_config_dir`, for `reload_config`
+    config_source: Option<(std::path::PathBuf, bool)>,
+    /// Running alert cooldown per filter id, with the matches it held back
+    alert_cooldowns: Arc<RwLock<HashMap<String, AlertCooldown>>>,
+    slot_leaders: Option<Arc<SlotLeaderCache>>,
+    /// Only set when a filter uses `AccountOwnedBy`
+    account_owners: Option<Arc<AccountOwnerCache>>,
+    /// Only set when a filter uses `SupplyPercentage`
+    mint_supplies: Option<Arc<MintSupplyCache>>,
+    /// Called with every match after its actions ran, in registration order
+    sinks: Vec<Arc<dyn ResultSink>>,
+    /// Durable copy of `Store` actions (`STORAGE_BACKEND`); `storage` keeps matches in memory either way
+    storage_backend: Option<Arc<dyn StorageBackend>>,
+    /// When a slot was last processed successfully (or the monitor started), for the silence watchdog
+    last_processed: Arc<std::sync::Mutex<Instant>>,
+    /// Totals since startup, for the HTTP API's `/status`
+    processed_slots: std::sync::atomic::AtomicU64,
+    matches_found: std::sync::atomic::AtomicU64,
+    /// Highest slot processed so far; 0 until the first one
+    highest_slot: std::sync::atomic::AtomicU64,
+    /// Chain tip the monitor loop last read; 0 until it reads one
+    chain_tip: std::sync::atomic::AtomicU64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredTransaction {
     pub transaction: ExtractedTransaction,
     pub matched_filters: Vec<String>,
     pub stored_at: DateTime<Utc>,
     pub collection: String,
+    /// Leader of the transaction's slot, when leader attribution is enabled
+    pub slot_leader: Option<String>,
+}
+
+impl StoredTransaction {
+    /// A fresh record of `transaction` matched by `filter_id`, stored now into `collection`
+    pub fn matched(transaction: ExtractedTransaction, collection: &str, filter_id: &str) -> Self {
+        Self {
+            slot_leader: transaction.slot_leader.clone(),
+            transaction,
+            matched_filters: vec![filter_id.to_string()],
+            stored_at: Utc::now(),
+            collection: collection.to_string(),
+        }
+    }
+}
+
+/// What the monitor has processed since startup
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ProcessingStats {
+    pub last_processed_slot: Option<u64>,
+    /// Chain tip as of the monitor loop's last read, see `record_chain_tip`
+    pub chain_tip: Option<u64>,
+    pub slots_processed: u64,
+    pub matches_found: u64,
+}
+
+/// Where one slot's processing time went, to tell RPC-bound from CPU-bound runs apart
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlotTimings {
+    /// `getBlock`; zero for blocks handed in by the caller
+    pub fetch: Duration,
+    pub extract: Duration,
+    /// Account owner, mint supply and slot leader lookups needed before evaluation
+    pub lookups: Duration,
+    pub evaluate: Duration,
+    /// Alerts, webhooks, storage and result sinks of the matches
+    pub actions: Duration,
+}
+
+/// Signatures listed in a cooldown digest; further held-back matches are only counted
+const MAX_DIGEST_SIGNATURES: usize = 10;
+
+/// A filter's alert cooldown: when it started and the matches whose chat alerts it held back
+#[derive(Debug, Clone)]
+struct AlertCooldown {
+    started: Instant,
+    duration: Duration,
+    filter_name: String,
+    /// Chat alerts the filter would have sent, which receive the digest
+    alerts: Vec<Action>,
+    /// Signature and slot of the first `MAX_DIGEST_SIGNATURES` held-back matches
+    suppressed: Vec<(String, u64)>,
+    suppressed_count: usize,
+}
+
+impl AlertCooldown {
+    fn is_active(&self) -> bool {
+        self.started.elapsed() < self.duration
+    }
+    
+    /// Title and body of the digest for the matches held back, or None if there were none
+    fn digest(&self) -> Option<(String, String)> {
+        if self.suppressed_count == 0 {
+            return None;
+        }
+        
+        let title = format!("🔁 {}: {} more match(es)", self.filter_name, self.suppressed_count);
+        let mut body = format!("Held back during the {}s alert cooldown:\n", self.duration.as_secs());
+        for (signature, slot) in &self.suppressed {
+            body.push_str(&format!("• {} (slot {})\n", signature, slot));
+        }
+        if self.suppressed_count > self.suppressed.len() {
+            body.push_str(&format!("… and {} more\n", self.suppressed_count - self.suppressed.len()));
+        }
+        Some((title, body))
+    }
+}
+
+/// Where a stored record lives: its collection and its insertion number within that collection
+type RecordKey = (String, u64);
+
+/// Most recent failed actions kept in memory; the `failed_actions` log file keeps all of them
+const MAX_FAILED_ACTIONS: usize = 1_000;
+
+/// A matched transaction whose alert or webhook could not be delivered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedAction {
+    pub transaction: ExtractedTransaction,
+    pub filter_id: String,
+    pub filter_name: String,
+    /// Alert channel (`telegram`, `slack`, `discord`, `email`), `webhook:<host>` or `storage:<backend>`
+    pub channel: String,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl FailedAction {
+    /// One JSON line per failure, for replaying or investigating deliveries later
+    fn append(&self, path: &str) -> Result<()> {
+        use std::io::Write;
+        
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path))?;
+        writeln!(file, "{}", serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path))?;
+        Ok(())
+    }
+}
+
+/// `FAILED_ACTIONS_FILE`, or `failed_actions.jsonl`
+fn failed_actions_path() -> String {
+    std::env::var("FAILED_ACTIONS_FILE")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| "failed_actions.jsonl".to_string())
 }
 
 pub struct TransactionStorage {
     collections: HashMap<String, Vec<StoredTransaction>>,
+    /// Keyed by collection, `"*"` as the fallback; collections without a policy grow unbounded
+    retention: HashMap<String, RetentionPolicy>,
+    /// Records dropped from the front of each collection by retention, to turn insertion numbers
+    /// into positions
+    evicted: HashMap<String, u64>,
+    /// Records whose token balance changes include the mint
+    by_mint: HashMap<String, BTreeSet<RecordKey>>,
+    by_signature: HashMap<String, BTreeSet<RecordKey>>,
+    failed_actions: VecDeque<FailedAction>,
 }
 
 impl TransactionStorage {
     pub fn new() -> Self {
         Self {
             collections: HashMap::new(),
+            retention: HashMap::new(),
+            evicted: HashMap::new(),
+            by_mint: HashMap::new(),
+            by_signature: HashMap::new(),
+            failed_actions: VecDeque::new(),
         }
     }
     
+    pub fn with_retention(mut self, retention: HashMap<String, RetentionPolicy>) -> Self {
+        self.retention = retention;
+        self
+    }
+    
     pub fn store_transaction(
         &mut self,
         transaction: ExtractedTransaction,
         collection: &str,
         filter_id: &str,
     ) {
-        let stored = StoredTransaction {
-            transaction,
-            matched_filters: vec![filter_id.to_string()],
-            stored_at: Utc::now(),
-            collection: collection.to_string(),
-        };
-        
-        self.collections
+        self.insert(StoredTransaction::matched(transaction, collection, filter_id));
+    }
+    
+    /// Store a record from elsewhere (e.g. an earlier export) into `collection`, keeping its
+    /// matched filters and original `stored_at`
+    pub fn import_record(&mut self, mut stored: StoredTransaction, collection: &str) {
+        stored.collection = collection.to_string();
+        self.insert(stored);
+    }
+    
+    fn insert(&mut self, stored: StoredTransaction) {
+        let collection = stored.collection.clone();
+        let collection = collection.as_str();
+        let entries = self.collections
             .entry(collection.to_string())
-            .or_insert_with(Vec::new)
-            .push(stored);
+            .or_insert_with(Vec::new);
+        let evicted_before = self.evicted.get(collection).copied().unwrap_or(0);
+        let key = (collection.to_string(), evicted_before + entries.len() as u64);
+        index_record(&mut self.by_mint, &mut self.by_signature, &stored, &key, true);
+        entries.push(stored);
+        
+        let policy = self.retention.get(collection).or_else(|| self.retention.get("*"));
+        if let Some(policy) = policy {
+            let evicted = apply_retention(entries, policy, Utc::now());
+            if !evicted.is_empty() {
+                debug!("Evicted {} records from collection '{}'", evicted.len(), collection);
+                for (offset, record) in evicted.iter().enumerate() {
+                    let key = (collection.to_string(), evicted_before + offset as u64);
+                    index_record(&mut self.by_mint, &mut self.by_signature, record, &key, false);
+                }
+                *self.evicted.entry(collection.to_string()).or_insert(0) += evicted.len() as u64;
+            }
+        }
     }
     
     pub fn get_collection(&self, collection: &str) -> Option<&Vec<StoredTransaction>> {
         self.collections.get(collection)
     }
     
+    /// The size of `collection` and its records from `offset` on, at most `limit` of them;
+    /// the page is empty past the end
+    pub fn get_page(&self, collection: &str, offset: usize, limit: usize) -> Option<(usize, &[StoredTransaction])> {
+        let entries = self.collections.get(collection)?;
+        let start = offset.min(entries.len());
+        let end = start.saturating_add(limit).min(entries.len());
+        Some((entries.len(), &entries[start..end]))
+    }
+    
+    /// Stored records, across all collections, with a token balance change of `mint`, oldest
+    /// first within each collection. Served from an index kept up to date on store and eviction.
+    pub fn get_matches_by_mint(&self, mint: &str) -> Vec<&StoredTransaction> {
+        self.lookup(self.by_mint.get(mint))
+    }
+    
+    /// Every stored record of the transaction `signature` (one per collection it was stored to)
+    pub fn get_matches_by_signature(&self, signature: &str) -> Vec<&StoredTransaction> {
+        self.lookup(self.by_signature.get(signature))
+    }
+    
+    fn lookup(&self, keys: Option<&BTreeSet<RecordKey>>) -> Vec<&StoredTransaction> {
+        keys.into_iter()
+            .flatten()
+            .filter_map(|(collection, number)| {
+                let evicted = self.evicted.get(collection).copied().unwrap_or(0);
+                let position = usize::try_from(number.checked_sub(evicted)?).ok()?;
+                self.collections.get(collection)?.get(position)
+            })
+            .collect()
+    }
+    
+    pub fn record_failed_action(&mut self, failed: FailedAction) {
+        if self.failed_actions.len() >= MAX_FAILED_ACTIONS {
+            self.failed_actions.pop_front();
+        }
+        self.failed_actions.push_back(failed);
+    }
+    
+    /// Matches whose alerts or webhooks failed, oldest first (the most recent `MAX_FAILED_ACTIONS`)
+    pub fn failed_actions(&self) -> &VecDeque<FailedAction> {
+        &self.failed_actions
+    }
+    
     pub fn get_all_collections(&self) -> Vec<(String, usize)> {
         self.collections
             .iter()
@@ -79,17 +327,19 @@ impl FilteredTransactionMonitor {
     pub async fn new(
         rpc_url: String,
         filter_config_path: Option<String>,
+        strict: bool,
     ) -> Result<Self> {
         let rpc_client = Arc::new(RpcClient::new(rpc_url.clone()));
         
         // Load filters
         let filters = if let Some(path) = filter_config_path {
-            FilterEngine::from_json_file(&path)?
+            FilterEngine::from_json_file(&path, strict)?
         } else {
             // Use default YUYA mint filters
             let yuya_address = std::env::var("YU_TOKEN_ADDRESS")
                 .unwrap_or_else(|_| "YUYAiJo8KVbnc6Fb6h3MnH2VGND4uGWDH4iLnw7DLEu".to_string());
             let default_filters = create_yuya_mint_filters(&yuya_address);
+            log_default_thresholds(&rpc_url, &yuya_address, &default_filters).await;
             FilterEngine::new(default_filters)
         };
         
@@ -102,7 +352,7 @@ impl FilteredTransactionMonitor {
         ) {
             (Ok(token), Ok(chat_id)) => {
                 info!("Telegram notifications enabled");
-                let notifier = TelegramNotifier::new(token, chat_id);
+                let notifier = TelegramNotifier::new(token, chat_id).with_threads_from_env();
                 
                 // Send test message
                 if let Err(e) = notifier.send_message("🚀 Solana transaction monitor started! Filters are active.").await {
@@ -140,19 +390,46 @@ impl FilteredTransactionMonitor {
         //     }
         // };
         
-        let transaction_extractor = Arc::new(TransactionExtractor::new(rpc_url));
-        let notification_manager = Arc::new(RwLock::new(NotificationManager::new()));
+        let discord_notifier = discord_notifier_from_env();
+        
+        let account_owners = filter_engine.needs_account_owners()
+            .then(|| Arc::new(AccountOwnerCache::new(rpc_url.clone())));
+        let mint_supplies = mint_supply_cache(&filter_engine, &rpc_url);
+        let slot_leaders = filter_engine.needs_slot_leaders()
+            .then(|| Arc::new(SlotLeaderCache::new(rpc_url.clone())));
+        let transaction_extractor = Arc::new(
+            TransactionExtractor::new(rpc_url)
+                .with_lean(lean_extraction_from_env())
+                .with_rewards(filter_engine.needs_rewards() || capture_rewards_from_env())
+        );
+        let notification_manager = notification_manager(&telegram_notifier, &None, &discord_notifier).await;
         let storage = Arc::new(RwLock::new(TransactionStorage::new()));
         
         Ok(Self {
             rpc_client,
-            filter_engine,
+            filter_engine: std::sync::RwLock::new(filter_engine),
             telegram_notifier,
             slack_notifier: None,
+            discord_notifier,
+            email_notifier: email_notifier_from_env()?,
+            webhook_sender: Arc::new(WebhookSender::new()),
             notification_manager,
+            alert_digest: AlertDigest::from_env()?.map(Arc::new),
             transaction_extractor,
             storage,
-            config_manager: None,
+            config_manager: std::sync::RwLock::new(None),
+            config_source: None,
+            alert_cooldowns: Arc::new(RwLock::new(HashMap::new())),
+            slot_leaders,
+            account_owners,
+            mint_supplies,
+            sinks: Vec::new(),
+            storage_backend: configured_storage_backend().await?,
+            last_processed: Arc::new(std::sync::Mutex::new(Instant::now())),
+            processed_slots: Default::default(),
+            matches_found: Default::default(),
+            highest_slot: Default::default(),
+            chain_tip: Default::default(),
         })
     }
     
@@ -160,16 +437,12 @@ impl FilteredTransactionMonitor {
     pub async fn from_config_dir(
         rpc_url: String,
         config_dir: impl AsRef<std::path::Path>,
+        strict: bool,
     ) -> Result<Self> {
         let rpc_client = Arc::new(RpcClient::new(rpc_url.clone()));
         
-        // Load configurations
-        let mut config_manager = ConfigManager::new(config_dir);
-        config_manager.load_all()?;
-        
-        // Get filters with resolved alerts
-        let filters = config_manager.get_filters_with_alerts()?;
-        let filter_engine = Arc::new(FilterEngine::new(filters));
+        let (config_manager, filters) = load_config_dir(config_dir.as_ref(), strict)?;
+        let filter_engine = Arc::new(FilterEngine::try_new(filters)?);
         let config_manager = Arc::new(config_manager);
         
         // Setup Telegram if credentials are available
@@ -179,7 +452,7 @@ impl FilteredTransactionMonitor {
         ) {
             (Ok(token), Ok(chat_id)) => {
                 info!("Telegram notifications enabled");
-                let notifier = TelegramNotifier::new(token, chat_id);
+                let notifier = TelegramNotifier::new(token, chat_id).with_threads_from_env();
                 
                 // Send test message
                 if let Err(e) = notifier.send_message("🚀 Solana transaction monitor started! Filters are active.").await {
@@ -213,38 +486,460 @@ impl FilteredTransactionMonitor {
             }
         };
         
-        let transaction_extractor = Arc::new(TransactionExtractor::new(rpc_url));
-        let notification_manager = Arc::new(RwLock::new(NotificationManager::new()));
-        let storage = Arc::new(RwLock::new(TransactionStorage::new()));
+        let discord_notifier = discord_notifier_from_env();
+        
+        let account_owners = filter_engine.needs_account_owners()
+            .then(|| Arc::new(AccountOwnerCache::new(rpc_url.clone())));
+        let mint_supplies = mint_supply_cache(&filter_engine, &rpc_url);
+        let slot_leaders = filter_engine.needs_slot_leaders()
+            .then(|| Arc::new(SlotLeaderCache::new(rpc_url.clone())));
+        let transaction_extractor = Arc::new(
+            TransactionExtractor::new(rpc_url)
+                .with_lean(lean_extraction_from_env())
+                .with_rewards(filter_engine.needs_rewards() || capture_rewards_from_env())
+        );
+        let notification_manager = notification_manager(&telegram_notifier, &slack_notifier, &discord_notifier).await;
+        let storage = Arc::new(RwLock::new(
+            TransactionStorage::new().with_retention(config_manager.retention().clone())
+        ));
         
         Ok(Self {
             rpc_client,
-            filter_engine,
+            filter_engine: std::sync::RwLock::new(filter_engine),
             telegram_notifier,
             slack_notifier,
+            discord_notifier,
+            email_notifier: email_notifier_from_env()?,
+            webhook_sender: Arc::new(WebhookSender::new()),
             notification_manager,
+            alert_digest: AlertDigest::from_env()?.map(Arc::new),
             transaction_extractor,
             storage,
-            config_manager: Some(config_manager),
+            config_manager: std::sync::RwLock::new(Some(config_manager)),
+            config_source: Some((config_dir.as_ref().to_path_buf(), strict)),
+            alert_cooldowns: Arc::new(RwLock::new(HashMap::new())),
+            slot_leaders,
+            account_owners,
+            mint_supplies,
+            sinks: Vec::new(),
+            storage_backend: configured_storage_backend().await?,
+            last_processed: Arc::new(std::sync::Mutex::new(Instant::now())),
+            processed_slots: Default::default(),
+            matches_found: Default::default(),
+            highest_slot: Default::default(),
+            chain_tip: Default::default(),
         })
     }
     
+    /// Plain informational message (e.g. "caught up, now live") to every configured chat
+    /// channel. Failures are logged, never returned.
+    pub async fn send_status_message(&self, text: &str) {
+        if let Some(telegram) = &self.telegram_notifier {
+            if let Err(e) = telegram.send_message(text).await {
+                warn!("Failed to send Telegram status message: {:#}", e);
+            }
+        }
+        if let Some(slack) = &self.slack_notifier {
+            if let Err(e) = slack.send_simple_message(text).await {
+                warn!("Failed to send Slack status message: {:#}", e);
+            }
+        }
+        if let Some(discord) = &self.discord_notifier {
+            if let Err(e) = discord.send_message(text).await {
+                warn!("Failed to send Discord status message: {:#}", e);
+            }
+        }
+    }
+    
+    /// Critical alert about the monitor itself to every configured chat channel, routed to the
+    /// Critical topic/webhook where one is configured. Failures are logged, never returned.
+    pub async fn send_critical_message(&self, title: &str, body: &str) {
+        if let Some(telegram) = &self.telegram_notifier {
+            if let Err(e) = telegram.send_custom_alert(title, body, &AlertSeverity::Critical).await {
+                warn!("Failed to send Telegram critical message: {:#}", e);
+            }
+        }
+        if let Some(slack) = &self.slack_notifier {
+            if let Err(e) = slack.send_simple_message(&format!("*{}*\n{}", title, body)).await {
+                warn!("Failed to send Slack critical message: {:#}", e);
+            }
+        }
+        if let Some(discord) = &self.discord_notifier {
+            if let Err(e) = discord.send_severity_message(&format!("**{}**\n{}", title, body), &AlertSeverity::Critical).await {
+                warn!("Failed to send Discord critical message: {:#}", e);
+            }
+        }
+        if let Some(email) = &self.email_notifier {
+            if let Err(e) = email.send_custom_alert(title, body, &AlertSeverity::Critical).await {
+                warn!("Failed to send critical email: {:#}", e);
+            }
+        }
+    }
+    
+    /// Count slots handled without fetching their blocks (e.g. a pre-filter ruled the batch
+    /// out) as processed, so the silence watchdog doesn't fire during quiet stretches
+    pub fn mark_processed(&self) {
+        *self.last_processed.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+    
+    /// Publish the chain tip the monitor loop just read, so readers of `processing_stats`
+    /// (the HTTP API) don't each have to ask the RPC for it
+    pub fn record_chain_tip(&self, slot: u64) {
+        self.chain_tip.store(slot, std::sync::atomic::Ordering::Relaxed);
+    }
+    
+    /// How many slots went through the filters since startup, and how many matches they produced
+    pub fn processing_stats(&self) -> ProcessingStats {
+        use std::sync::atomic::Ordering;
+        let highest_slot = self.highest_slot.load(Ordering::Relaxed);
+        let chain_tip = self.chain_tip.load(Ordering::Relaxed);
+        ProcessingStats {
+            last_processed_slot: (highest_slot > 0).then_some(highest_slot),
+            chain_tip: (chain_tip > 0).then_some(chain_tip),
+            slots_processed: self.processed_slots.load(Ordering::Relaxed),
+            matches_found: self.matches_found.load(Ordering::Relaxed),
+        }
+    }
+    
+    /// How long since a slot was last processed successfully (or since startup)
+    pub fn silent_for(&self) -> Duration {
+        self.last_processed.lock().unwrap_or_else(|e| e.into_inner()).elapsed()
+    }
+    
+    /// Send a Critical self-alert once no slot has been processed successfully for `threshold`
+    /// (RPC outage, stuck loop, ...), and a status message once processing resumes. Slots
+    /// processed without matches count as processed; only failures and stalls are silence.
+    pub fn spawn_silence_watchdog(self: &Arc<Self>, threshold: Duration) -> tokio::task::JoinHandle<()> {
+        let monitor = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut alerted = false;
+            let mut interval = tokio::time::interval(threshold.min(Duration::from_secs(10)));
+            loop {
+                interval.tick().await;
+                let silent_for = monitor.silent_for();
+                if silent_for >= threshold && !alerted {
+                    alerted = true;
+                    error!("No slot processed successfully for {}s", silent_for.as_secs());
+                    monitor.send_critical_message(
+                        "🔇 Monitor silent",
+                        &format!("No slot has been processed successfully for {}s", silent_for.as_secs()),
+                    ).await;
+                } else if silent_for < threshold && alerted {
+                    alerted = false;
+                    info!("Slot processing resumed");
+                    monitor.send_status_message("🔊 Monitor resumed processing slots").await;
+                }
+            }
+        })
+    }
+    
+    /// Send the alert digest every `DIGEST_INTERVAL_MINS` to Telegram and Slack; None when
+    /// digest mode is off
+    pub fn spawn_digest_scheduler(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        let digest = Arc::clone(self.alert_digest.as_ref()?);
+        let monitor = Arc::clone(self);
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(digest.interval());
+            // The first tick completes immediately, before anything was collected
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                monitor.send_alert_digest().await;
+            }
+        }))
+    }
+    
+    /// Send each channel what the alert digest collected for it since the last one, if anything
+    async fn send_alert_digest(&self) {
+        let Some(digest) = &self.alert_digest else {
+            return;
+        };
+        for (channel, summary) in digest.take_summaries() {
+            let sent = match channel.as_str() {
+                "telegram" => match &self.telegram_notifier {
+                    Some(telegram) => telegram.send_message(&summary).await,
+                    None => continue,
+                },
+                "slack" => match &self.slack_notifier {
+                    Some(slack) => slack.send_simple_message(&summary).await,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if let Err(e) = sent {
+                warn!("Failed to send {} alert digest: {:#}", channel, e);
+            }
+        }
+    }
+    
+    /// Send the alert digest and digests of alert cooldowns still holding matches back, then
+    /// deliver notifications still buffered (failed sends waiting for a retry), each giving up
+    /// after `timeout`.
+    /// Returns how many buffered notifications were delivered; what's left is logged.
+    pub async fn flush_notifications(&self, timeout: Duration) -> usize {
+        if tokio::time::timeout(timeout, self.send_alert_digest()).await.is_err() {
+            warn!("Gave up sending the alert digest after {:?}", timeout);
+        }
+        if tokio::time::timeout(timeout, self.send_cooldown_digests(true)).await.is_err() {
+            warn!("Gave up sending alert cooldown digests after {:?}", timeout);
+        }
+        
+        let notification_manager = self.notification_manager.read().await;
+        let pending = notification_manager.pending_retries().await;
+        if pending == 0 {
+            return 0;
+        }
+        
+        info!("Flushing {} pending notifications before exit", pending);
+        match tokio::time::timeout(timeout, notification_manager.retry_failed()).await {
+            Ok(delivered) => {
+                info!("Flushed {} of {} pending notifications", delivered, pending);
+                if delivered < pending {
+                    warn!("{} notifications could not be delivered and are dropped", pending - delivered);
+                }
+                delivered
+            },
+            Err(_) => {
+                warn!("Gave up flushing {} pending notifications after {:?}", pending, timeout);
+                0
+            }
+        }
+    }
+    
+    pub fn transaction_extractor(&self) -> &TransactionExtractor {
+        &self.transaction_extractor
+    }
+    
+    /// Hand every match to `sink` as well, after the matching filters' actions ran
+    pub fn with_sink(mut self, sink: Arc<dyn ResultSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+    
+    /// The filter engine currently in use
+    pub fn filter_engine(&self) -> Arc<FilterEngine> {
+        self.filter_engine.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    
+    fn config_manager(&self) -> Option<Arc<ConfigManager>> {
+        self.config_manager.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    
+    /// The config directory of `from_config_dir`; `None` for monitors built from a filter file
+    pub fn config_dir(&self) -> Option<&std::path::Path> {
+        self.config_source.as_ref().map(|(dir, _)| dir.as_path())
+    }
+    
+    /// Load the config directory again and swap in a new filter engine and config manager
+    /// (templates, routing) between slots. The new config is validated like at startup, and
+    /// filters that need a lookup the monitor didn't set up at startup (account owners, mint
+    /// supplies, slot leaders, rewards) are rejected; on any error the current config stays.
+    /// The seen-set is saved and read back, other in-memory condition state starts over. A slot
+    /// that was already being filtered when the reload started can still add to the old
+    /// engine's seen-set after the save; those additions are lost.
+    /// Returns the number of active filters.
+    pub fn reload_config(&self) -> Result<usize> {
+        let Some((dir, strict)) = &self.config_source else {
+            anyhow::bail!("Only monitors created from a config directory can reload their config");
+        };
+        let (config_manager, filters) = load_config_dir(dir, *strict)?;
+        
+        // Held until the swap so no new slot picks up the old engine after its state is saved
+        let mut current = self.filter_engine.write().unwrap_or_else(|e| e.into_inner());
+        // Saved first so the new engine reads the seen-set back
+        current.save_state().context("Failed to save filter state before reloading")?;
+        let filter_engine = Arc::new(FilterEngine::try_new(filters)?);
+        
+        let mut missing = Vec::new();
+        if filter_engine.needs_account_owners() && self.account_owners.is_none() {
+            missing.push("account owners (AccountOwnedBy)");
+        }
+        if filter_engine.needs_slot_leaders() && self.slot_leaders.is_none() {
+            missing.push("slot leaders (SlotLeader)");
+        }
+        if filter_engine.needs_rewards() && !self.transaction_extractor.captures_rewards() {
+            missing.push("block rewards (Reward)");
+        }
+        let supply_mints = self.mint_supplies.as_ref().map(|cache| cache.mints()).unwrap_or_default();
+        if filter_engine.supply_mints().iter().any(|mint| !supply_mints.contains(mint)) {
+            missing.push("supplies of new SupplyPercentage mints");
+        }
+        if !missing.is_empty() {
+            anyhow::bail!("The new config needs lookups set up at startup only, restart to apply it: {}", missing.join(", "));
+        }
+        
+        let active = filter_engine.filters().len();
+        *current = filter_engine;
+        drop(current);
+        *self.config_manager.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(config_manager));
+        info!("Reloaded config from {}: {} active filters", dir.display(), active);
+        Ok(active)
+    }
+    
+    /// Also write `Store` actions to `backend`, replacing the one chosen by `STORAGE_BACKEND`
+    pub fn with_storage_backend(mut self, backend: Arc<dyn StorageBackend>) -> Self {
+        self.storage_backend = Some(backend);
+        self
+    }
+    
+    /// The durable storage backend, if one is configured
+    pub fn storage_backend(&self) -> Option<Arc<dyn StorageBackend>> {
+        self.storage_backend.clone()
+    }
+    
+    /// Shared storage behind `Store` actions, e.g. for a `StorageSink`
+    pub fn storage(&self) -> Arc<RwLock<TransactionStorage>> {
+        self.storage.clone()
+    }
+    
+    /// Look up the slot leader for slots with matches and attach it to the matched transactions,
+    /// so alerts, stored records and templates (`${slot_leader}`) can show which validator
+    /// produced the block
+    pub fn with_leader_attribution(mut self, rpc_url: String) -> Self {
+        self.slot_leaders = Some(Arc::new(SlotLeaderCache::new(rpc_url)));
+        self
+    }
+    
+    /// Fetch blocks at an explicit commitment instead of the RPC node's default
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        let extractor = self.transaction_extractor.as_ref().clone().with_commitment(commitment);
+        self.transaction_extractor = Arc::new(extractor);
+        self
+    }
+    
+    /// Fill in `account_owners` with the owners of each transaction's writable accounts, in one
+    /// batched lookup for all of them. A no-op unless a filter uses `AccountOwnedBy`.
+    pub async fn resolve_account_owners(&self, transactions: &mut [ExtractedTransaction]) {
+        let Some(cache) = &self.account_owners else {
+            return;
+        };
+        
+        let writable: Vec<String> = transactions.iter()
+            .flat_map(|tx| tx.accounts.iter())
+            .filter(|account| account.is_writable)
+            .map(|account| account.pubkey.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let owners = cache.owners_of(&writable).await;
+        
+        for transaction in transactions.iter_mut() {
+            transaction.account_owners = transaction.accounts.iter()
+                .filter(|account| account.is_writable)
+                .filter_map(|account| owners.get(&account.pubkey).map(|owner| (account.pubkey.clone(), owner.clone())))
+                .collect();
+        }
+    }
+    
+    /// Fill in `mint_supplies` for transactions touching a `SupplyPercentage` mint, refreshing
+    /// supplies older than the TTL. A no-op unless a filter uses `SupplyPercentage`.
+    pub async fn resolve_mint_supplies(&self, transactions: &mut [ExtractedTransaction]) {
+        let Some(cache) = &self.mint_supplies else {
+            return;
+        };
+        
+        let touched = |tx: &ExtractedTransaction, mint: &str| {
+            tx.token_balance_changes.iter().any(|change| change.mint == mint)
+        };
+        let filter_engine = self.filter_engine();
+        if !transactions.iter().any(|tx| filter_engine.supply_mints().iter().any(|mint| touched(tx, mint))) {
+            return;
+        }
+        
+        let supplies = cache.supplies().await;
+        for transaction in transactions.iter_mut() {
+            let mint_supplies = supplies.iter()
+                .filter(|(mint, _)| touched(transaction, mint.as_str()))
+                .map(|(mint, supply)| (mint.clone(), *supply))
+                .collect();
+            transaction.mint_supplies = mint_supplies;
+        }
+    }
+    
     pub async fn monitor_slot(&self, slot: u64) -> Result<Vec<StoredTransaction>> {
+        Ok(self.monitor_slot_timed(slot).await?.0)
+    }
+    
+    /// `monitor_slot`, also reporting how long each stage took
+    #[tracing::instrument(name = "monitor_slot", skip(self))]
+    pub async fn monitor_slot_timed(&self, slot: u64) -> Result<(Vec<StoredTransaction>, SlotTimings)> {
         info!("Monitoring slot {} with filters", slot);
+        let mut timings = SlotTimings::default();
         
-        let transactions = self.transaction_extractor
-            .extract_from_slot(slot)
+        let started = Instant::now();
+        let block = self.transaction_extractor
+            .fetch_block(slot)
             .await
             .context("Failed to extract transactions")?;
+        timings.fetch = started.elapsed();
+        
+        let started = Instant::now();
+        let transactions = self.transaction_extractor.extract_from_block(block, slot).transactions;
+        timings.extract = started.elapsed();
         
         info!("Extracted {} transactions from slot {}", transactions.len(), slot);
+        let matches = self.process_transactions(slot, transactions, &mut timings).await?;
+        Ok((matches, timings))
+    }
+    
+    /// Same as `monitor_slot` for a block the caller already has (e.g. fetched by a pre-filter,
+    /// or a fixture), so the block itself is not fetched again. Owner, supply and slot leader
+    /// lookups still go to the RPC when a filter or leader attribution needs them.
+    pub async fn monitor_block(&self, block: UiConfirmedBlock, slot: u64) -> Result<Vec<StoredTransaction>> {
+        let extraction = self.transaction_extractor.extract_from_block(block, slot);
+        debug!("Extracted {} transactions from pre-fetched block {}", extraction.transactions.len(), slot);
+        self.process_transactions(slot, extraction.transactions, &mut SlotTimings::default()).await
+    }
+    
+    /// Run already extracted transactions of `slot` through the filters and their actions, for
+    /// ingestion backends that don't fetch whole blocks (see `geyser_source`)
+    pub async fn process_streamed_transactions(
+        &self,
+        slot: u64,
+        transactions: Vec<ExtractedTransaction>,
+    ) -> Result<Vec<StoredTransaction>> {
+        self.process_transactions(slot, transactions, &mut SlotTimings::default()).await
+    }
+    
+    async fn process_transactions(
+        &self,
+        slot: u64,
+        mut transactions: Vec<ExtractedTransaction>,
+        timings: &mut SlotTimings,
+    ) -> Result<Vec<StoredTransaction>> {
+        // One engine for the whole slot, even if a reload swaps it meanwhile
+        let filter_engine = self.filter_engine();
+        let started = Instant::now();
+        self.resolve_account_owners(&mut transactions).await;
+        self.resolve_mint_supplies(&mut transactions).await;
         
         let mut stored_transactions = Vec::new();
+        // Looked up once per slot: up front when a `SlotLeader` condition needs it, otherwise on
+        // the first match
+        let mut slot_leader: Option<Option<String>> = None;
+        if let (Some(cache), true) = (&self.slot_leaders, filter_engine.needs_slot_leaders()) {
+            let leader = cache.leader_for(slot).await;
+            for transaction in &mut transactions {
+                transaction.slot_leader = leader.clone();
+            }
+            slot_leader = Some(leader);
+        }
+        timings.lookups = started.elapsed();
         
-        for transaction in transactions {
-            let matched_filters = self.filter_engine.evaluate_transaction(&transaction);
+        for mut transaction in transactions {
+            let started = Instant::now();
+            let matched_filters = filter_engine.evaluate_transaction(&transaction);
+            timings.evaluate += started.elapsed();
+            let started = Instant::now();
             
             if !matched_filters.is_empty() {
+                if let Some(cache) = &self.slot_leaders {
+                    if slot_leader.is_none() {
+                        slot_leader = Some(cache.leader_for(slot).await);
+                    }
+                    transaction.slot_leader = slot_leader.clone().flatten();
+                }
+                
                 let original_count = matched_filters.len();
                 
                 // Deduplicate filters by category to only keep the highest priority one
@@ -257,13 +952,25 @@ impl FilteredTransactionMonitor {
                     original_count
                 );
                 
+                // Several filters may post the same payload to the same webhook; send it once
+                let mut sent_webhooks = HashSet::new();
+                
                 // Process actions for each matched filter
                 for matched_filter in &deduplicated_filters {
-                    for action in &matched_filter.actions {
+                    let routed_alerts = self.routed_alerts(matched_filter);
+                    let alerts_allowed = self.try_start_alert_cooldown(matched_filter, &transaction, &routed_alerts).await;
+                    
+                    for action in matched_filter.actions.iter().chain(&routed_alerts) {
+                        if !alerts_allowed && is_chat_alert(action) {
+                            debug!("Skipping alert for filter {} (cooldown active)", matched_filter.filter_id);
+                            continue;
+                        }
+                        
                         if let Err(e) = self.process_action(
                             action,
                             &transaction,
                             matched_filter,
+                            &mut sent_webhooks,
                         ).await {
                             error!("Failed to process action: {}", e);
                         }
@@ -278,127 +985,148 @@ impl FilteredTransactionMonitor {
                         .collect(),
                     stored_at: Utc::now(),
                     collection: "filtered".to_string(),
+                    slot_leader: transaction.slot_leader.clone(),
                 };
+                for sink in &self.sinks {
+                    if let Err(e) = sink.handle(&stored).await {
+                        error!("Result sink {} failed: {:#}", sink.name(), e);
+                    }
+                }
                 stored_transactions.push(stored);
             }
+            timings.actions += started.elapsed();
         }
         
+        self.send_cooldown_digests(false).await;
+        self.mark_processed();
+        self.processed_slots.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.matches_found.fetch_add(stored_transactions.len() as u64, std::sync::atomic::Ordering::Relaxed);
+        self.highest_slot.fetch_max(slot, std::sync::atomic::Ordering::Relaxed);
         Ok(stored_transactions)
     }
     
+    /// Alerts `config/collection_routing.json` adds for the collections a filter stores into,
+    /// minus channels the filter already alerts on. They run after the filter's own actions and
+    /// share its cooldown.
+    fn routed_alerts(&self, matched_filter: &crate::filter_engine::MatchedFilter) -> Vec<Action> {
+        let Some(config_mgr) = self.config_manager() else {
+            return Vec::new();
+        };
+        let routing = config_mgr.collection_routing();
+        if routing.is_empty() {
+            return Vec::new();
+        }
+        
+        let mut covered: HashSet<&str> = matched_filter.actions.iter()
+            .filter_map(|action| match action {
+                Action::Alert { channels, .. } => Some(channels.iter().map(String::as_str)),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        
+        let mut alerts = Vec::new();
+        for action in &matched_filter.actions {
+            let Action::Store { collection } = action else {
+                continue;
+            };
+            let Some(route) = routing.get(collection) else {
+                continue;
+            };
+            let channels: Vec<String> = route.channels.iter()
+                .filter(|channel| covered.insert(channel.as_str()))
+                .cloned()
+                .collect();
+            if !channels.is_empty() {
+                debug!("Routing {} match to {:?} via collection {}", matched_filter.filter_id, channels, collection);
+                alerts.push(Action::Alert { severity: route.severity.clone(), channels });
+            }
+        }
+        alerts
+    }
+    
     async fn process_action(
         &self,
         action: &Action,
         transaction: &ExtractedTransaction,
         matched_filter: &crate::filter_engine::MatchedFilter,
+        sent_webhooks: &mut HashSet<u64>,
     ) -> Result<()> {
         match action {
             Action::Alert { severity, channels } => {
-                for channel in channels {
-                    match channel.as_str() {
-                        "telegram" => {
-                            if let Some(telegram) = &self.telegram_notifier {
-                                // Look for telegram template if config manager is available
-                                let template = if let Some(config_mgr) = &self.config_manager {
-                                    self.find_telegram_template(config_mgr, &matched_filter.filter_id, transaction)
-                                } else {
-                                    None
-                                };
-                                
-                                if let Some((title, body)) = template {
-                                    telegram.send_custom_message(&title, &body).await?;
-                                } else {
-                                    telegram.send_alert(transaction, matched_filter, severity).await?;
-                                }
-                            }
-                        },
-                        "database" => {
-                            // Store in notification manager
-                            let mut nm = self.notification_manager.write().await;
-                            nm.add_notification(
-                                &matched_filter.filter_name,
-                                &format!("Transaction {} matched filter", transaction.signature),
-                                match severity {
-                                    AlertSeverity::Low => crate::notifications::NotificationLevel::Info,
-                                    AlertSeverity::Medium => crate::notifications::NotificationLevel::Warning,
-                                    AlertSeverity::High | AlertSeverity::Critical => {
-                                        crate::notifications::NotificationLevel::Error
-                                    },
-                                },
-                            );
-                        },
-                        "slack" => {
-                            if let Some(slack) = &self.slack_notifier {
-                                // Look for slack template if config manager is available
-                                let template = if let Some(config_mgr) = &self.config_manager {
-                                    self.find_slack_template(config_mgr, &matched_filter.filter_id, transaction)
-                                } else {
-                                    None
-                                };
-                                
-                                if let Some((title, body)) = template {
-                                    slack.send_simple_message(&format!("{}\n\n{}", title, body)).await?;
-                                } else {
-                                    // Send formatted transaction alert
-                                    let amount = transaction.token_balance_changes.first()
-                                        .map(|change| change.change);
-                                    let token = transaction.token_balance_changes.first()
-                                        .map(|change| change.mint.as_str());
-                                    
-                                    slack.send_transaction_alert(
-                                        &format!("🚨 {} - {:?}", matched_filter.filter_name, severity),
-                                        &transaction.signature,
-                                        transaction.slot,
-                                        amount,
-                                        token,
-                                        vec![
-                                            ("Filter".to_string(), matched_filter.filter_name.clone()),
-                                            ("Success".to_string(), transaction.success.to_string()),
-                                            ("Fee".to_string(), format!("{} lamports", transaction.fee)),
-                                        ],
-                                    ).await?;
-                                }
-                            }
-                        },
-                        _ => {
-                            warn!("Unknown notification channel: {}", channel);
-                        }
+                let held: Vec<String> = match &self.alert_digest {
+                    Some(digest) => channels.iter().filter(|channel| digest.holds(channel, severity)).cloned().collect(),
+                    None => Vec::new(),
+                };
+                if let Some(digest) = self.alert_digest.as_ref().filter(|_| !held.is_empty()) {
+                    digest.add(transaction, matched_filter, severity, held.clone());
+                }
+                for channel in channels.iter().filter(|channel| !held.contains(*channel)) {
+                    if let Err(e) = self.send_alert_to_channel(channel, severity, transaction, matched_filter).await {
+                        error!("Failed to send {} alert for filter {}: {:#}", channel, matched_filter.filter_id, e);
+                        self.record_failed_action(transaction, matched_filter, channel, &e).await;
+                        self.queue_alert_retry(channel, severity, transaction, matched_filter).await;
                     }
                 }
             },
             
             Action::Store { collection } => {
-                let mut storage = self.storage.write().await;
-                storage.store_transaction(
-                    transaction.clone(),
-                    collection,
-                    &matched_filter.filter_id,
-                );
+                let stored = StoredTransaction::matched(transaction.clone(), collection, &matched_filter.filter_id);
+                if let Some(backend) = &self.storage_backend {
+                    if let Err(e) = backend.store(&stored).await {
+                        error!("Failed to store transaction in {} collection {}: {:#}", backend.name(), collection, e);
+                        let channel = format!("storage:{}", backend.name());
+                        self.record_failed_action(transaction, matched_filter, &channel, &e).await;
+                    }
+                }
+                self.storage.write().await.import_record(stored, collection);
                 debug!("Stored transaction in collection: {}", collection);
             },
             
-            Action::Webhook { url, method } => {
+            Action::Webhook { url, method, headers, payload_template, secret } => {
                 if url.contains("discord.com/api/webhooks") {
                     // Handle Discord webhook
                     let discord = DiscordNotifier::new(url.clone());
                     
                     // Look for Discord template if config manager is available
-                    let template = if let Some(config_mgr) = &self.config_manager {
-                        self.find_discord_template(config_mgr, &matched_filter.filter_id, transaction)
+                    let template = if let Some(config_mgr) = self.config_manager() {
+                        self.find_discord_template(&config_mgr, &matched_filter.filter_id, transaction)
                     } else {
                         None
                     };
                     
-                    if let Err(e) = discord.send_transaction_alert(
+                    let payload = discord.transaction_payload(
                         transaction,
                         &matched_filter.filter_name,
                         template.as_ref(),
-                    ).await {
+                    );
+                    if !sent_webhooks.insert(payload.dedup_key(url)) {
+                        debug!(
+                            "Skipping duplicate webhook to {} for transaction {}",
+                            crate::filter_summary::url_host(url), transaction.signature
+                        );
+                    } else if let Err(e) = discord.send_payload(payload).await {
                         error!("Failed to send Discord notification: {}", e);
+                        let channel = format!("webhook:{}", crate::filter_summary::url_host(url));
+                        self.record_failed_action(transaction, matched_filter, &channel, &e).await;
                     }
                 } else {
-                    // Generic webhook
-                    warn!("Generic webhook not yet implemented: {} {}", method, url);
+                    let sent = match render_payload(payload_template.as_deref(), transaction, matched_filter) {
+                        Ok(body) if !sent_webhooks.insert(generic_webhook_dedup_key(url, &body)) => {
+                            debug!(
+                                "Skipping duplicate webhook to {} for transaction {}",
+                                crate::filter_summary::url_host(url), transaction.signature
+                            );
+                            Ok(())
+                        },
+                        Ok(body) => self.webhook_sender.send(url, method, headers, &body, secret.as_deref()).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = sent {
+                        error!("Failed to send webhook for filter {}: {:#}", matched_filter.filter_id, e);
+                        let channel = format!("webhook:{}", crate::filter_summary::url_host(url));
+                        self.record_failed_action(transaction, matched_filter, &channel, &e).await;
+                    }
                 }
             },
             
@@ -416,6 +1144,281 @@ impl FilteredTransactionMonitor {
         Ok(())
     }
     
+    /// Deliver one alert channel of an `Action::Alert`
+    async fn send_alert_to_channel(
+        &self,
+        channel: &str,
+        severity: &AlertSeverity,
+        transaction: &ExtractedTransaction,
+        matched_filter: &crate::filter_engine::MatchedFilter,
+    ) -> Result<()> {
+        match channel {
+            "telegram" => {
+                if let Some(telegram) = &self.telegram_notifier {
+                    // Look for telegram template if config manager is available
+                    let template = if let Some(config_mgr) = self.config_manager() {
+                        self.find_telegram_template(&config_mgr, &matched_filter.filter_id, transaction)
+                    } else {
+                        None
+                    };
+                    
+                    if let Some((title, body)) = template {
+                        telegram.send_custom_alert(&title, &body, severity).await?;
+                    } else {
+                        telegram.send_alert(transaction, matched_filter, severity).await?;
+                    }
+                }
+            },
+            "database" => {
+                // Store in notification manager
+                let mut nm = self.notification_manager.write().await;
+                nm.add_notification(
+                    &matched_filter.filter_name,
+                    &format!("Transaction {} matched filter", transaction.signature),
+                    match severity {
+                        AlertSeverity::Low => crate::notifications::NotificationLevel::Info,
+                        AlertSeverity::Medium => crate::notifications::NotificationLevel::Warning,
+                        AlertSeverity::High | AlertSeverity::Critical => {
+                            crate::notifications::NotificationLevel::Error
+                        },
+                    },
+                );
+            },
+            "slack" => {
+                if let Some(slack) = &self.slack_notifier {
+                    // Look for slack template if config manager is available
+                    let template = if let Some(config_mgr) = self.config_manager() {
+                        self.find_slack_template(&config_mgr, &matched_filter.filter_id, transaction)
+                    } else {
+                        None
+                    };
+                    
+                    if let Some((title, body)) = template {
+                        slack.send_simple_message(&format!("{}\n\n{}", title, body)).await?;
+                    } else {
+                        // Send formatted transaction alert
+                        let amount = transaction.token_balance_changes.first()
+                            .map(|change| change.change);
+                        let token = transaction.token_balance_changes.first()
+                            .map(|change| change.mint.as_str());
+                        
+                        let mut fields = vec![
+                            ("Filter".to_string(), matched_filter.filter_name.clone()),
+                            ("Success".to_string(), transaction.success.to_string()),
+                            ("Fee".to_string(), format!("{} lamports", transaction.fee)),
+                        ];
+                        if let Some(leader) = &transaction.slot_leader {
+                            fields.push(("Leader".to_string(), leader.clone()));
+                        }
+                        
+                        slack.send_transaction_alert(
+                            &format!("🚨 {} - {:?}", matched_filter.filter_name, severity),
+                            &transaction.signature,
+                            transaction.slot,
+                            amount,
+                            token,
+                            fields,
+                        ).await?;
+                    }
+                }
+            },
+            "discord" => {
+                if let Some(discord) = &self.discord_notifier {
+                    let template = if let Some(config_mgr) = self.config_manager() {
+                        self.find_discord_template(&config_mgr, &matched_filter.filter_id, transaction)
+                    } else {
+                        None
+                    };
+                    
+                    discord.send_severity_alert(
+                        transaction,
+                        &matched_filter.filter_name,
+                        template.as_ref(),
+                        Some(severity),
+                    ).await?;
+                }
+            },
+            "email" => {
+                if let Some(email) = &self.email_notifier {
+                    let template = if let Some(config_mgr) = self.config_manager() {
+                        self.format_template(&config_mgr, &matched_filter.filter_id, AlertType::Email, transaction)
+                    } else {
+                        None
+                    };
+                    
+                    if let Some((title, body)) = template {
+                        email.send_custom_alert(&title, &body, severity).await?;
+                    } else {
+                        email.send_alert(transaction, matched_filter, severity).await?;
+                    }
+                }
+            },
+            _ => {
+                warn!("Unknown notification channel: {}", channel);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Queue a failed alert send for the notification manager's retry, the last one being
+    /// `flush_notifications` on shutdown
+    async fn queue_alert_retry(
+        &self,
+        channel: &str,
+        severity: &AlertSeverity,
+        transaction: &ExtractedTransaction,
+        matched_filter: &crate::filter_engine::MatchedFilter,
+    ) {
+        let event = TransactionEvent {
+            signature: transaction.signature.clone(),
+            slot: transaction.slot,
+            success: transaction.success,
+            fee: transaction.fee,
+        };
+        let alert = create_alert_from_transaction(
+            &event,
+            format!("{:?} alert: {}", severity, matched_filter.filter_name),
+            format!("Transaction {} matched filter {}", transaction.signature, matched_filter.filter_name),
+            notification_severity(severity),
+        );
+        if self.notification_manager.read().await.queue_retry(channel, alert).await {
+            debug!("Queued {} alert for filter {} for retry", channel, matched_filter.filter_id);
+        }
+    }
+    
+    /// Keep a match whose alert or webhook could not be delivered (after the notifier's own
+    /// retries) in the `failed_actions` log, so it can be investigated or replayed
+    async fn record_failed_action(
+        &self,
+        transaction: &ExtractedTransaction,
+        matched_filter: &crate::filter_engine::MatchedFilter,
+        channel: &str,
+        error: &anyhow::Error,
+    ) {
+        let failed = FailedAction {
+            transaction: transaction.clone(),
+            filter_id: matched_filter.filter_id.clone(),
+            filter_name: matched_filter.filter_name.clone(),
+            channel: channel.to_string(),
+            error: format!("{:#}", error),
+            failed_at: Utc::now(),
+        };
+        if let Err(e) = failed.append(&failed_actions_path()) {
+            warn!("Failed to record failed action: {:#}", e);
+        }
+        self.storage.write().await.record_failed_action(failed);
+    }
+    
+    /// Returns false while the filter's alert cooldown is active, holding the match back for the
+    /// digest; otherwise starts a new cooldown window, first sending the digest of the previous one
+    async fn try_start_alert_cooldown(
+        &self,
+        matched_filter: &crate::filter_engine::MatchedFilter,
+        transaction: &ExtractedTransaction,
+        routed_alerts: &[Action],
+    ) -> bool {
+        let Some(cooldown_secs) = matched_filter.cooldown_secs else {
+            return true;
+        };
+        
+        let previous = {
+            let mut cooldowns = self.alert_cooldowns.write().await;
+            if let Some(cooldown) = cooldowns.get_mut(&matched_filter.filter_id) {
+                if cooldown.is_active() {
+                    if cooldown.suppressed.len() < MAX_DIGEST_SIGNATURES {
+                        cooldown.suppressed.push((transaction.signature.clone(), transaction.slot));
+                    }
+                    cooldown.suppressed_count += 1;
+                    return false;
+                }
+            }
+            
+            cooldowns.insert(matched_filter.filter_id.clone(), AlertCooldown {
+                started: Instant::now(),
+                duration: Duration::from_secs(cooldown_secs),
+                filter_name: matched_filter.filter_name.clone(),
+                alerts: matched_filter.actions.iter().chain(routed_alerts).filter(|a| is_chat_alert(a)).cloned().collect(),
+                suppressed: Vec::new(),
+                suppressed_count: 0,
+            })
+        };
+        
+        if let Some(previous) = previous {
+            self.send_cooldown_digest(&previous).await;
+        }
+        true
+    }
+    
+    /// Send the digests of cooldowns that have ended and forget them; with `all`, also of the
+    /// ones still running (on shutdown), so held-back matches are never silently lost
+    async fn send_cooldown_digests(&self, all: bool) {
+        // Checked under the read lock, so slots without cooldowns don't queue for the write lock
+        if self.alert_cooldowns.read().await.is_empty() {
+            return;
+        }
+        let ended: Vec<AlertCooldown> = {
+            let mut cooldowns = self.alert_cooldowns.write().await;
+            let ids: Vec<String> = cooldowns.iter()
+                .filter(|(_, cooldown)| !cooldown.is_active() || (all && cooldown.suppressed_count > 0))
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| cooldowns.remove(id)).collect()
+        };
+        
+        for cooldown in &ended {
+            self.send_cooldown_digest(cooldown).await;
+        }
+    }
+    
+    /// One message per alert channel summing up the matches a cooldown held back. Failures are
+    /// logged, never returned.
+    async fn send_cooldown_digest(&self, cooldown: &AlertCooldown) {
+        let Some((title, body)) = cooldown.digest() else {
+            return;
+        };
+        
+        for action in &cooldown.alerts {
+            let Action::Alert { severity, channels } = action else {
+                continue;
+            };
+            for channel in channels {
+                if let Err(e) = self.send_digest_to_channel(channel, severity, &title, &body).await {
+                    warn!("Failed to send {} cooldown digest for {}: {:#}", channel, cooldown.filter_name, e);
+                }
+            }
+        }
+    }
+    
+    async fn send_digest_to_channel(&self, channel: &str, severity: &AlertSeverity, title: &str, body: &str) -> Result<()> {
+        match channel {
+            "telegram" => {
+                if let Some(telegram) = &self.telegram_notifier {
+                    telegram.send_custom_alert(title, body, severity).await?;
+                }
+            },
+            "slack" => {
+                if let Some(slack) = &self.slack_notifier {
+                    slack.send_simple_message(&format!("*{}*\n{}", title, body)).await?;
+                }
+            },
+            "discord" => {
+                if let Some(discord) = &self.discord_notifier {
+                    discord.send_message(&format!("**{}**\n{}", title, body)).await?;
+                }
+            },
+            "email" => {
+                if let Some(email) = &self.email_notifier {
+                    email.send_custom_alert(title, body, severity).await?;
+                }
+            },
+            _ => {
+                warn!("Unknown notification channel {}; cooldown digest not sent: {}", channel, title);
+            }
+        }
+        Ok(())
+    }
+    
     pub async fn get_storage_summary(&self) -> HashMap<String, usize> {
         let storage = self.storage.read().await;
         storage.get_all_collections()
@@ -428,6 +1431,41 @@ impl FilteredTransactionMonitor {
         storage.get_collection(collection).cloned()
     }
     
+    /// Up to `limit` records of `collection` starting at `offset`, oldest first, and the size of
+    /// the collection; only the page is copied out of storage
+    pub async fn get_stored_page(&self, collection: &str, offset: usize, limit: usize) -> Option<(usize, Vec<StoredTransaction>)> {
+        let storage = self.storage.read().await;
+        storage.get_page(collection, offset, limit)
+            .map(|(total, page)| (total, page.to_vec()))
+    }
+    
+    pub async fn get_failed_actions(&self) -> Vec<FailedAction> {
+        let storage = self.storage.read().await;
+        storage.failed_actions().iter().cloned().collect()
+    }
+    
+    pub async fn get_matches_by_mint(&self, mint: &str) -> Vec<StoredTransaction> {
+        let storage = self.storage.read().await;
+        storage.get_matches_by_mint(mint).into_iter().cloned().collect()
+    }
+    
+    /// Load historical records into `collection` of the monitor's storage, so lookups and stats
+    /// include them without re-scanning the chain. Returns how many were stored.
+    pub async fn import_records(&self, records: Vec<StoredTransaction>, collection: &str) -> usize {
+        let mut storage = self.storage.write().await;
+        let count = records.len();
+        for record in records {
+            storage.import_record(record, collection);
+        }
+        info!("Imported {} records into collection {}", count, collection);
+        count
+    }
+    
+    pub async fn get_matches_by_signature(&self, signature: &str) -> Vec<StoredTransaction> {
+        let storage = self.storage.read().await;
+        storage.get_matches_by_signature(signature).into_iter().cloned().collect()
+    }
+    
     /// Deduplicate filters to only keep the highest threshold match for each category
     fn deduplicate_filters(&self, matched_filters: Vec<crate::filter_engine::MatchedFilter>) -> Vec<crate::filter_engine::MatchedFilter> {
         use std::collections::HashMap;
@@ -496,25 +1534,7 @@ impl FilteredTransactionMonitor {
         filter_id: &str,
         transaction: &ExtractedTransaction,
     ) -> Option<(String, String)> {
-        // Get monitor config to find alert IDs
-        if let Some(monitor) = config_mgr.loaded_monitors.get(filter_id) {
-            // Look for telegram alerts
-            for alert_id in &monitor.alerts {
-                if let Some(alert) = config_mgr.get_alert(alert_id) {
-                    if matches!(alert.trigger_type, crate::config_manager::AlertType::Telegram) {
-                        // Convert transaction to JSON for template substitution
-                        let transaction_json = serde_json::to_value(transaction).ok()?;
-                        
-                        let (title, body) = crate::config_manager::format_message(
-                            &alert.config.message,
-                            &transaction_json,
-                        );
-                        return Some((title, body));
-                    }
-                }
-            }
-        }
-        None
+        self.format_template(config_mgr, filter_id, AlertType::Telegram, transaction)
     }
     
     /// Find slack template for a filter and format with transaction data
@@ -524,25 +1544,7 @@ impl FilteredTransactionMonitor {
         filter_id: &str,
         transaction: &ExtractedTransaction,
     ) -> Option<(String, String)> {
-        // Get monitor config to find alert IDs
-        if let Some(monitor) = config_mgr.loaded_monitors.get(filter_id) {
-            // Look for slack alerts
-            for alert_id in &monitor.alerts {
-                if let Some(alert) = config_mgr.get_alert(alert_id) {
-                    if matches!(alert.trigger_type, crate::config_manager::AlertType::Slack) {
-                        // Convert transaction to JSON for template substitution
-                        let transaction_json = serde_json::to_value(transaction).ok()?;
-                        
-                        let (title, body) = crate::config_manager::format_message(
-                            &alert.config.message,
-                            &transaction_json,
-                        );
-                        return Some((title, body));
-                    }
-                }
-            }
-        }
-        None
+        self.format_template(config_mgr, filter_id, AlertType::Slack, transaction)
     }
     
     /// Find discord template for a filter and format with transaction data
@@ -552,40 +1554,330 @@ impl FilteredTransactionMonitor {
         filter_id: &str,
         transaction: &ExtractedTransaction,
     ) -> Option<crate::config_manager::MessageTemplate> {
-        // Get monitor config to find alert IDs
-        if let Some(monitor) = config_mgr.loaded_monitors.get(filter_id) {
-            // Look for discord alerts
-            for alert_id in &monitor.alerts {
-                if let Some(alert) = config_mgr.get_alert(alert_id) {
-                    if matches!(alert.trigger_type, crate::config_manager::AlertType::Discord) {
-                        // Convert transaction to JSON for template substitution
-                        let transaction_json = serde_json::to_value(transaction).ok()?;
-                        
-                        let (title, body) = crate::config_manager::format_message(
-                            &alert.config.message,
-                            &transaction_json,
-                        );
-                        
-                        return Some(crate::config_manager::MessageTemplate {
-                            title,
-                            body,
-                        });
-                    }
-                }
+        self.format_template(config_mgr, filter_id, AlertType::Discord, transaction)
+            .map(|(title, body)| crate::config_manager::MessageTemplate { title, body })
+    }
+    
+    /// Resolve the filter's template for `channel` (templates.json first, then its alerts) and
+    /// substitute the transaction's fields
+    fn format_template(
+        &self,
+        config_mgr: &ConfigManager,
+        filter_id: &str,
+        channel: AlertType,
+        transaction: &ExtractedTransaction,
+    ) -> Option<(String, String)> {
+        let template = config_mgr.find_template(filter_id, channel)?;
+        
+        // Convert transaction to JSON for template substitution
+        let transaction_json = serde_json::to_value(transaction).ok()?;
+        Some(crate::config_manager::format_message(template, &transaction_json))
+    }
+}
+
+/// Identical bodies to the same URL within one transaction are sent once
+fn generic_webhook_dedup_key(url: &str, body: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Actions that notify a chat channel (alerts and Discord webhooks), as opposed to storage or generic webhooks
+fn is_chat_alert(action: &Action) -> bool {
+    match action {
+        Action::Alert { .. } => true,
+        Action::Webhook { url, .. } => url.contains("discord.com/api/webhooks"),
+        _ => false,
+    }
+}
+
+/// Add (or remove) a record's mints and signature in the lookup indexes
+fn index_record(
+    by_mint: &mut HashMap<String, BTreeSet<RecordKey>>,
+    by_signature: &mut HashMap<String, BTreeSet<RecordKey>>,
+    stored: &StoredTransaction,
+    key: &RecordKey,
+    insert: bool,
+) {
+    let update = |index: &mut HashMap<String, BTreeSet<RecordKey>>, value: &str| {
+        if insert {
+            index.entry(value.to_string()).or_default().insert(key.clone());
+        } else if let Some(keys) = index.get_mut(value) {
+            keys.remove(key);
+            if keys.is_empty() {
+                index.remove(value);
             }
         }
-        None
+    };
+    
+    let mints: HashSet<&str> = stored.transaction.token_balance_changes.iter()
+        .map(|change| change.mint.as_str())
+        .collect();
+    for mint in mints {
+        update(by_mint, mint);
+    }
+    update(by_signature, &stored.transaction.signature);
+}
+
+/// Drops the records the policy no longer allows from the front (oldest first) and returns them
+fn apply_retention(entries: &mut Vec<StoredTransaction>, policy: &RetentionPolicy, now: DateTime<Utc>) -> Vec<StoredTransaction> {
+    let mut expired = 0;
+    let max_age = policy.max_age_secs
+        .and_then(|secs| chrono::Duration::try_seconds(i64::try_from(secs).ok()?));
+    if let Some(max_age) = max_age {
+        let cutoff = now - max_age;
+        expired = entries.partition_point(|stored| stored.stored_at < cutoff);
+    }
+    if let Some(max_entries) = policy.max_entries {
+        expired = expired.max(entries.len().saturating_sub(max_entries));
+    }
+    entries.drain(..expired).collect()
+}
+
+/// Amounts in the default filters are whole tokens (UI units), which hold for any decimals. Log
+/// what they amount to for the configured token, and warn about thresholds above its supply.
+async fn log_default_thresholds(rpc_url: &str, mint: &str, filters: &[FilterConfig]) {
+    let Ok(pubkey) = Pubkey::from_str(mint) else {
+        warn!("YU_TOKEN_ADDRESS {} is not a valid address", mint);
+        return;
+    };
+    let supply = match RpcClientWithFailover::new(rpc_url.to_string()).get_token_supply(&pubkey).await {
+        Ok(supply) => supply,
+        Err(e) => {
+            warn!("Could not look up decimals of {}: {:#} (default thresholds are whole tokens)", mint, e);
+            return;
+        }
+    };
+    
+    info!("Default filters watch {} ({} decimals, supply {:.0})", mint, supply.decimals, supply.ui_amount);
+    for filter in filters {
+        for condition in filter.conditions.iter() {
+            let Condition::TokenMint { amount, .. } = condition else { continue };
+            let base_units = amount * 10f64.powi(supply.decimals as i32);
+            let share = if supply.ui_amount > 0.0 { amount / supply.ui_amount * 100.0 } else { f64::INFINITY };
+            info!("  {}: mint >= {} tokens ({:.0} base units, {:.2}% of supply)", filter.id, amount, base_units, share);
+            if *amount > supply.ui_amount {
+                warn!("Filter {} needs a mint of {} tokens, more than the current supply of {:.0}", filter.id, amount, supply.ui_amount);
+            }
+        }
+    }
+}
+
+/// Load and validate a config directory: monitors with their alerts resolved
+fn load_config_dir(dir: &std::path::Path, strict: bool) -> Result<(ConfigManager, Vec<FilterConfig>)> {
+    let mut config_manager = ConfigManager::new(dir).with_strict(strict);
+    config_manager.load_all()?;
+    
+    // Get filters with resolved alerts
+    let filters = config_manager.get_filters_with_alerts()?;
+    Ok((config_manager, filters))
+}
+
+fn mint_supply_cache(filter_engine: &FilterEngine, rpc_url: &str) -> Option<Arc<MintSupplyCache>> {
+    let mints = filter_engine.supply_mints();
+    (!mints.is_empty()).then(|| {
+        Arc::new(MintSupplyCache::new(rpc_url.to_string(), mints.to_vec(), MintSupplyCache::ttl_from_env()))
+    })
+}
+
+/// `CAPTURE_REWARDS=true` fetches block rewards even when no filter uses `Reward`, so stored
+/// and exported transactions carry them
+fn capture_rewards_from_env() -> bool {
+    std::env::var("CAPTURE_REWARDS")
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// `LEAN_EXTRACTION=true` drops raw pre/post token balances from extracted transactions
+fn lean_extraction_from_env() -> bool {
+    std::env::var("LEAN_EXTRACTION")
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Notification manager with the configured notifiers registered as channels, so alert sends
+/// that failed are retried through them
+async fn notification_manager(
+    telegram: &Option<Arc<TelegramNotifier>>,
+    slack: &Option<Arc<SlackNotifier>>,
+    discord: &Option<Arc<DiscordNotifier>>,
+) -> Arc<RwLock<NotificationManager>> {
+    let manager = NotificationManager::new();
+    if let Some(telegram) = telegram {
+        manager.add_channel(Box::new(NotifierChannel::Telegram(Arc::clone(telegram)))).await;
+    }
+    if let Some(slack) = slack {
+        manager.add_channel(Box::new(NotifierChannel::Slack(Arc::clone(slack)))).await;
+    }
+    if let Some(discord) = discord {
+        manager.add_channel(Box::new(NotifierChannel::Discord(Arc::clone(discord)))).await;
+    }
+    Arc::new(RwLock::new(manager))
+}
+
+/// A configured notifier as a `NotificationManager` channel, named like the alert channel
+enum NotifierChannel {
+    Telegram(Arc<TelegramNotifier>),
+    Slack(Arc<SlackNotifier>),
+    Discord(Arc<DiscordNotifier>),
+}
+
+#[async_trait::async_trait]
+impl NotificationChannel for NotifierChannel {
+    async fn send(&self, alert: &Alert) -> Result<()> {
+        let text = format!("{}\n\n{}", alert.title, alert.message);
+        match self {
+            Self::Telegram(telegram) => telegram.send_message(&text).await,
+            Self::Slack(slack) => slack.send_simple_message(&text).await,
+            Self::Discord(discord) => discord.send_message(&text).await,
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Telegram(_) => "telegram",
+            Self::Slack(_) => "slack",
+            Self::Discord(_) => "discord",
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+fn notification_severity(severity: &AlertSeverity) -> crate::notifications::AlertSeverity {
+    match severity {
+        AlertSeverity::Low => crate::notifications::AlertSeverity::Low,
+        AlertSeverity::Medium => crate::notifications::AlertSeverity::Medium,
+        AlertSeverity::High => crate::notifications::AlertSeverity::High,
+        AlertSeverity::Critical => crate::notifications::AlertSeverity::Critical,
+    }
+}
+
+/// Email notifier for the `email` alert channel, configured from the environment
+fn email_notifier_from_env() -> Result<Option<Arc<EmailNotifier>>> {
+    match EmailNotifier::from_env().context("Invalid email configuration")? {
+        Some(notifier) => {
+            info!("Email notifications enabled");
+            Ok(Some(Arc::new(notifier)))
+        },
+        None => {
+            info!("Email alert channel disabled (SMTP_HOST not set)");
+            Ok(None)
+        }
+    }
+}
+
+/// Discord notifier for the `discord` alert channel, configured from the environment
+fn discord_notifier_from_env() -> Option<Arc<DiscordNotifier>> {
+    match DiscordNotifier::from_env() {
+        Some(notifier) => {
+            info!("Discord notifications enabled");
+            Some(Arc::new(notifier))
+        },
+        None => {
+            info!("Discord alert channel disabled (DISCORD_WEBHOOK_URL not set)");
+            None
+        }
     }
 }
 
 // Helper to save filter configuration
-pub fn save_filter_config(filters: &[FilterConfig], path: &str) -> Result<()> {
-    let json = serde_json::to_string_pretty(filters)?;
+pub fn save_filter_config(filters: &[FilterConfig], path: &str, pretty: bool) -> Result<()> {
+    let json = to_export_json(filters, pretty)?;
     std::fs::write(path, json)?;
     info!("Saved {} filters to {}", filters.len(), path);
     Ok(())
 }
 
+/// Write each storage collection to `<dir>/<collection>.jsonl` (`.jsonl.gz` with `gzip`), one
+/// stored transaction per line in slot order. Characters outside `[A-Za-z0-9_.-]` in collection
+/// names become `_`. Returns the number of files written.
+pub async fn export_collections(monitor: &FilteredTransactionMonitor, dir: &std::path::Path, gzip: bool) -> Result<usize> {
+    use std::io::Write;
+    
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create export directory {}", dir.display()))?;
+    
+    let storage = monitor.storage.read().await;
+    let mut written = 0;
+    for (collection, _) in storage.get_all_collections() {
+        let Some(transactions) = storage.get_collection(&collection) else {
+            continue;
+        };
+        let mut transactions: Vec<&StoredTransaction> = transactions.iter().collect();
+        transactions.sort_by_key(|stored| stored.transaction.slot);
+        
+        let file_name: String = collection.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') { c } else { '_' })
+            .collect();
+        let path = dir.join(format!("{}.jsonl{}", file_name, if gzip { ".gz" } else { "" }));
+        
+        let mut file = ExportWriter::create(&path)?;
+        for stored in &transactions {
+            serde_json::to_writer(&mut file, stored)?;
+            file.write_all(b"\n")?;
+        }
+        file.finish().with_context(|| format!("Failed to write {}", path.display()))?;
+        
+        info!("Exported {} transactions from collection {} to {}", transactions.len(), collection, path.display());
+        written += 1;
+    }
+    
+    Ok(written)
+}
+
+/// Records read by `read_match_records`, plus the lines that could not be parsed
+#[derive(Debug, Default)]
+pub struct ImportedRecords {
+    pub records: Vec<StoredTransaction>,
+    /// Line number (1-based) and error of every rejected line
+    pub rejected: Vec<(usize, String)>,
+}
+
+/// Read a JSON-lines file of `StoredTransaction` records (as written by `export_collections`)
+/// or bare `ExtractedTransaction`s, e.g. from older analyses. `.gz` files are decompressed.
+/// Bare transactions get no matched filters and the current time as `stored_at`. Blank lines
+/// are skipped; lines that are neither format are returned in `rejected`, not as an error.
+pub fn read_match_records(path: &std::path::Path) -> Result<ImportedRecords> {
+    use std::io::BufRead;
+    
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader: Box<dyn BufRead> = if path.extension().is_some_and(|ext| ext == "gz") {
+        Box::new(std::io::BufReader::new(flate2::read::GzDecoder::new(file)))
+    } else {
+        Box::new(std::io::BufReader::new(file))
+    };
+    
+    let mut imported = ImportedRecords::default();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(stored) = serde_json::from_str::<StoredTransaction>(&line) {
+            imported.records.push(stored);
+            continue;
+        }
+        match serde_json::from_str::<ExtractedTransaction>(&line) {
+            Ok(transaction) => imported.records.push(StoredTransaction {
+                slot_leader: transaction.slot_leader.clone(),
+                transaction,
+                matched_filters: Vec::new(),
+                stored_at: Utc::now(),
+                collection: String::new(),
+            }),
+            Err(e) => imported.rejected.push((index + 1, e.to_string())),
+        }
+    }
+    Ok(imported)
+}
+
 // Create example filter configuration
 pub fn create_example_filter_config() -> Vec<FilterConfig> {
     let yuya_address = std::env::var("YU_TOKEN_ADDRESS")
@@ -602,12 +1894,12 @@ pub fn create_example_filter_config() -> Vec<FilterConfig> {
             all_of: Some(vec![
                 crate::filter_engine::Condition::ProgramInvoked {
                     program_id: "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".to_string(), // Raydium CLMM
-                },
+                }.into(),
                 crate::filter_engine::Condition::TokenTransfer {
                     mint: Some(yuya_address.clone()),
                     operator: crate::filter_engine::ComparisonOperator::GreaterThan,
                     amount: 100_000.0, // 100k YUYA
-                },
+                }.into(),
             ]),
             any_of: None,
             none_of: None,
@@ -621,7 +1913,236 @@ pub fn create_example_filter_config() -> Vec<FilterConfig> {
                 collection: "large_yuya_swaps".to_string(),
             },
         ],
+        cooldown_secs: None,
+        priority: 0,
     });
     
     filters
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_engine::tests::{test_transaction, token_change};
+    
+    fn transaction(signature: &str, mint: &str) -> ExtractedTransaction {
+        let mut transaction = test_transaction();
+        transaction.signature = signature.to_string();
+        transaction.token_balance_changes = vec![token_change("holder", mint, 1.0)];
+        transaction
+    }
+    
+    #[test]
+    fn test_lookup_indexes_follow_retention() {
+        let retention = HashMap::from([(
+            "mints".to_string(),
+            RetentionPolicy { max_entries: Some(2), max_age_secs: None },
+        )]);
+        let mut storage = TransactionStorage::new().with_retention(retention);
+        storage.store_transaction(transaction("a", "YU"), "mints", "f");
+        storage.store_transaction(transaction("b", "USDC"), "mints", "f");
+        storage.store_transaction(transaction("a", "YU"), "all", "f");
+        
+        let signatures = |records: Vec<&StoredTransaction>| -> Vec<String> {
+            records.iter().map(|r| format!("{}@{}", r.transaction.signature, r.collection)).collect()
+        };
+        assert_eq!(signatures(storage.get_matches_by_mint("YU")), vec!["a@all", "a@mints"]);
+        assert_eq!(storage.get_matches_by_signature("a").len(), 2);
+        
+        // Evicts "a" from "mints"; the copy in "all" stays findable
+        storage.store_transaction(transaction("c", "YU"), "mints", "f");
+        assert_eq!(signatures(storage.get_matches_by_mint("YU")), vec!["a@all", "c@mints"]);
+        assert_eq!(signatures(storage.get_matches_by_signature("b")), vec!["b@mints"]);
+        assert!(storage.get_matches_by_mint("DOGE").is_empty());
+    }
+    
+    #[test]
+    fn test_import_reads_stored_and_bare_records() {
+        let stored = StoredTransaction {
+            transaction: transaction("a", "YU"),
+            matched_filters: vec!["big_mint".to_string()],
+            stored_at: Utc::now(),
+            collection: "critical_mints".to_string(),
+            slot_leader: None,
+        };
+        let path = std::env::temp_dir().join(format!("import_{}.jsonl", uuid::Uuid::new_v4()));
+        let lines = [
+            serde_json::to_string(&stored).unwrap(),
+            String::new(),
+            serde_json::to_string(&transaction("b", "USDC")).unwrap(),
+            "{\"not\": \"a record\"}".to_string(),
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        
+        let imported = read_match_records(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(imported.records.len(), 2);
+        assert_eq!(imported.rejected.len(), 1);
+        assert_eq!(imported.rejected[0].0, 4);
+        
+        let mut storage = TransactionStorage::new();
+        for record in imported.records {
+            storage.import_record(record, "history");
+        }
+        let found = storage.get_matches_by_mint("YU");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].collection, "history");
+        assert_eq!(found[0].matched_filters, vec!["big_mint".to_string()]);
+        assert_eq!(storage.get_matches_by_signature("b").len(), 1);
+    }
+    
+    #[test]
+    fn test_page_stays_within_collection() {
+        let mut storage = TransactionStorage::new();
+        for signature in ["a", "b", "c"] {
+            storage.store_transaction(transaction(signature, "YU"), "mints", "f");
+        }
+        let page = |offset, limit| storage.get_page("mints", offset, limit)
+            .map(|(total, page)| (total, page.iter().map(|r| r.transaction.signature.as_str()).collect::<Vec<_>>()));
+        
+        assert_eq!(page(0, 2), Some((3, vec!["a", "b"])));
+        assert_eq!(page(2, 2), Some((3, vec!["c"])));
+        assert_eq!(page(10, 2), Some((3, vec![])));
+        assert!(storage.get_page("unknown", 0, 2).is_none());
+    }
+    
+    #[test]
+    fn test_cooldown_digest_lists_held_back_matches() {
+        let mut cooldown = AlertCooldown {
+            started: Instant::now(),
+            duration: Duration::from_secs(600),
+            filter_name: "Big mint".to_string(),
+            alerts: vec![],
+            suppressed: vec![],
+            suppressed_count: 0,
+        };
+        assert!(cooldown.is_active());
+        assert!(cooldown.digest().is_none(), "nothing held back, nothing to send");
+        
+        cooldown.suppressed = (0..MAX_DIGEST_SIGNATURES as u64).map(|slot| (format!("sig{}", slot), slot)).collect();
+        cooldown.suppressed_count = MAX_DIGEST_SIGNATURES + 3;
+        let (title, body) = cooldown.digest().unwrap();
+        assert_eq!(title, "🔁 Big mint: 13 more match(es)");
+        assert!(body.contains("during the 600s alert cooldown"));
+        assert!(body.contains("• sig9 (slot 9)"));
+        assert!(body.ends_with("… and 3 more\n"));
+    }
+    
+    /// Records what it delivers, standing in for Telegram once it is reachable again
+    struct RecordingChannel {
+        sent: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+    
+    #[async_trait::async_trait]
+    impl NotificationChannel for RecordingChannel {
+        async fn send(&self, alert: &Alert) -> Result<()> {
+            self.sent.lock().unwrap().push(alert.title.clone());
+            Ok(())
+        }
+        
+        fn name(&self) -> &str {
+            "telegram"
+        }
+        
+        fn is_enabled(&self) -> bool {
+            true
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_flush_delivers_failed_alert_sends() {
+        let dir = std::env::temp_dir().join(format!("monitor_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("monitors")).unwrap();
+        let monitor = FilteredTransactionMonitor::from_config_dir("http://127.0.0.1:8899".to_string(), &dir, false)
+            .await
+            .unwrap();
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        monitor.notification_manager.read().await
+            .add_channel(Box::new(RecordingChannel { sent: Arc::clone(&sent) }))
+            .await;
+        
+        let matched = crate::filter_engine::MatchedFilter {
+            filter_id: "big_mint".to_string(),
+            filter_name: "Big mint".to_string(),
+            actions: vec![],
+            cooldown_secs: None,
+            context: Default::default(),
+        };
+        // What `process_action` does after the Telegram send failed
+        monitor.queue_alert_retry("telegram", &AlertSeverity::High, &transaction("a", "YU"), &matched).await;
+        monitor.queue_alert_retry("pagerduty", &AlertSeverity::High, &transaction("b", "YU"), &matched).await;
+        assert_eq!(monitor.notification_manager.read().await.pending_retries().await, 1);
+        
+        assert_eq!(monitor.flush_notifications(Duration::from_secs(5)).await, 1);
+        assert_eq!(*sent.lock().unwrap(), vec!["High alert: Big mint".to_string()]);
+        assert_eq!(monitor.notification_manager.read().await.pending_retries().await, 0);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_reload_config_keeps_engine_when_new_lookups_are_needed() {
+        let dir = std::env::temp_dir().join(format!("monitor_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("monitors")).unwrap();
+        let filter = |id: &str, condition: serde_json::Value| serde_json::json!({
+            "id": id, "name": id, "enabled": true,
+            "conditions": { "all_of": [condition] },
+            "actions": [],
+        });
+        let burn = serde_json::json!({ "type": "TokenBurn", "mint": "YU", "operator": "GreaterThan", "amount": 1.0 });
+        let write = |filters: Vec<serde_json::Value>| {
+            std::fs::write(dir.join("monitors/filters.json"), serde_json::Value::Array(filters).to_string()).unwrap();
+        };
+        let filter_ids = |monitor: &FilteredTransactionMonitor| -> BTreeSet<String> {
+            monitor.filter_engine().filters().iter().map(|f| f.id.clone()).collect()
+        };
+        
+        write(vec![filter("burns", burn.clone())]);
+        let monitor = FilteredTransactionMonitor::from_config_dir("http://127.0.0.1:8899".to_string(), &dir, false)
+            .await
+            .unwrap();
+        
+        write(vec![filter("burns", burn.clone()), filter("big_burns", burn.clone())]);
+        assert_eq!(monitor.reload_config().unwrap(), 2);
+        assert_eq!(filter_ids(&monitor), BTreeSet::from(["burns".to_string(), "big_burns".to_string()]));
+        
+        // The leader cache is only set up at startup
+        write(vec![filter("leaders", serde_json::json!({ "type": "SlotLeader", "leaders": ["validator"] }))]);
+        let error = monitor.reload_config().unwrap_err();
+        assert!(format!("{:#}", error).contains("slot leaders (SlotLeader)"), "{:#}", error);
+        assert_eq!(filter_ids(&monitor), BTreeSet::from(["burns".to_string(), "big_burns".to_string()]));
+        std::fs::remove_dir_all(dir).unwrap();
+    }    
+    #[tokio::test]
+    async fn test_reload_config_accepts_lookups_set_up_at_startup() {
+        let dir = std::env::temp_dir().join(format!("monitor_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("monitors")).unwrap();
+        let write = |filters: serde_json::Value| {
+            std::fs::write(dir.join("monitors/filters.json"), filters.to_string()).unwrap();
+        };
+        let config_a = serde_json::json!([
+            { "id": "leaders", "name": "Leaders", "enabled": true, "actions": [],
+              "conditions": { "all_of": [{ "type": "SlotLeader", "leaders": ["validator"] }] } },
+            { "id": "supply", "name": "Supply", "enabled": true, "actions": [],
+              "conditions": { "all_of": [{
+                  "type": "SupplyPercentage", "mint": "YU", "operator": "GreaterThanOrEqual", "percent": 5.0
+              }] } }
+        ]);
+        let config_b = serde_json::json!([
+            { "id": "burns", "name": "Burns", "enabled": true, "actions": [],
+              "conditions": { "all_of": [{ "type": "TokenBurn", "mint": "YU", "operator": "GreaterThan", "amount": 1.0 }] } }
+        ]);
+        
+        write(config_a.clone());
+        let monitor = FilteredTransactionMonitor::from_config_dir("http://127.0.0.1:8899".to_string(), &dir, false)
+            .await
+            .unwrap();
+        
+        write(config_b);
+        assert_eq!(monitor.reload_config().unwrap(), 1);
+        // The leader and supply caches outlive the config that needed them
+        write(config_a);
+        assert_eq!(monitor.reload_config().unwrap(), 2);
+        assert!(monitor.filter_engine().needs_slot_leaders());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
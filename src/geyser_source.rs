@@ -0,0 +1,242 @@
+use anyhow::{anyhow, bail, Context, Result};
+use futures::{sink::SinkExt, stream::StreamExt};
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::time::Duration;
+use tonic::transport::ClientTlsConfig;
+use tracing::{info, warn};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::convert_from::create_tx_with_meta;
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof,
+    CommitmentLevel,
+    SubscribeRequest,
+    SubscribeRequestFilterTransactions,
+    SubscribeRequestPing,
+    SubscribeUpdateTransaction,
+};
+use crate::filtered_monitor::FilteredTransactionMonitor;
+use crate::transaction_extractor::ExtractedTransaction;
+
+/// Where live monitoring gets its transactions from (`INGESTION_BACKEND`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IngestionBackend {
+    /// Fetch every block with `getBlock` (default)
+    #[default]
+    Rpc,
+    /// Consume a Yellowstone gRPC (Geyser) transaction stream
+    Geyser,
+}
+
+impl IngestionBackend {
+    pub fn from_env() -> Result<Self> {
+        match env::var("INGESTION_BACKEND").ok().filter(|v| !v.trim().is_empty()) {
+            None => Ok(Self::Rpc),
+            Some(value) => match value.trim().to_ascii_lowercase().as_str() {
+                "rpc" => Ok(Self::Rpc),
+                "geyser" | "yellowstone" => Ok(Self::Geyser),
+                other => bail!("Invalid INGESTION_BACKEND '{}': expected rpc or geyser", other),
+            },
+        }
+    }
+}
+
+/// Connection and subscription settings for the Geyser backend
+#[derive(Debug, Clone)]
+pub struct GeyserConfig {
+    pub endpoint: String,
+    pub x_token: Option<String>,
+    /// Only stream transactions touching one of these accounts; empty streams every non-vote transaction
+    pub accounts: Vec<String>,
+    pub commitment: CommitmentLevel,
+}
+
+impl GeyserConfig {
+    /// `YELLOWSTONE_GRPC_URL` (required), `YELLOWSTONE_AUTH_TOKEN`, `YELLOWSTONE_ACCOUNTS` (comma-separated) and
+    /// `YELLOWSTONE_COMMITMENT` (processed, confirmed or finalized; default confirmed)
+    pub fn from_env() -> Result<Self> {
+        let endpoint = env::var("YELLOWSTONE_GRPC_URL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .context("YELLOWSTONE_GRPC_URL must be set for INGESTION_BACKEND=geyser")?;
+        let x_token = env::var("YELLOWSTONE_AUTH_TOKEN").ok().filter(|v| !v.trim().is_empty());
+        let accounts = env::var("YELLOWSTONE_ACCOUNTS")
+            .map(|v| parse_accounts(&v))
+            .unwrap_or_default();
+        let commitment = match env::var("YELLOWSTONE_COMMITMENT").ok().filter(|v| !v.trim().is_empty()) {
+            None => CommitmentLevel::Confirmed,
+            Some(value) => parse_commitment(&value)?,
+        };
+
+        Ok(Self { endpoint: endpoint.trim().to_string(), x_token, accounts, commitment })
+    }
+
+    fn subscribe_request(&self) -> SubscribeRequest {
+        let mut transactions = HashMap::new();
+        transactions.insert("monitor".to_string(), SubscribeRequestFilterTransactions {
+            vote: Some(false),
+            failed: None,
+            signature: None,
+            account_include: self.accounts.clone(),
+            account_exclude: vec![],
+            account_required: vec![],
+        });
+
+        SubscribeRequest {
+            transactions,
+            commitment: Some(self.commitment as i32),
+            ..Default::default()
+        }
+    }
+}
+
+fn parse_accounts(value: &str) -> Vec<String> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|account| !account.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_commitment(value: &str) -> Result<CommitmentLevel> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "processed" => Ok(CommitmentLevel::Processed),
+        "confirmed" => Ok(CommitmentLevel::Confirmed),
+        "finalized" => Ok(CommitmentLevel::Finalized),
+        other => bail!("Invalid YELLOWSTONE_COMMITMENT '{}': expected processed, confirmed or finalized", other),
+    }
+}
+
+/// Totals of a `run_geyser_stream` call
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeyserStreamStats {
+    pub slots: u64,
+    pub transactions: u64,
+    pub matches: u64,
+    /// Highest slot whose transactions all went through the filters
+    pub last_slot: Option<u64>,
+}
+
+/// Stream transactions from Geyser into the monitor's filter pipeline until `should_stop`
+/// returns true. Transactions are buffered per slot and a slot is processed as one batch once
+/// a later slot shows up, so slot-scoped conditions and sinks see the same batches as with
+/// `getBlock`. Lost connections are retried with a 1s→30s backoff.
+pub async fn run_geyser_stream(
+    monitor: &FilteredTransactionMonitor,
+    config: &GeyserConfig,
+    should_stop: impl Fn() -> bool,
+) -> Result<GeyserStreamStats> {
+    let mut stats = GeyserStreamStats::default();
+    let mut pending: BTreeMap<u64, Vec<ExtractedTransaction>> = BTreeMap::new();
+    let mut backoff = Duration::from_secs(1);
+
+    while !should_stop() {
+        match follow_transactions(monitor, config, &should_stop, &mut pending, &mut stats).await {
+            Ok(()) => break,
+            Err(e) => {
+                warn!("Geyser stream from {} failed: {:#}; reconnecting in {:?}", config.endpoint, e, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(Duration::from_secs(30));
+            }
+        }
+    }
+
+    // The newest slot may still be streaming when we stop; it is dropped rather than processed
+    // partially, so `last_slot` (and the checkpoint built from it) only covers complete slots
+    Ok(stats)
+}
+
+async fn follow_transactions(
+    monitor: &FilteredTransactionMonitor,
+    config: &GeyserConfig,
+    should_stop: &impl Fn() -> bool,
+    pending: &mut BTreeMap<u64, Vec<ExtractedTransaction>>,
+    stats: &mut GeyserStreamStats,
+) -> Result<()> {
+    let mut client = GeyserGrpcClient::build_from_shared(config.endpoint.clone())?
+        .x_token(config.x_token.clone())?
+        .tls_config(ClientTlsConfig::new().with_native_roots())?
+        .connect()
+        .await
+        .context("Failed to connect to Geyser endpoint")?;
+    let (mut subscribe_tx, mut stream) = client.subscribe().await?;
+    subscribe_tx.send(config.subscribe_request()).await?;
+    info!("Geyser transaction stream connected to {}", config.endpoint);
+
+    while !should_stop() {
+        // Wake up regularly so a deadline or Ctrl-C is noticed on a quiet stream
+        let Ok(update) = tokio::time::timeout(Duration::from_secs(1), stream.next()).await else {
+            continue;
+        };
+        let update = update.ok_or_else(|| anyhow!("stream closed by server"))??;
+
+        match update.update_oneof {
+            Some(UpdateOneof::Transaction(update)) => {
+                let slot = update.slot;
+                match extract_update(monitor, update) {
+                    Ok(tx) => pending.entry(slot).or_default().push(tx),
+                    Err(e) => warn!("Skipping streamed transaction in slot {}: {:#}", slot, e),
+                }
+                // Everything before the newest slot is complete
+                while pending.len() > 1 {
+                    let (slot, transactions) = pending.pop_first().expect("pending is not empty");
+                    process_batch(monitor, slot, transactions, stats).await;
+                }
+            },
+            Some(UpdateOneof::Ping(_)) => {
+                subscribe_tx.send(SubscribeRequest {
+                    ping: Some(SubscribeRequestPing { id: 1 }),
+                    ..Default::default()
+                }).await?;
+            },
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+fn extract_update(monitor: &FilteredTransactionMonitor, update: SubscribeUpdateTransaction) -> Result<ExtractedTransaction> {
+    let info = update.transaction.context("update without transaction")?;
+    let tx_with_meta = create_tx_with_meta(info).map_err(|e| anyhow!("invalid transaction: {}", e))?;
+    let encoded = tx_with_meta.encode(UiTransactionEncoding::JsonParsed, Some(0), true)
+        .map_err(|e| anyhow!("failed to encode transaction: {}", e))?;
+    monitor.transaction_extractor().extract_encoded(encoded, update.slot)
+}
+
+async fn process_batch(
+    monitor: &FilteredTransactionMonitor,
+    slot: u64,
+    transactions: Vec<ExtractedTransaction>,
+    stats: &mut GeyserStreamStats,
+) {
+    stats.slots += 1;
+    stats.transactions += transactions.len() as u64;
+    match monitor.process_streamed_transactions(slot, transactions).await {
+        Ok(matched) => stats.matches += matched.len() as u64,
+        Err(e) => warn!("Failed to process streamed slot {}: {:#}", slot, e),
+    }
+    stats.last_slot = Some(stats.last_slot.map_or(slot, |last| last.max(slot)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_request_excludes_votes_and_filters_accounts() {
+        let config = GeyserConfig {
+            endpoint: "http://localhost:10000".to_string(),
+            x_token: None,
+            accounts: parse_accounts(" AccountA, ,AccountB "),
+            commitment: parse_commitment("Finalized").unwrap(),
+        };
+
+        let request = config.subscribe_request();
+        let filter = &request.transactions["monitor"];
+        assert_eq!(filter.vote, Some(false));
+        assert_eq!(filter.account_include, vec!["AccountA", "AccountB"]);
+        assert_eq!(request.commitment, Some(CommitmentLevel::Finalized as i32));
+        assert!(parse_commitment("recent").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+use crate::api_auth::{require_token, ApiAuth};
+use crate::filter_summary::describe_filter;
+use crate::filtered_monitor::{FilteredTransactionMonitor, StoredTransaction};
+
+/// Page size when a request doesn't ask for one
+const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest page a single request may ask for
+const MAX_PAGE_SIZE: usize = 500;
+
+/// Settings of the read-only HTTP API for dashboards
+#[derive(Debug, Clone)]
+pub struct HttpApiConfig {
+    pub port: u16,
+    /// Listen on all interfaces instead of localhost; requires `API_TOKEN`
+    pub public: bool,
+}
+
+impl HttpApiConfig {
+    /// `API_ENABLED` turns the API on; it listens on `REST_API_PORT` (default 3000), on every
+    /// interface when `API_BIND_PUBLIC` is set. `None` when the API is disabled.
+    pub fn from_env() -> Result<Option<Self>> {
+        let env = |key: &str| std::env::var(key).ok().filter(|value| !value.trim().is_empty());
+        let is_set = |value: Option<String>| value
+            .is_some_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"));
+
+        if !is_set(env("API_ENABLED")) {
+            return Ok(None);
+        }
+        let port = match env("REST_API_PORT") {
+            Some(port) => port.trim().parse()
+                .with_context(|| format!("Invalid REST_API_PORT '{}'", port))?,
+            None => 3000,
+        };
+        Ok(Some(Self { port, public: is_set(env("API_BIND_PUBLIC")) }))
+    }
+}
+
+#[derive(Clone)]
+struct ApiState {
+    monitor: Arc<FilteredTransactionMonitor>,
+}
+
+/// Routes of the API, guarded by `auth`:
+/// - `GET /status`: last processed slot, chain tip, slots behind and match totals
+/// - `GET /collections`: stored collections and their sizes
+/// - `GET /collections/{name}/transactions?offset=&limit=`: one page of a collection, oldest first
+/// - `GET /filters`: the active filters
+pub fn router(monitor: Arc<FilteredTransactionMonitor>, auth: ApiAuth) -> Router {
+    Router::new()
+        .route("/status", get(status))
+        .route("/collections", get(collections))
+        .route("/collections/{name}/transactions", get(collection_transactions))
+        .route("/filters", get(filters))
+        .layer(axum::middleware::from_fn_with_state(Arc::new(auth), require_token))
+        .with_state(ApiState { monitor })
+}
+
+/// Bind the API per `config` and serve it in the background. Binding happens before this
+/// returns, so a taken port or a refused public bind fails startup instead of a later log line.
+pub async fn spawn(monitor: Arc<FilteredTransactionMonitor>, config: &HttpApiConfig) -> Result<JoinHandle<()>> {
+    let auth = ApiAuth::from_env();
+    let address = auth.bind_address(config.port, config.public)?;
+    let listener = tokio::net::TcpListener::bind(address).await
+        .with_context(|| format!("Failed to bind HTTP API to {}", address))?;
+    let app = router(monitor, auth);
+
+    info!("HTTP API listening on {}", address);
+    Ok(tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("HTTP API stopped: {:#}", e);
+        }
+    }))
+}
+
+#[derive(Debug, Serialize)]
+struct StatusResponse {
+    last_processed_slot: Option<u64>,
+    /// Chain tip at the monitor's commitment, as last read by the monitor loop; `None` before
+    /// its first read and for the Geyser stream, which has no separate tip
+    current_slot: Option<u64>,
+    slots_behind: Option<u64>,
+    slots_processed: u64,
+    matches_found: u64,
+    /// Seconds since a slot was last processed successfully
+    silent_for_secs: u64,
+}
+
+async fn status(State(state): State<ApiState>) -> Json<StatusResponse> {
+    let stats = state.monitor.processing_stats();
+    let current_slot = stats.chain_tip;
+    let slots_behind = current_slot.zip(stats.last_processed_slot)
+        .map(|(current, last)| current.saturating_sub(last));
+
+    Json(StatusResponse {
+        last_processed_slot: stats.last_processed_slot,
+        current_slot,
+        slots_behind,
+        slots_processed: stats.slots_processed,
+        matches_found: stats.matches_found,
+        silent_for_secs: state.monitor.silent_for().as_secs(),
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct CollectionSummary {
+    name: String,
+    transactions: usize,
+}
+
+async fn collections(State(state): State<ApiState>) -> Json<Vec<CollectionSummary>> {
+    let mut collections: Vec<CollectionSummary> = state.monitor.get_storage_summary().await
+        .into_iter()
+        .map(|(name, transactions)| CollectionSummary { name, transactions })
+        .collect();
+    collections.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(collections)
+}
+
+#[derive(Debug, Deserialize)]
+struct PageQuery {
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct TransactionPage {
+    collection: String,
+    total: usize,
+    offset: usize,
+    limit: usize,
+    transactions: Vec<StoredTransaction>,
+}
+
+async fn collection_transactions(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let Some((total, transactions)) = state.monitor.get_stored_page(&name, query.offset, limit).await else {
+        return (StatusCode::NOT_FOUND, format!("Unknown collection '{}'", name)).into_response();
+    };
+
+    Json(TransactionPage {
+        total,
+        offset: query.offset,
+        limit,
+        transactions,
+        collection: name,
+    }).into_response()
+}
+
+#[derive(Debug, Serialize)]
+struct FilterSummary {
+    id: String,
+    name: String,
+    enabled: bool,
+    description: String,
+}
+
+async fn filters(State(state): State<ApiState>) -> Json<Vec<FilterSummary>> {
+    let engine = state.monitor.filter_engine();
+    Json(engine.filters().iter()
+        .map(|filter| FilterSummary {
+            id: filter.id.clone(),
+            name: filter.name.clone(),
+            enabled: filter.enabled,
+            description: describe_filter(filter),
+        })
+        .collect())
+}
@@ -1,16 +1,38 @@
 pub mod config;
 pub mod filter_engine;
+pub mod filter_state;
+pub mod filter_summary;
+pub mod filter_fixtures;
 pub mod telegram_notifier;
 pub mod filtered_monitor;
 pub mod transaction_extractor;
 pub mod notifications;
+pub mod alert_digest;
+pub mod result_sink;
+pub mod catchup_ledger;
+pub mod checkpoint;
+pub mod api_auth;
+pub mod http_api;
 pub mod config_manager;
+pub mod config_watcher;
 pub mod discord_notifier;
 pub mod slack_notifier;
+pub mod email_notifier;
+pub mod notifier_retry;
+pub mod webhook_sender;
 pub mod rpc_client_with_failover;
+pub mod block_cache;
 pub mod concurrent_slot_processor;
 pub mod parallel_filter_processor;
 pub mod fast_slot_monitor;
+pub mod geyser_source;
 pub mod slot_pre_filter;
+pub mod prefilter_audit;
+pub mod slot_leader_cache;
+pub mod account_owner_cache;
+pub mod mint_supply_cache;
+pub mod storage_codec;
+pub mod storage_backend;
+pub mod telemetry;
 pub mod selective_monitor;
 pub mod yu_focused_filter;
\ No newline at end of file
@@ -0,0 +1,79 @@
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::rpc_client_with_failover::RpcClientWithFailover;
+
+/// Default age after which a cached supply is fetched again
+const DEFAULT_SUPPLY_TTL_SECS: u64 = 300;
+
+/// Mint → current total supply (UI units), fetched with `getTokenSupply` and refreshed once
+/// older than the TTL. Only the mints handed to `new` are looked up, so the cache stays tiny.
+///
+/// Supply is read at lookup time, not at the transaction's slot, which is close enough for
+/// percentage thresholds on a live monitor but drifts when replaying old slots.
+pub struct MintSupplyCache {
+    rpc_client: RpcClientWithFailover,
+    mints: Vec<String>,
+    ttl: Duration,
+    supplies: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl MintSupplyCache {
+    pub fn new(rpc_url: String, mints: Vec<String>, ttl: Duration) -> Self {
+        Self {
+            rpc_client: RpcClientWithFailover::new(rpc_url),
+            mints,
+            ttl,
+            supplies: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The mints whose supply is looked up
+    pub fn mints(&self) -> &[String] {
+        &self.mints
+    }
+
+    /// TTL from `MINT_SUPPLY_TTL_SECS` (default 300)
+    pub fn ttl_from_env() -> Duration {
+        let secs = std::env::var("MINT_SUPPLY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_SUPPLY_TTL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    /// Supplies of all tracked mints, refreshing the ones that are missing or stale. A failed
+    /// refresh keeps the previous value (if any) and is logged, never returned.
+    pub async fn supplies(&self) -> HashMap<String, f64> {
+        let stale: Vec<String> = {
+            let supplies = self.supplies.lock().await;
+            self.mints.iter()
+                .filter(|mint| supplies.get(*mint).is_none_or(|(_, fetched)| fetched.elapsed() >= self.ttl))
+                .cloned()
+                .collect()
+        };
+
+        // The lock is not held across RPC calls, so concurrent slots don't wait on each other
+        for mint in stale {
+            let Ok(pubkey) = Pubkey::from_str(&mint) else {
+                warn!("Cannot look up the supply of invalid mint {}", mint);
+                continue;
+            };
+            match self.rpc_client.get_token_supply(&pubkey).await {
+                Ok(supply) => {
+                    debug!("Supply of {} is {}", mint, supply.ui_amount);
+                    self.supplies.lock().await.insert(mint, (supply.ui_amount, Instant::now()));
+                },
+                Err(e) => warn!("Could not refresh the supply of {}: {:#}", mint, e),
+            }
+        }
+
+        self.supplies.lock().await.iter()
+            .map(|(mint, (supply, _))| (mint.clone(), *supply))
+            .collect()
+    }
+}
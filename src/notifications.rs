@@ -7,7 +7,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error};
 use std::time::{Duration, Instant};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use futures::future;
 use chrono;
@@ -55,18 +55,28 @@ pub struct Alert {
     pub metadata: HashMap<String, String>,
 }
 
+/// Default number of alert keys remembered for deduplication (~0.5 KB each, so ~5 MB at most)
+const DEFAULT_DEDUP_CAPACITY: usize = 10_000;
+/// Default number of failed sends kept for retry (~2 KB per alert, so ~2 MB at most)
+const DEFAULT_RETRY_QUEUE_CAPACITY: usize = 1_000;
+
 pub struct NotificationManager {
     channels: Arc<RwLock<Vec<Box<dyn NotificationChannel>>>>,
     rate_limiter: Arc<RwLock<RateLimiter>>,
     deduplication_cache: Arc<RwLock<DeduplicationCache>>,
+    retry_queue: Arc<RwLock<RetryQueue>>,
 }
 
 impl NotificationManager {
     pub fn new() -> Self {
+        let dedup_capacity = capacity_from_env("NOTIFICATION_DEDUP_CAPACITY", DEFAULT_DEDUP_CAPACITY);
+        let retry_capacity = capacity_from_env("NOTIFICATION_RETRY_QUEUE_CAPACITY", DEFAULT_RETRY_QUEUE_CAPACITY);
+
         Self {
             channels: Arc::new(RwLock::new(Vec::new())),
             rate_limiter: Arc::new(RwLock::new(RateLimiter::new())),
-            deduplication_cache: Arc::new(RwLock::new(DeduplicationCache::new())),
+            deduplication_cache: Arc::new(RwLock::new(DeduplicationCache::new(dedup_capacity))),
+            retry_queue: Arc::new(RwLock::new(RetryQueue::new(retry_capacity))),
         }
     }
 
@@ -129,18 +139,92 @@ impl NotificationManager {
                 
                 send_futures.push(async move {
                     match channel_clone.send(&alert_clone).await {
-                        Ok(_) => info!("Alert sent successfully via {}", channel_name),
-                        Err(e) => error!("Failed to send alert via {}: {}", channel_name, e),
+                        Ok(_) => {
+                            info!("Alert sent successfully via {}", channel_name);
+                            None
+                        },
+                        Err(e) => {
+                            error!("Failed to send alert via {}: {}", channel_name, e);
+                            Some((channel_name, alert_clone))
+                        }
                     }
                 });
             }
         }
 
         // Execute all sends concurrently
-        futures::future::join_all(send_futures).await;
+        let failed = futures::future::join_all(send_futures).await;
+
+        let mut retry_queue = self.retry_queue.write().await;
+        for (channel_name, alert) in failed.into_iter().flatten() {
+            retry_queue.push(channel_name, alert);
+        }
 
         Ok(())
     }
+
+    /// Queue `alert` for `retry_failed` after a send through the channel named `channel_name`
+    /// failed outside `send_alert`. Returns false, queueing nothing, when no such channel is
+    /// registered.
+    pub async fn queue_retry(&self, channel_name: &str, alert: Alert) -> bool {
+        if !self.channels.read().await.iter().any(|c| c.name() == channel_name) {
+            return false;
+        }
+        self.retry_queue.write().await.push(channel_name.to_string(), alert);
+        true
+    }
+
+    /// Failed sends waiting in the retry queue
+    pub async fn pending_retries(&self) -> usize {
+        self.retry_queue.read().await.pending.len()
+    }
+
+    /// Resend alerts whose delivery failed earlier; sends that fail again go back on the queue
+    pub async fn retry_failed(&self) -> usize {
+        let pending = self.retry_queue.write().await.drain();
+        if pending.is_empty() {
+            return 0;
+        }
+
+        let channels = self.channels.read().await;
+        let mut delivered = 0;
+        let mut still_failed = Vec::new();
+
+        for (channel_name, alert) in pending {
+            let Some(channel) = channels.iter().find(|c| c.name() == channel_name && c.is_enabled()) else {
+                warn!("Dropping queued alert {} for unavailable channel {}", alert.id, channel_name);
+                continue;
+            };
+
+            match channel.send(&alert).await {
+                Ok(_) => delivered += 1,
+                Err(e) => {
+                    warn!("Retry of alert {} via {} failed: {}", alert.id, channel_name, e);
+                    still_failed.push((channel_name, alert));
+                }
+            }
+        }
+
+        let mut retry_queue = self.retry_queue.write().await;
+        for (channel_name, alert) in still_failed {
+            retry_queue.push(channel_name, alert);
+        }
+
+        delivered
+    }
+}
+
+fn capacity_from_env(name: &str, default: usize) -> usize {
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => match value.trim().parse::<usize>() {
+            Ok(capacity) if capacity > 0 => capacity,
+            _ => {
+                warn!("Ignoring invalid {}={:?}, using {}", name, value, default);
+                default
+            }
+        },
+        _ => default,
+    }
 }
 
 // Slack notification implementation
@@ -451,39 +535,97 @@ impl RateLimiter {
     }
 }
 
-// Deduplication cache to prevent duplicate alerts
+// Deduplication cache to prevent duplicate alerts.
+//
+// Keys are kept in insertion order alongside the lookup map, so expired entries are popped from
+// the front (amortized O(1) per alert) and the oldest key is evicted once `capacity` is reached.
 struct DeduplicationCache {
     cache: HashMap<String, Instant>,
+    order: VecDeque<(String, Instant)>,
     ttl: Duration,
+    capacity: usize,
 }
 
 impl DeduplicationCache {
-    fn new() -> Self {
+    fn new(capacity: usize) -> Self {
         Self {
             cache: HashMap::new(),
+            order: VecDeque::new(),
             ttl: Duration::from_secs(300), // 5 minutes
+            capacity: capacity.max(1),
         }
     }
 
-    fn is_duplicate(&mut self, alert: &Alert) -> bool {
-        // Clean expired entries
-        self.cache.retain(|_, timestamp| timestamp.elapsed() < self.ttl);
+    fn cache_key(alert: &Alert) -> String {
+        format!("{}-{}-{}", alert.title, alert.severity.to_string(),
+            alert.transaction.as_ref().map(|tx| &tx.signature).unwrap_or(&alert.id))
+    }
 
-        // Create cache key from alert content
-        let cache_key = format!("{}-{}-{}", alert.title, alert.severity.to_string(), 
-            alert.transaction.as_ref().map(|tx| &tx.signature).unwrap_or(&alert.id));
+    fn is_duplicate(&mut self, alert: &Alert) -> bool {
+        self.evict_expired();
+        self.cache.contains_key(&Self::cache_key(alert))
+    }
 
+    fn add(&mut self, alert: &Alert) {
+        let cache_key = Self::cache_key(alert);
         if self.cache.contains_key(&cache_key) {
-            true
-        } else {
-            false
+            // Keeps `order` and `cache` the same length; the TTL runs from the first sighting
+            return;
         }
+
+        if self.cache.len() >= self.capacity {
+            if let Some((oldest, _)) = self.order.pop_front() {
+                self.cache.remove(&oldest);
+            }
+        }
+
+        let now = Instant::now();
+        self.cache.insert(cache_key.clone(), now);
+        self.order.push_back((cache_key, now));
     }
 
-    fn add(&mut self, alert: &Alert) {
-        let cache_key = format!("{}-{}-{}", alert.title, alert.severity.to_string(),
-            alert.transaction.as_ref().map(|tx| &tx.signature).unwrap_or(&alert.id));
-        self.cache.insert(cache_key, Instant::now());
+    fn evict_expired(&mut self) {
+        while let Some((_, inserted)) = self.order.front() {
+            if inserted.elapsed() < self.ttl {
+                break;
+            }
+            if let Some((key, _)) = self.order.pop_front() {
+                self.cache.remove(&key);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+// Failed sends waiting for `NotificationManager::retry_failed`; the oldest are dropped when full
+struct RetryQueue {
+    pending: VecDeque<(String, Alert)>,
+    capacity: usize,
+}
+
+impl RetryQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn push(&mut self, channel_name: String, alert: Alert) {
+        if self.pending.len() >= self.capacity {
+            if let Some((dropped_channel, dropped)) = self.pending.pop_front() {
+                warn!("Retry queue full, dropping alert {} for {}", dropped.id, dropped_channel);
+            }
+        }
+        self.pending.push_back((channel_name, alert));
+    }
+
+    fn drain(&mut self) -> Vec<(String, Alert)> {
+        self.pending.drain(..).collect()
     }
 }
 
@@ -551,4 +693,27 @@ mod tests {
         // 21st request should be blocked
         assert!(!limiter.check_limit(&AlertSeverity::High));
     }
+
+    #[test]
+    fn test_dedup_cache_is_bounded() {
+        let alert = |id: usize| Alert {
+            id: format!("alert-{}", id),
+            timestamp: 0,
+            severity: AlertSeverity::Low,
+            title: "Flood".to_string(),
+            message: String::new(),
+            transaction: None,
+            metadata: HashMap::new(),
+        };
+
+        let mut cache = DeduplicationCache::new(3);
+        for id in 0..10 {
+            assert!(!cache.is_duplicate(&alert(id)));
+            cache.add(&alert(id));
+        }
+
+        assert_eq!(cache.len(), 3);
+        assert!(cache.is_duplicate(&alert(9)));
+        assert!(!cache.is_duplicate(&alert(0)));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,148 @@
+use anyhow::{Result, Context};
+use reqwest::{Response, StatusCode};
+use serde::Serialize;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+/// Total attempts per notification, including the first one
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+/// Never wait longer than this, even if the server asks for it
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_CONCURRENT_NOTIFICATIONS: usize = 10;
+
+/// Bounds outbound notifier requests across every notifier and slot in the process, so an alert
+/// storm queues instead of opening connections without limit. None when disabled.
+static NOTIFICATION_LIMITER: LazyLock<Option<Semaphore>> = LazyLock::new(|| {
+    max_concurrent_notifications_from_env().map(Semaphore::new)
+});
+
+/// `MAX_CONCURRENT_NOTIFICATIONS` (default 10); 0 turns the limit off
+fn max_concurrent_notifications_from_env() -> Option<usize> {
+    let limit = match std::env::var("MAX_CONCURRENT_NOTIFICATIONS") {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse::<usize>().unwrap_or_else(|_| {
+            warn!(
+                "Invalid MAX_CONCURRENT_NOTIFICATIONS '{}', using {}",
+                value, DEFAULT_MAX_CONCURRENT_NOTIFICATIONS
+            );
+            DEFAULT_MAX_CONCURRENT_NOTIFICATIONS
+        }),
+        _ => DEFAULT_MAX_CONCURRENT_NOTIFICATIONS,
+    };
+    (limit > 0).then_some(limit)
+}
+
+/// POST a JSON body, retrying connection errors, 5xx responses and 429s.
+///
+/// Other 4xx responses are returned immediately since resending the same payload won't help.
+/// When a 429 carries a `Retry-After` header or a `retry_after` field in the body (Discord and
+/// Telegram both use one), that delay is used instead of the exponential backoff. The final
+/// response is returned as-is so callers keep their own status handling.
+///
+/// Each attempt holds a permit of the process-wide `MAX_CONCURRENT_NOTIFICATIONS` limit while
+/// the request is in flight; backoff waits don't.
+pub async fn post_json_with_retry<T: Serialize + ?Sized>(
+    client: &reqwest::Client,
+    url: &str,
+    body: &T,
+    service: &str,
+) -> Result<Response> {
+    send_with_retry(service, MAX_ATTEMPTS, || client.post(url).json(body)).await
+}
+
+/// `post_json_with_retry` for any request: `request` builds a fresh request for every attempt,
+/// of which there are at most `max_attempts`
+pub async fn send_with_retry(
+    service: &str,
+    max_attempts: u32,
+    request: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<Response> {
+    let max_attempts = max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        let backoff = (BASE_BACKOFF * 2u32.saturating_pow(attempt - 1)).min(MAX_RETRY_AFTER);
+        let last_attempt = attempt >= max_attempts;
+
+        let permit = match NOTIFICATION_LIMITER.as_ref() {
+            Some(limiter) => Some(limiter.acquire().await.context("Notification limiter closed")?),
+            None => None,
+        };
+        let sent = request().send().await;
+        drop(permit);
+
+        match sent {
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+                if !retryable || last_attempt {
+                    return Ok(response);
+                }
+
+                let delay = if status == StatusCode::TOO_MANY_REQUESTS {
+                    retry_after(response).await.unwrap_or(backoff)
+                } else {
+                    backoff
+                };
+                warn!(
+                    "{} returned {} (attempt {}/{}), retrying in {:?}",
+                    service, status, attempt, max_attempts, delay
+                );
+                tokio::time::sleep(delay).await;
+            },
+            Err(e) if !last_attempt && (e.is_connect() || e.is_timeout() || e.is_request()) => {
+                warn!(
+                    "{} request failed (attempt {}/{}), retrying in {:?}: {}",
+                    service, attempt, max_attempts, backoff, e
+                );
+                tokio::time::sleep(backoff).await;
+            },
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to send {} request", service));
+            }
+        }
+
+        attempt += 1;
+    }
+}
+
+/// Delay requested by a rate-limited response, capped at `MAX_RETRY_AFTER`
+async fn retry_after(response: Response) -> Option<Duration> {
+    let from_header = response.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok());
+
+    let seconds = match from_header {
+        Some(seconds) => seconds,
+        None => retry_after_from_body(&response.text().await.ok()?)?,
+    };
+
+    Some(Duration::from_secs_f64(seconds.max(0.0)).min(MAX_RETRY_AFTER))
+}
+
+/// `retry_after` (Discord) or `parameters.retry_after` (Telegram), in seconds
+fn retry_after_from_body(body: &str) -> Option<f64> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    json.get("retry_after")
+        .or_else(|| json.get("parameters").and_then(|p| p.get("retry_after")))
+        .and_then(|value| value.as_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_after_from_body() {
+        assert_eq!(retry_after_from_body(r#"{"message":"rate limited","retry_after":1.5}"#), Some(1.5));
+        assert_eq!(
+            retry_after_from_body(r#"{"ok":false,"error_code":429,"parameters":{"retry_after":7}}"#),
+            Some(7.0)
+        );
+        assert_eq!(retry_after_from_body(r#"{"ok":false}"#), None);
+        assert_eq!(retry_after_from_body("rate limited"), None);
+    }
+}
@@ -0,0 +1,140 @@
+use anyhow::{Context, Result};
+use std::sync::Mutex;
+
+/// Audited slots needed before the miss rate is judged; a handful of slots says nothing
+const MIN_AUDITED_SLOTS: u64 = 20;
+
+/// Re-checks a random sample of the slots a pre-filter (YU-focused, selective or optimization
+/// pre-filter) skipped, so its false negatives become visible. The caller runs the sampled slots
+/// through full extraction and reports back whether they had matches.
+pub struct PrefilterAudit {
+    /// Fraction of skipped slots re-processed (0-1]
+    sample_rate: f64,
+    /// Share of audited slots with matches above which the pre-filter is reported as unreliable
+    max_miss_rate: f64,
+    seed: u64,
+    stats: Mutex<AuditStats>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AuditStats {
+    pub audited: u64,
+    /// Audited slots that had at least one match the pre-filter would have dropped
+    pub missed: u64,
+    alerted: bool,
+}
+
+impl AuditStats {
+    pub fn miss_rate(&self) -> f64 {
+        if self.audited == 0 { 0.0 } else { self.missed as f64 / self.audited as f64 }
+    }
+}
+
+impl PrefilterAudit {
+    pub fn new(sample_rate: f64, max_miss_rate: f64) -> Self {
+        Self {
+            sample_rate: sample_rate.clamp(0.0, 1.0),
+            max_miss_rate,
+            seed: uuid::Uuid::new_v4().as_u128() as u64,
+            stats: Mutex::new(AuditStats::default()),
+        }
+    }
+
+    /// Enabled by `PREFILTER_AUDIT_SAMPLE_RATE` (e.g. 0.01 re-checks 1% of skipped slots);
+    /// `PREFILTER_AUDIT_MAX_MISS_RATE` (default 0.01) is the tolerated share of audited slots
+    /// with matches. Unset or 0 turns the audit off.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(rate) = std::env::var("PREFILTER_AUDIT_SAMPLE_RATE").ok().filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let sample_rate = rate.trim().parse::<f64>()
+            .ok()
+            .filter(|rate| (0.0..=1.0).contains(rate))
+            .with_context(|| format!("Invalid PREFILTER_AUDIT_SAMPLE_RATE '{}': expected 0-1", rate))?;
+        if sample_rate == 0.0 {
+            return Ok(None);
+        }
+
+        let max_miss_rate = match std::env::var("PREFILTER_AUDIT_MAX_MISS_RATE").ok().filter(|v| !v.trim().is_empty()) {
+            Some(value) => value.trim().parse::<f64>()
+                .with_context(|| format!("Invalid PREFILTER_AUDIT_MAX_MISS_RATE '{}'", value))?,
+            None => 0.01,
+        };
+        Ok(Some(Self::new(sample_rate, max_miss_rate)))
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// The skipped slots to re-process. Selection is random per run but stable per slot.
+    pub fn sample(&self, skipped: &[u64]) -> Vec<u64> {
+        skipped.iter()
+            .copied()
+            .filter(|slot| unit_interval(splitmix64(slot ^ self.seed)) < self.sample_rate)
+            .collect()
+    }
+
+    /// Record one audited slot. Returns a warning the first time the miss rate goes above the
+    /// threshold (after `MIN_AUDITED_SLOTS`); it re-arms once the rate drops back below.
+    pub fn record(&self, slot: u64, matches: usize) -> Option<String> {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats.audited += 1;
+        if matches > 0 {
+            stats.missed += 1;
+        }
+
+        let over = stats.audited >= MIN_AUDITED_SLOTS && stats.miss_rate() > self.max_miss_rate;
+        if !over {
+            stats.alerted = false;
+            return None;
+        }
+        if stats.alerted {
+            return None;
+        }
+        stats.alerted = true;
+        Some(format!(
+            "⚠️ Pre-filter audit: {} of {} re-checked skipped slots had matches ({:.1}%, threshold {:.1}%), latest slot {}",
+            stats.missed,
+            stats.audited,
+            stats.miss_rate() * 100.0,
+            self.max_miss_rate * 100.0,
+            slot
+        ))
+    }
+
+    pub fn stats(&self) -> AuditStats {
+        *self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+fn unit_interval(x: u64) -> f64 {
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warns_once_when_miss_rate_exceeds_threshold() {
+        let audit = PrefilterAudit::new(1.0, 0.05);
+        assert_eq!(audit.sample(&[1, 2, 3]), vec![1, 2, 3]);
+
+        for slot in 0..19 {
+            assert!(audit.record(slot, 0).is_none());
+        }
+        // 1 miss in 20 audited slots is exactly the threshold, not above it
+        assert!(audit.record(19, 1).is_none());
+        assert!(audit.record(20, 1).is_some());
+        assert!(audit.record(21, 1).is_none(), "warned only once");
+        assert_eq!(audit.stats().missed, 3);
+    }
+}
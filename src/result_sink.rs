@@ -0,0 +1,245 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use crate::filtered_monitor::{StoredTransaction, TransactionStorage};
+use crate::notifications::{create_alert_from_transaction, AlertSeverity, NotificationChannel, TransactionEvent};
+use crate::transaction_extractor::TokenAmount;
+
+const VOTE_PROGRAM_ID: &str = "Vote111111111111111111111111111111111111111";
+
+/// Receives every matched transaction once the matching filters' actions have run, for
+/// embedding the monitor in another system without changing the action dispatch. Register
+/// sinks with `FilteredTransactionMonitor::with_sink`; they run in registration order, and a
+/// failing sink is logged without affecting the others.
+#[async_trait]
+pub trait ResultSink: Send + Sync {
+    /// Shown in logs when `handle` fails
+    fn name(&self) -> &str;
+
+    async fn handle(&self, matched: &StoredTransaction) -> Result<()>;
+}
+
+/// Stores every match into one collection, e.g. the monitor's own storage
+/// (`FilteredTransactionMonitor::storage`) to keep all matches regardless of filter actions
+pub struct StorageSink {
+    storage: Arc<RwLock<TransactionStorage>>,
+    collection: String,
+}
+
+impl StorageSink {
+    pub fn new(storage: Arc<RwLock<TransactionStorage>>, collection: impl Into<String>) -> Self {
+        Self { storage, collection: collection.into() }
+    }
+}
+
+#[async_trait]
+impl ResultSink for StorageSink {
+    fn name(&self) -> &str {
+        "storage"
+    }
+
+    async fn handle(&self, matched: &StoredTransaction) -> Result<()> {
+        self.storage.write().await.import_record(matched.clone(), &self.collection);
+        Ok(())
+    }
+}
+
+/// Sends a short alert for every match through any notification channel (console, Slack, ...)
+pub struct NotificationSink {
+    channel: Arc<dyn NotificationChannel>,
+    severity: AlertSeverity,
+}
+
+impl NotificationSink {
+    pub fn new(channel: Arc<dyn NotificationChannel>, severity: AlertSeverity) -> Self {
+        Self { channel, severity }
+    }
+}
+
+#[async_trait]
+impl ResultSink for NotificationSink {
+    fn name(&self) -> &str {
+        self.channel.name()
+    }
+
+    async fn handle(&self, matched: &StoredTransaction) -> Result<()> {
+        if !self.channel.is_enabled() {
+            return Ok(());
+        }
+
+        let transaction = &matched.transaction;
+        let event = TransactionEvent {
+            signature: transaction.signature.clone(),
+            slot: transaction.slot,
+            success: transaction.success,
+            fee: transaction.fee,
+        };
+        let alert = create_alert_from_transaction(
+            &event,
+            format!("Matched {}", matched.matched_filters.join(", ")),
+            format!("Transaction {} in slot {}", transaction.signature, transaction.slot),
+            self.severity.clone(),
+        );
+        self.channel.send(&alert).await
+    }
+}
+
+/// Writes every match as one JSON line shaped like a Geyser plugin transaction notification
+/// (`slot` plus `transaction` with `signature`, `is_vote`, `transaction.message.account_keys`
+/// and `meta`), so consumers built for Geyser output can read the monitor's matches directly.
+/// Only the fields the monitor keeps are filled in; token balances are rebuilt from the
+/// transaction's token balance changes, so they are present in lean extraction too.
+pub struct GeyserFormatSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl GeyserFormatSink {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self { writer: Mutex::new(Box::new(writer)) }
+    }
+
+    /// Appends to `path`, creating it if needed
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        Ok(Self::new(std::io::BufWriter::new(file)))
+    }
+}
+
+#[async_trait]
+impl ResultSink for GeyserFormatSink {
+    fn name(&self) -> &str {
+        "geyser"
+    }
+
+    async fn handle(&self, matched: &StoredTransaction) -> Result<()> {
+        let line = serde_json::to_string(&geyser_transaction_notification(matched))?;
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(writer, "{}", line)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// The Geyser-style notification for one match
+pub fn geyser_transaction_notification(matched: &StoredTransaction) -> Value {
+    let transaction = &matched.transaction;
+    let token_balance = |account: &str, mint: &str, owner: &Option<String>, amount: &TokenAmount| {
+        let account_index = transaction.account_keys.iter().position(|key| key == account)?;
+        Some(json!({
+            "account_index": account_index,
+            "mint": mint,
+            "owner": owner.clone().unwrap_or_default(),
+            "ui_token_amount": {
+                "amount": amount.amount,
+                "decimals": amount.decimals,
+                "ui_amount": amount.ui_amount,
+                "ui_amount_string": amount.ui_amount.map(|ui| ui.to_string()).unwrap_or_default(),
+            },
+        }))
+    };
+    let pre_token_balances: Vec<Value> = transaction.token_balance_changes.iter()
+        .filter_map(|change| token_balance(&change.account, &change.mint, &change.owner, &change.before))
+        .collect();
+    let post_token_balances: Vec<Value> = transaction.token_balance_changes.iter()
+        .filter_map(|change| token_balance(&change.account, &change.mint, &change.owner, &change.after))
+        .collect();
+
+    json!({
+        "slot": transaction.slot,
+        "transaction": {
+            "signature": transaction.signature,
+            "is_vote": transaction.instructions.iter().any(|inst| inst.program_id == VOTE_PROGRAM_ID),
+            "transaction": {
+                "signatures": [transaction.signature],
+                "message": {
+                    "account_keys": transaction.account_keys,
+                    "recent_blockhash": transaction.recent_blockhash,
+                },
+            },
+            "meta": {
+                "err": transaction.error,
+                "fee": transaction.fee,
+                "pre_balances": transaction.pre_balances,
+                "post_balances": transaction.post_balances,
+                "pre_token_balances": pre_token_balances,
+                "post_token_balances": post_token_balances,
+                "log_messages": transaction.log_messages,
+                "compute_units_consumed": transaction.compute_units_consumed,
+            },
+        },
+        "matched_filters": matched.matched_filters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_engine::tests::{test_transaction, token_change};
+
+    #[tokio::test]
+    async fn test_storage_sink_stores_every_match() {
+        let mut transaction = test_transaction();
+        transaction.signature = "sig1".to_string();
+        transaction.slot = 7;
+        let matched = StoredTransaction {
+            transaction,
+            matched_filters: vec!["big_mint".to_string(), "whale".to_string()],
+            stored_at: chrono::Utc::now(),
+            collection: "filtered".to_string(),
+            slot_leader: None,
+        };
+
+        let storage = Arc::new(RwLock::new(TransactionStorage::new()));
+        let sink: Arc<dyn ResultSink> = Arc::new(StorageSink::new(storage.clone(), "all_matches"));
+        sink.handle(&matched).await.unwrap();
+
+        let storage = storage.read().await;
+        let stored = storage.get_collection("all_matches").unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].transaction.signature, "sig1");
+        assert_eq!(stored[0].matched_filters, vec!["big_mint".to_string(), "whale".to_string()]);
+        assert_eq!(stored[0].collection, "all_matches");
+        assert_eq!(storage.get_matches_by_signature("sig1").len(), 1);
+    }
+
+    #[test]
+    fn test_geyser_notification_carries_token_balances() {
+        let mut change = token_change("holder", "YU", 2.5);
+        change.owner = Some("wallet".to_string());
+        change.before.decimals = 6;
+        change.after = TokenAmount { amount: "2500000".to_string(), decimals: 6, ui_amount: Some(2.5) };
+        let mut transaction = test_transaction();
+        transaction.signature = "sig1".to_string();
+        transaction.slot = 7;
+        transaction.account_keys = vec!["payer".to_string(), "holder".to_string()];
+        transaction.pre_balances = vec![10, 0];
+        transaction.post_balances = vec![5, 0];
+        transaction.token_balance_changes = vec![change];
+        transaction.recent_blockhash = "hash".to_string();
+        let matched = StoredTransaction {
+            transaction,
+            matched_filters: vec!["big_mint".to_string()],
+            stored_at: chrono::Utc::now(),
+            collection: "filtered".to_string(),
+            slot_leader: None,
+        };
+
+        let notification = geyser_transaction_notification(&matched);
+        assert_eq!(notification["slot"], 7);
+        assert_eq!(notification["transaction"]["signature"], "sig1");
+        assert_eq!(notification["transaction"]["transaction"]["message"]["account_keys"][1], "holder");
+        let post = &notification["transaction"]["meta"]["post_token_balances"][0];
+        assert_eq!(post["account_index"], 1);
+        assert_eq!(post["ui_token_amount"]["amount"], "2500000");
+        assert_eq!(post["ui_token_amount"]["ui_amount_string"], "2.5");
+    }
+}
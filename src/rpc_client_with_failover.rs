@@ -1,92 +1,503 @@
 use anyhow::{Result, Context};
 use solana_client::rpc_client::RpcClient;
-use solana_client::rpc_config::{RpcBlockConfig, RpcSignatureStatusConfig};
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcBlockConfig, RpcSignatureStatusConfig};
 use solana_client::rpc_response::RpcVersionInfo;
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::http_sender::HttpSender;
+use solana_rpc_client::rpc_client::RpcClientConfig;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 use solana_transaction_status::{EncodedConfirmedBlock, UiConfirmedBlock};
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
-use tracing::{info, warn, error};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, RwLock, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Semaphore};
+use tracing::{debug, info, warn, error};
+use crate::block_cache;
 
+const DEFAULT_RPC_CONFIG_FILE: &str = "config/rpc.json";
+
+/// Process-wide cap on in-flight RPC calls, picked up by every client created after it is installed
+static GLOBAL_RPC_LIMITER: OnceLock<Arc<Semaphore>> = OnceLock::new();
+
+/// Bound the number of concurrent RPC calls across every `RpcClientWithFailover` in the process
+/// (pre-filters, extraction, leader and owner lookups), so stages running at the same time can't
+/// add up past the limit. Call once at startup, before any client is created; returns false if a
+/// limit was already installed.
+pub fn install_global_rpc_limit(limit: usize) -> bool {
+    GLOBAL_RPC_LIMITER.set(Arc::new(Semaphore::new(limit))).is_ok()
+}
+
+/// Process-wide ceiling on RPC calls for the whole run, and the calls made so far
+static RPC_CALL_BUDGET: OnceLock<u64> = OnceLock::new();
+static RPC_CALLS: AtomicU64 = AtomicU64::new(0);
+
+/// Cap the total number of RPC calls (each failover attempt counts) made by every
+/// `RpcClientWithFailover` in the process. Once used up, calls fail without reaching the network
+/// and `rpc_budget_exhausted` turns true so monitoring loops can stop cleanly. Call once at
+/// startup; returns false if a budget was already installed.
+pub fn install_rpc_call_budget(max_calls: u64) -> bool {
+    RPC_CALL_BUDGET.set(max_calls).is_ok()
+}
+
+/// The installed budget and the calls counted against it so far
+pub fn rpc_call_budget() -> Option<(u64, u64)> {
+    RPC_CALL_BUDGET.get().map(|limit| (RPC_CALLS.load(Ordering::Relaxed), *limit))
+}
+
+pub fn rpc_budget_exhausted() -> bool {
+    rpc_call_budget().is_some_and(|(used, limit)| used >= limit)
+}
+
+/// Count one call against the budget, failing if none is left
+fn take_rpc_call(operation_name: &str) -> Result<()> {
+    let Some(limit) = RPC_CALL_BUDGET.get() else {
+        return Ok(());
+    };
+    RPC_CALLS.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| (used < *limit).then_some(used + 1))
+        .map(|_| ())
+        .map_err(|_| anyhow::anyhow!("RPC call budget of {} exhausted; not sending {}", limit, operation_name))
+}
+
+/// Endpoint pools of every client alive in the process, for swapping URLs everywhere at once
+static RPC_POOLS: Mutex<Vec<Weak<RwLock<EndpointPool>>>> = Mutex::new(Vec::new());
+
+/// Extra HTTP headers per RPC URL from the config file, sent by every client built for that URL
+static RPC_HEADERS: LazyLock<RwLock<HashMap<String, HeaderMap>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Blocking client for `url` that sends the URL's configured headers (e.g. `x-api-key`), if any
+pub fn build_rpc_client(url: &str, timeout: Duration) -> RpcClient {
+    let headers = RPC_HEADERS.read().unwrap().get(url).cloned();
+    let Some(headers) = headers else {
+        return RpcClient::new_with_timeout(url.to_string(), timeout);
+    };
+    
+    match reqwest::Client::builder().default_headers(headers).timeout(timeout).build() {
+        Ok(client) => RpcClient::new_sender(HttpSender::new_with_client(url, client), RpcClientConfig::default()),
+        Err(e) => {
+            warn!("Failed to build RPC client with custom headers for {}: {} (sending none)", url, e);
+            RpcClient::new_with_timeout(url.to_string(), timeout)
+        }
+    }
+}
+
+/// Async HTTP clients for JSON-RPC batch requests, one per URL so connections are reused;
+/// dropped when the URL's headers change
+static BATCH_HTTP_CLIENTS: LazyLock<Mutex<HashMap<String, reqwest::Client>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The batch client for `url`, built with the URL's configured headers on first use
+fn batch_http_client(url: &str) -> Result<reqwest::Client> {
+    let mut clients = BATCH_HTTP_CLIENTS.lock().unwrap();
+    if let Some(client) = clients.get(url) {
+        return Ok(client.clone());
+    }
+    let headers = RPC_HEADERS.read().unwrap().get(url).cloned().unwrap_or_default();
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .timeout(Duration::from_secs(60))
+        .build()
+        .context("Failed to build HTTP client for batch requests")?;
+    clients.insert(url.to_string(), client.clone());
+    Ok(client)
+}
+
+/// `getBlock` calls per JSON-RPC batch request (`RPC_BATCH_SIZE`)
+const DEFAULT_RPC_BATCH_SIZE: usize = 20;
+
+/// `RPC_BATCH_SIZE`, or 20. 1 sends every `getBlock` on its own, for providers that reject batches.
+pub fn rpc_batch_size() -> usize {
+    std::env::var("RPC_BATCH_SIZE").ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_RPC_BATCH_SIZE)
+}
+
+/// Re-read the RPC config file (`config/rpc.json` or `RPC_CONFIG_FILE`) and swap every client's
+/// URLs for its endpoints, e.g. on SIGHUP. The new URLs are probed first; see `set_rpc_endpoints`.
+pub fn reload_rpc_config() -> Result<usize> {
+    let path = rpc_config_path();
+    let endpoints = load_rpc_config(Path::new(&path))?
+        .ok_or_else(|| anyhow::anyhow!("No RPC config file at {}", path))?;
+    set_rpc_endpoints(endpoints)
+}
+
+/// Replace the URLs of every `RpcClientWithFailover` in the process. Each URL must answer
+/// `getVersion` to be adopted; when none does, nothing changes. Probing blocks, so async callers
+/// should run this on a blocking thread. Returns how many clients were updated.
+pub fn set_rpc_endpoints(endpoints: Vec<(String, u32)>) -> Result<usize> {
+    let healthy = probe_endpoints(endpoints)?;
+    
+    let mut pools = RPC_POOLS.lock().unwrap();
+    pools.retain(|pool| pool.strong_count() > 0);
+    for pool in pools.iter().filter_map(Weak::upgrade) {
+        *pool.write().unwrap() = EndpointPool::new(healthy.clone());
+    }
+    
+    info!("Switched {} RPC clients to {:?}", pools.len(), healthy.iter().map(|(url, _)| url).collect::<Vec<_>>());
+    Ok(pools.len())
+}
+
+/// One entry of the RPC config file (`config/rpc.json`, override with `RPC_CONFIG_FILE`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcEndpointConfig {
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Read the URL from this environment variable instead, to keep API keys out of the file
+    #[serde(default)]
+    pub url_env: Option<String>,
+    /// Relative share of normal traffic; 0 means failover only
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Extra HTTP headers sent with every request to this endpoint, e.g. `{"x-api-key": "..."}`
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Headers whose value is read from an environment variable, e.g. `{"x-api-key": "RPC_API_KEY"}`
+    #[serde(default)]
+    pub headers_env: HashMap<String, String>,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcConfigFile {
+    endpoints: Vec<RpcEndpointConfig>,
+}
+
+/// Observed traffic for one RPC URL
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStats {
+    pub url: String,
+    pub weight: u32,
+    pub requests: u64,
+    pub errors: u64,
+    pub avg_latency_ms: f64,
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    /// Smooth weighted round-robin accumulator
+    current_weight: i64,
+    requests: u64,
+    errors: u64,
+    total_latency: Duration,
+}
+
+/// Latency of one URL over repeated identical calls, from `bench_get_block`
+#[derive(Debug, Clone)]
+pub struct EndpointBenchmark {
+    pub url: String,
+    pub weight: u32,
+    pub iterations: usize,
+    pub errors: usize,
+    /// Successful calls only, fastest first
+    pub latencies: Vec<Duration>,
+}
+
+impl EndpointBenchmark {
+    pub fn min(&self) -> Option<Duration> {
+        self.latencies.first().copied()
+    }
+    
+    pub fn median(&self) -> Option<Duration> {
+        self.percentile(50.0)
+    }
+    
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(95.0)
+    }
+    
+    /// Nearest-rank percentile of the successful calls
+    pub fn percentile(&self, percent: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (percent / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+    
+    pub fn error_rate(&self) -> f64 {
+        if self.iterations == 0 { 0.0 } else { self.errors as f64 / self.iterations as f64 }
+    }
+}
+
+/// Result of `getTokenSupply`
+#[derive(Debug, Clone, Copy)]
+pub struct TokenSupply {
+    /// Supply in UI units (raw amount scaled by `decimals`)
+    pub ui_amount: f64,
+    pub decimals: u8,
+}
+
+/// URLs with their weights and traffic, swapped as a whole by `set_urls`
+struct EndpointPool {
+    rpc_urls: Vec<String>,
+    weights: Vec<u32>,
+    state: Vec<EndpointState>,
+}
+
+impl EndpointPool {
+    /// Duplicates keep the first entry; all-zero weights fall back to round-robin
+    fn new(endpoints: Vec<(String, u32)>) -> Self {
+        let mut rpc_urls = Vec::new();
+        let mut weights = Vec::new();
+        for (url, weight) in endpoints {
+            if !rpc_urls.contains(&url) {
+                rpc_urls.push(url);
+                weights.push(weight);
+            }
+        }
+        
+        // With every weight at zero there would be nothing to pick from
+        if weights.iter().all(|w| *w == 0) {
+            weights.iter_mut().for_each(|w| *w = 1);
+        }
+        
+        let state = (0..rpc_urls.len()).map(|_| EndpointState::default()).collect();
+        Self { rpc_urls, weights, state }
+    }
+}
+
+/// Clones share the endpoint pool, so `set_urls` on one applies to all of them
 #[derive(Clone)]
 pub struct RpcClientWithFailover {
-    rpc_urls: Vec<String>,
-    current_index: Arc<RwLock<usize>>,
+    pool: Arc<RwLock<EndpointPool>>,
     max_retries: usize,
+    /// Each call attempt holds a permit while it runs
+    limiter: Option<Arc<Semaphore>>,
+    /// Pushed slots replace `get_slot` polling while the socket is up
+    slot_subscription: Option<Arc<SlotSubscription>>,
 }
 
 impl RpcClientWithFailover {
+    /// The primary URL gets weight 1 and `SOLANA_RPC_URL_2..5` weight 0 (failover only), so without
+    /// an RPC config file all normal traffic goes to the primary. Entries in the config file set
+    /// weights for known URLs or add new ones.
     pub fn new(primary_url: String) -> Self {
-        let mut rpc_urls = vec![primary_url];
+        let mut endpoints = vec![(primary_url, 1)];
         
         // Add additional RPC URLs from environment variables
-        if let Ok(url2) = std::env::var("SOLANA_RPC_URL_2") {
-            if !url2.is_empty() {
-                rpc_urls.push(url2);
+        for key in ["SOLANA_RPC_URL_2", "SOLANA_RPC_URL_3", "SOLANA_RPC_URL_4", "SOLANA_RPC_URL_5"] {
+            if let Ok(url) = std::env::var(key) {
+                if !url.is_empty() {
+                    endpoints.push((url, 0));
+                }
             }
         }
         
-        if let Ok(url3) = std::env::var("SOLANA_RPC_URL_3") {
-            if !url3.is_empty() {
-                rpc_urls.push(url3);
-            }
-        }
-
-        if let Ok(url4) = std::env::var("SOLANA_RPC_URL_4") {
-            if !url4.is_empty() {
-                rpc_urls.push(url4);
-            }
-        }
-
-        if let Ok(url5) = std::env::var("SOLANA_RPC_URL_5") {
-            if !url5.is_empty() {
-                rpc_urls.push(url5);
-            }
+        let config_path = rpc_config_path();
+        match load_rpc_config(Path::new(&config_path)) {
+            Ok(Some(configured)) => {
+                for (url, weight) in configured {
+                    match endpoints.iter_mut().find(|(existing, _)| *existing == url) {
+                        Some(endpoint) => endpoint.1 = weight,
+                        None => endpoints.push((url, weight)),
+                    }
+                }
+            },
+            Ok(None) => {},
+            Err(e) => warn!("Ignoring RPC config {}: {:#}", config_path, e),
         }
         
-        info!("Initialized RPC client with {} URLs", rpc_urls.len());
+        Self::from_endpoints(endpoints)
+    }
+    
+    /// Build from explicit `(url, weight)` pairs; duplicates keep the first entry
+    pub fn from_endpoints(endpoints: Vec<(String, u32)>) -> Self {
+        let pool = EndpointPool::new(endpoints);
+        info!("Initialized RPC client with {} URLs (weights {:?})", pool.rpc_urls.len(), pool.weights);
+        
+        let pool = Arc::new(RwLock::new(pool));
+        RPC_POOLS.lock().unwrap().push(Arc::downgrade(&pool));
         
         Self {
-            rpc_urls,
-            current_index: Arc::new(RwLock::new(0)),
+            pool,
             max_retries: 3,
+            limiter: GLOBAL_RPC_LIMITER.get().cloned(),
+            slot_subscription: None,
+        }
+    }
+    
+    /// Follow new slots over a `slotSubscribe` WebSocket at `ws_url`: `get_slot` answers from
+    /// the latest pushed slot without an RPC call, and `wait_for_new_slot` wakes as soon as one
+    /// arrives. While the socket is down (it reconnects on its own) both fall back to polling.
+    pub fn with_slot_subscription(mut self, ws_url: String) -> Self {
+        self.slot_subscription = Some(Arc::new(SlotSubscription::spawn(ws_url)));
+        self
+    }
+    
+    /// Wait until a slot after `slot` is pushed, at most `timeout`. Without a connected
+    /// subscription this just sleeps `timeout`, the polling interval.
+    pub async fn wait_for_new_slot(&self, slot: u64, timeout: Duration) {
+        match &self.slot_subscription {
+            Some(subscription) if subscription.is_connected() => subscription.wait_for_slot_after(slot, timeout).await,
+            _ => tokio::time::sleep(timeout).await,
         }
     }
     
-    async fn get_current_client(&self) -> RpcClient {
-        let index = *self.current_index.read().await;
-        let url = &self.rpc_urls[index];
-        RpcClient::new_with_timeout(url.clone(), Duration::from_secs(10))
+    /// Swap this client's URLs (and its clones') at runtime. Each URL must answer `getVersion`
+    /// to be adopted; URLs already in the pool keep their weight, new ones get weight 1. When
+    /// no URL is healthy the pool is left unchanged. Returns the adopted URLs.
+    pub fn set_urls(&self, urls: Vec<String>) -> Result<Vec<String>> {
+        let endpoints = {
+            let pool = self.pool.read().unwrap();
+            urls.into_iter()
+                .map(|url| {
+                    let weight = pool.rpc_urls.iter().position(|existing| *existing == url)
+                        .map_or(1, |index| pool.weights[index]);
+                    (url, weight)
+                })
+                .collect()
+        };
+        let healthy = probe_endpoints(endpoints)?;
+        
+        let adopted: Vec<String> = healthy.iter().map(|(url, _)| url.clone()).collect();
+        *self.pool.write().unwrap() = EndpointPool::new(healthy);
+        info!("RPC URLs switched to {:?}", adopted);
+        Ok(adopted)
+    }
+    
+    /// Share `limiter` with other clients instead of the global limit (if any)
+    pub fn with_concurrency_limit(mut self, limiter: Arc<Semaphore>) -> Self {
+        self.limiter = Some(limiter);
+        self
     }
     
-    async fn rotate_to_next_url(&self) -> Result<()> {
-        let mut index = self.current_index.write().await;
-        let next_index = (*index + 1) % self.rpc_urls.len();
+    /// Time `getBlock` for `slot` `iterations` times against each URL on its own, one call at a
+    /// time, with the block config extraction uses. Failover, weights and concurrency limits are
+    /// bypassed and the calls don't count towards `endpoint_stats`.
+    pub async fn bench_get_block(&self, slot: u64, iterations: usize) -> Vec<EndpointBenchmark> {
+        let endpoints: Vec<(String, u32)> = {
+            let pool = self.pool.read().unwrap();
+            pool.rpc_urls.iter().cloned().zip(pool.weights.iter().copied()).collect()
+        };
+        let config = RpcBlockConfig {
+            encoding: Some(solana_transaction_status::UiTransactionEncoding::JsonParsed),
+            transaction_details: Some(solana_transaction_status::TransactionDetails::Full),
+            rewards: Some(false),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
         
-        info!(
-            "Rotating RPC URL from {} to {}", 
-            self.rpc_urls[*index], 
-            self.rpc_urls[next_index]
-        );
+        let mut results = Vec::new();
+        for (url, weight) in endpoints {
+            let client = Arc::new(build_rpc_client(&url, Duration::from_secs(30)));
+            let mut benchmark = EndpointBenchmark { url, weight, iterations, errors: 0, latencies: Vec::new() };
+            
+            for _ in 0..iterations {
+                let client = client.clone();
+                let started = Instant::now();
+                let result = tokio::task::spawn_blocking(move || client.get_block_with_config(slot, config)).await;
+                match result {
+                    Ok(Ok(_)) => benchmark.latencies.push(started.elapsed()),
+                    Ok(Err(e)) => {
+                        warn!("Benchmark getBlock {} on {} failed: {}", slot, benchmark.url, e);
+                        benchmark.errors += 1;
+                    },
+                    Err(e) => {
+                        warn!("Benchmark getBlock {} on {} panicked: {}", slot, benchmark.url, e);
+                        benchmark.errors += 1;
+                    },
+                }
+            }
+            
+            benchmark.latencies.sort();
+            results.push(benchmark);
+        }
+        results
+    }
+    
+    /// Per-URL request counts, errors and average latency since creation
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        let pool = self.pool.read().unwrap();
+        pool.rpc_urls.iter()
+            .zip(&pool.weights)
+            .zip(pool.state.iter())
+            .map(|((url, weight), endpoint)| EndpointStats {
+                url: url.clone(),
+                weight: *weight,
+                requests: endpoint.requests,
+                errors: endpoint.errors,
+                avg_latency_ms: if endpoint.requests > 0 {
+                    endpoint.total_latency.as_secs_f64() * 1000.0 / endpoint.requests as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect()
+    }
+    
+    /// Smooth weighted round-robin (as in nginx): spreads picks proportionally to weight without bursts
+    fn select_endpoint(&self) -> usize {
+        self.pick().0
+    }
+    
+    /// The weighted pick along with the URL list it indexes, taken under one lock so a
+    /// concurrent `set_urls` can't shift the indices
+    fn pick(&self) -> (usize, Vec<String>) {
+        let mut pool = self.pool.write().unwrap();
+        let pool = &mut *pool;
+        let total: i64 = pool.weights.iter().map(|w| *w as i64).sum();
         
-        *index = next_index;
-        Ok(())
+        let mut best = 0;
+        for (index, endpoint) in pool.state.iter_mut().enumerate() {
+            endpoint.current_weight += pool.weights[index] as i64;
+        }
+        for index in 1..pool.state.len() {
+            if pool.state[index].current_weight > pool.state[best].current_weight {
+                best = index;
+            }
+        }
+        pool.state[best].current_weight -= total;
+        
+        (best, pool.rpc_urls.clone())
+    }
+    
+    /// Traffic is recorded by URL; calls finishing after their URL was swapped out are dropped
+    fn record(&self, url: &str, latency: Duration, success: bool) {
+        let mut pool = self.pool.write().unwrap();
+        let Some(index) = pool.rpc_urls.iter().position(|existing| existing == url) else {
+            return;
+        };
+        let endpoint = &mut pool.state[index];
+        endpoint.requests += 1;
+        endpoint.total_latency += latency;
+        if !success {
+            endpoint.errors += 1;
+        }
     }
     
+    /// Try the weighted pick first, then every other URL in order until one succeeds
     async fn execute_with_failover<T, F>(&self, operation_name: &str, f: F) -> Result<T>
     where
         F: Fn(&RpcClient) -> Result<T>,
     {
         let mut last_error = None;
-        let total_urls = self.rpc_urls.len();
+        let (start, rpc_urls) = self.pick();
+        let total_urls = rpc_urls.len();
         
         for attempt in 0..total_urls {
-            let client = self.get_current_client().await;
-            let current_url = self.rpc_urls[*self.current_index.read().await].clone();
+            let index = (start + attempt) % total_urls;
+            let current_url = &rpc_urls[index];
+            let client = build_rpc_client(current_url, Duration::from_secs(10));
+            take_rpc_call(operation_name)?;
+            
+            let _permit = match &self.limiter {
+                Some(limiter) => Some(limiter.acquire().await.context("RPC concurrency limiter closed")?),
+                None => None,
+            };
+            
+            let started = Instant::now();
+            let result = f(&client);
+            self.record(current_url, started.elapsed(), result.is_ok());
             
-            match f(&client) {
+            match result {
                 Ok(result) => {
                     if attempt > 0 {
                         info!("Successfully completed {} after {} attempts", operation_name, attempt + 1);
@@ -104,25 +515,23 @@ impl RpcClientWithFailover {
                             operation_name, 
                             error_str
                         );
-                        
-                        // Rotate to next URL
-                        if attempt < total_urls - 1 {
-                            self.rotate_to_next_url().await?;
-                            continue;
-                        }
                     } else {
-                        // For non-429 errors, still try to rotate but log differently
+                        // For non-429 errors, still try the next URL but log differently
                         error!(
                             "RPC error on {} for {}: {}", 
                             current_url, 
                             operation_name, 
                             error_str
                         );
-                        
-                        if attempt < total_urls - 1 {
-                            self.rotate_to_next_url().await?;
-                            continue;
-                        }
+                    }
+                    
+                    if attempt < total_urls - 1 {
+                        info!(
+                            "Failing over {} from {} to {}",
+                            operation_name,
+                            current_url,
+                            rpc_urls[(index + 1) % total_urls]
+                        );
                     }
                     
                     last_error = Some(e);
@@ -138,19 +547,97 @@ impl RpcClientWithFailover {
         slot: u64,
         config: RpcBlockConfig,
     ) -> Result<UiConfirmedBlock> {
+        // A pre-filter may have fetched this block already
+        if let Some(block) = block_cache::shared().take(slot, &config) {
+            debug!("Using cached block for slot {}", slot);
+            return Ok(block);
+        }
         self.execute_with_failover("get_block_with_config", |client| {
             client.get_block_with_config(slot, config)
                 .context(format!("Failed to get block for slot {}", slot))
         }).await
     }
     
+    /// Fetch the blocks of `slots`, `rpc_batch_size()` `getBlock` calls per JSON-RPC batch
+    /// request, yielding each batch's results in `slots` order. The next batch is only fetched
+    /// once the stream is polled again, so callers hold one batch of blocks at a time. Each
+    /// batch counts as one call against the budget and the concurrency limit, and fails over as
+    /// a whole; a batch no URL accepts (some providers reject batches) falls back to one request
+    /// per slot. Per-slot errors such as skipped slots are kept per slot.
+    pub fn get_blocks_batched<'a>(
+        &'a self,
+        slots: &'a [u64],
+        config: RpcBlockConfig,
+    ) -> impl futures::Stream<Item = Vec<(u64, Result<UiConfirmedBlock>)>> + 'a {
+        use futures::StreamExt;
+        
+        let batch_size = rpc_batch_size();
+        // Unbatched, still fetch 20 slots at a time, one request each
+        let chunk_size = if batch_size > 1 { batch_size } else { DEFAULT_RPC_BATCH_SIZE };
+        futures::stream::iter(slots.chunks(chunk_size)).then(move |batch| async move {
+            if batch_size > 1 {
+                match self.get_block_batch(batch, config).await {
+                    Ok(blocks) => return blocks,
+                    Err(e) => warn!(
+                        "Batched getBlock for slots {}..={} failed, fetching one by one: {:#}",
+                        batch[0], batch[batch.len() - 1], e
+                    ),
+                }
+            }
+            let fetches = batch.iter().map(|&slot| async move { (slot, self.get_block_with_config(slot, config).await) });
+            futures::future::join_all(fetches).await
+        })
+    }
+    
+    /// One JSON-RPC batch of `getBlock` calls, with failover across URLs
+    async fn get_block_batch(&self, slots: &[u64], config: RpcBlockConfig) -> Result<Vec<(u64, Result<UiConfirmedBlock>)>> {
+        let params = serde_json::to_value(config).context("Failed to encode block config")?;
+        let requests: Vec<serde_json::Value> = slots.iter()
+            .enumerate()
+            .map(|(id, slot)| serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": "getBlock", "params": [slot, params] }))
+            .collect();
+        
+        let mut last_error = None;
+        let (start, rpc_urls) = self.pick();
+        for attempt in 0..rpc_urls.len() {
+            let url = &rpc_urls[(start + attempt) % rpc_urls.len()];
+            take_rpc_call("get_block_batch")?;
+            let _permit = match &self.limiter {
+                Some(limiter) => Some(limiter.acquire().await.context("RPC concurrency limiter closed")?),
+                None => None,
+            };
+            
+            let started = Instant::now();
+            let result = post_batch(url, &requests).await;
+            self.record(url, started.elapsed(), result.is_ok());
+            match result {
+                Ok(responses) => return Ok(batch_results(slots, responses)),
+                Err(e) => {
+                    warn!("Batched getBlock on {} failed: {:#}", url, e);
+                    last_error = Some(e);
+                },
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No RPC URLs configured")))
+    }
+    
     pub async fn get_slot(&self) -> Result<u64> {
+        if let Some(slot) = self.slot_subscription.as_ref().and_then(|subscription| subscription.latest_slot()) {
+            return Ok(slot);
+        }
         self.execute_with_failover("get_slot", |client| {
             client.get_slot()
                 .context("Failed to get current slot")
         }).await
     }
     
+    pub async fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> Result<u64> {
+        self.execute_with_failover("get_slot_with_commitment", |client| {
+            client.get_slot_with_commitment(commitment)
+                .context("Failed to get current slot")
+        }).await
+    }
+    
     pub async fn get_account(&self, pubkey: &Pubkey) -> Result<solana_sdk::account::Account> {
         self.execute_with_failover("get_account", |client| {
             client.get_account(pubkey)
@@ -158,6 +645,35 @@ impl RpcClientWithFailover {
         }).await
     }
     
+    /// Owner program of each account (None for accounts that don't exist). Account data is
+    /// sliced to zero bytes, so large program-owned accounts cost no more than empty ones.
+    pub async fn get_multiple_account_owners(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Pubkey>>> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig { offset: 0, length: 0 }),
+            ..RpcAccountInfoConfig::default()
+        };
+        
+        self.execute_with_failover("get_multiple_accounts", |client| {
+            let accounts = client.get_multiple_accounts_with_config(pubkeys, config.clone())
+                .context(format!("Failed to get {} accounts", pubkeys.len()))?
+                .value;
+            Ok(accounts.into_iter().map(|account| account.map(|a| a.owner)).collect())
+        }).await
+    }
+    
+    /// Total supply of `mint` and its decimals
+    pub async fn get_token_supply(&self, mint: &Pubkey) -> Result<TokenSupply> {
+        self.execute_with_failover("get_token_supply", |client| {
+            let supply = client.get_token_supply(mint)
+                .context(format!("Failed to get token supply of {}", mint))?;
+            let ui_amount = supply.ui_amount
+                .or_else(|| supply.ui_amount_string.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!("Token supply of {} has no UI amount", mint))?;
+            Ok(TokenSupply { ui_amount, decimals: supply.decimals })
+        }).await
+    }
+    
     pub async fn get_signatures_for_address(
         &self,
         address: &Pubkey,
@@ -188,4 +704,339 @@ impl RpcClientWithFailover {
                 .context("Failed to get version")
         }).await
     }
-}
\ No newline at end of file
+}
+
+/// POST a JSON-RPC batch; a response that isn't an array (e.g. a provider refusing batches)
+/// is an error
+async fn post_batch(url: &str, requests: &[serde_json::Value]) -> Result<Vec<serde_json::Value>> {
+    let response = batch_http_client(url)?
+        .post(url)
+        .json(requests)
+        .send()
+        .await
+        .context("Batch request failed")?
+        .error_for_status()
+        .context("Batch request rejected")?;
+    let body: serde_json::Value = response.json().await.context("Invalid batch response")?;
+    match body {
+        serde_json::Value::Array(responses) => Ok(responses),
+        other => Err(anyhow::anyhow!("Expected a batch response, got {}", other)),
+    }
+}
+
+/// Pair batch responses (in any order) with their slots by request id; a missing response,
+/// a JSON-RPC error or a null result (no block) is an error for that slot
+fn batch_results(slots: &[u64], responses: Vec<serde_json::Value>) -> Vec<(u64, Result<UiConfirmedBlock>)> {
+    let mut by_id: HashMap<u64, serde_json::Value> = responses.into_iter()
+        .filter_map(|response| Some((response.get("id")?.as_u64()?, response)))
+        .collect();
+    
+    slots.iter()
+        .enumerate()
+        .map(|(id, &slot)| {
+            let result = match by_id.remove(&(id as u64)) {
+                None => Err(anyhow::anyhow!("No response for block {} in batch", slot)),
+                Some(mut response) => match response.get("error") {
+                    Some(error) => Err(anyhow::anyhow!(
+                        "Failed to get block for slot {}: {} ({})",
+                        slot,
+                        error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error"),
+                        error.get("code").and_then(|c| c.as_i64()).unwrap_or_default()
+                    )),
+                    None => match response.get_mut("result").map(serde_json::Value::take) {
+                        None | Some(serde_json::Value::Null) => Err(anyhow::anyhow!("No block for slot {}", slot)),
+                        Some(block) => serde_json::from_value(block)
+                            .with_context(|| format!("Failed to decode block for slot {}", slot)),
+                    },
+                },
+            };
+            (slot, result)
+        })
+        .collect()
+}
+
+fn rpc_config_path() -> String {
+    std::env::var("RPC_CONFIG_FILE")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_RPC_CONFIG_FILE.to_string())
+}
+
+/// Keep the endpoints that answer `getVersion` within 5 seconds; an error when none does
+fn probe_endpoints(endpoints: Vec<(String, u32)>) -> Result<Vec<(String, u32)>> {
+    let mut healthy = Vec::new();
+    for (url, weight) in endpoints {
+        let client = build_rpc_client(&url, Duration::from_secs(5));
+        match client.get_version() {
+            Ok(version) => {
+                info!("RPC {} is healthy (solana-core {})", url, version.solana_core);
+                healthy.push((url, weight));
+            },
+            Err(e) => warn!("Not adopting RPC {}: {}", url, e),
+        }
+    }
+    
+    if healthy.is_empty() {
+        return Err(anyhow::anyhow!("None of the new RPC URLs answered; keeping the current ones"));
+    }
+    Ok(healthy)
+}
+
+/// Resolve the endpoints in an RPC config file; Ok(None) when the file doesn't exist. Their
+/// headers are registered for `build_rpc_client` as a side effect.
+fn load_rpc_config(path: &Path) -> Result<Option<Vec<(String, u32)>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    let config: RpcConfigFile = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {:?}", path))?;
+    
+    let mut endpoints = Vec::new();
+    for endpoint in config.endpoints {
+        let url = match (&endpoint.url, &endpoint.url_env) {
+            (Some(url), _) => url.clone(),
+            (None, Some(var)) => match std::env::var(var) {
+                Ok(url) if !url.trim().is_empty() => url,
+                _ => {
+                    warn!("RPC endpoint env var {} is not set; skipping", var);
+                    continue;
+                }
+            },
+            (None, None) => {
+                warn!("RPC endpoint in {:?} has neither url nor url_env; skipping", path);
+                continue;
+            }
+        };
+        
+        let headers = endpoint_headers(&endpoint);
+        let mut registry = RPC_HEADERS.write().unwrap();
+        if headers.is_empty() {
+            registry.remove(&url);
+        } else {
+            registry.insert(url.clone(), headers);
+        }
+        drop(registry);
+        BATCH_HTTP_CLIENTS.lock().unwrap().remove(&url);
+        
+        endpoints.push((url, endpoint.weight));
+    }
+    
+    Ok(Some(endpoints))
+}
+
+/// Literal and env-sourced headers of one endpoint; invalid names or values are skipped
+fn endpoint_headers(endpoint: &RpcEndpointConfig) -> HeaderMap {
+    let env_values = endpoint.headers_env.iter()
+        .filter_map(|(name, var)| match std::env::var(var) {
+            Ok(value) if !value.trim().is_empty() => Some((name.clone(), value)),
+            _ => {
+                warn!("RPC header env var {} is not set; not sending {}", var, name);
+                None
+            }
+        });
+    
+    let mut headers = HeaderMap::new();
+    for (name, value) in endpoint.headers.clone().into_iter().chain(env_values) {
+        match (HeaderName::try_from(name.as_str()), HeaderValue::try_from(value.trim())) {
+            (Ok(header_name), Ok(mut header_value)) => {
+                // Keeps API keys out of debug output
+                header_value.set_sensitive(true);
+                headers.insert(header_name, header_value);
+            },
+            _ => warn!("Skipping invalid RPC header '{}'", name),
+        }
+    }
+    headers
+}
+
+/// Slots more than this apart in time mean the socket went quiet; it is dropped and reopened
+const SLOT_NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Latest rooted slot from a `slotSubscribe` WebSocket, kept by a background task that
+/// reconnects with backoff (1s doubling to 30s). Notifications report the slot being processed
+/// and the node's root; the root is used, matching the default (finalized) commitment of
+/// `get_slot` and of block fetches, so pushed slots always have a fetchable block.
+pub struct SlotSubscription {
+    latest: watch::Receiver<Option<u64>>,
+    connected: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SlotSubscription {
+    pub fn spawn(ws_url: String) -> Self {
+        let (sender, latest) = watch::channel(None);
+        let connected = Arc::new(AtomicBool::new(false));
+        let task = tokio::spawn(run_slot_subscription(ws_url, sender, connected.clone()));
+        Self { latest, connected, task }
+    }
+    
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+    
+    /// Latest pushed slot; None while disconnected
+    pub fn latest_slot(&self) -> Option<u64> {
+        if !self.is_connected() {
+            return None;
+        }
+        *self.latest.borrow()
+    }
+    
+    pub async fn wait_for_slot_after(&self, slot: u64, timeout: Duration) {
+        let mut latest = self.latest.clone();
+        let _ = tokio::time::timeout(timeout, latest.wait_for(|latest| latest.is_some_and(|latest| latest > slot))).await;
+    }
+}
+
+impl Drop for SlotSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run_slot_subscription(ws_url: String, sender: watch::Sender<Option<u64>>, connected: Arc<AtomicBool>) {
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        match follow_slots(&ws_url, &sender, &connected).await {
+            Ok(()) => warn!("Slot subscription closed by {}; polling until it reconnects", ws_url),
+            Err(e) => warn!("Slot subscription to {} failed: {:#}; polling until it reconnects", ws_url, e),
+        }
+        if connected.swap(false, Ordering::Relaxed) {
+            backoff = Duration::from_secs(1);
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(Duration::from_secs(30));
+    }
+}
+
+async fn follow_slots(ws_url: &str, sender: &watch::Sender<Option<u64>>, connected: &AtomicBool) -> Result<()> {
+    use futures::{SinkExt, StreamExt};
+    
+    // tungstenite answers pings by itself while the stream is read
+    use tokio_tungstenite::tungstenite::Message;
+    
+    let (mut socket, _) = tokio_tungstenite::connect_async(ws_url).await
+        .with_context(|| format!("Failed to connect to {}", ws_url))?;
+    let request = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "slotSubscribe" });
+    socket.send(Message::Text(request.to_string())).await
+        .context("Failed to send slotSubscribe")?;
+    
+    loop {
+        let message = tokio::time::timeout(SLOT_NOTIFICATION_TIMEOUT, socket.next()).await
+            .map_err(|_| anyhow::anyhow!("No slot notification for {}s", SLOT_NOTIFICATION_TIMEOUT.as_secs()))?;
+        let Some(message) = message else {
+            return Ok(());
+        };
+        match message.context("WebSocket error")? {
+            Message::Text(text) => {
+                if let Some(root) = parse_slot_notification(&text) {
+                    if !connected.swap(true, Ordering::Relaxed) {
+                        info!("Slot subscription to {} is live", ws_url);
+                    }
+                    // Keep the highest root; notifications from forks can arrive out of order
+                    sender.send_if_modified(|latest| {
+                        let newer = latest.is_none_or(|latest| root > latest);
+                        if newer {
+                            *latest = Some(root);
+                        }
+                        newer
+                    });
+                }
+            },
+            Message::Close(_) => return Ok(()),
+            _ => {},
+        }
+    }
+}
+
+/// The root slot of a `slotNotification` message; None for anything else (e.g. the
+/// subscription id reply)
+fn parse_slot_notification(text: &str) -> Option<u64> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    if value["method"] != "slotNotification" {
+        return None;
+    }
+    value["params"]["result"]["root"].as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_batch_results_match_responses_by_id() {
+        let responses = vec![
+            serde_json::json!({ "jsonrpc": "2.0", "id": 2, "result": null }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": 0, "error": { "code": -32007, "message": "Slot 10 was skipped" } }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": {
+                "previousBlockhash": "11111111111111111111111111111111", "blockhash": "11111111111111111111111111111111",
+                "parentSlot": 10, "transactions": [], "blockTime": null, "blockHeight": null
+            } }),
+        ];
+        
+        let results = batch_results(&[10, 11, 12, 13], responses);
+        assert_eq!(results.iter().map(|(slot, _)| *slot).collect::<Vec<_>>(), vec![10, 11, 12, 13]);
+        assert!(results[0].1.as_ref().unwrap_err().to_string().contains("skipped (-32007)"));
+        assert_eq!(results[1].1.as_ref().unwrap().parent_slot, 10);
+        assert!(results[2].1.is_err(), "null result means no block");
+        assert!(results[3].1.is_err(), "missing response");
+    }
+    
+    #[test]
+    fn test_weighted_selection_is_proportional() {
+        let client = RpcClientWithFailover::from_endpoints(vec![
+            ("https://fast.example".to_string(), 3),
+            ("https://cheap.example".to_string(), 1),
+            ("https://backup.example".to_string(), 0),
+        ]);
+        
+        let mut counts = [0; 3];
+        for _ in 0..40 {
+            counts[client.select_endpoint()] += 1;
+        }
+        assert_eq!(counts, [30, 10, 0]);
+    }
+    
+    #[test]
+    fn test_all_zero_weights_fall_back_to_round_robin() {
+        let client = RpcClientWithFailover::from_endpoints(vec![
+            ("https://a.example".to_string(), 0),
+            ("https://b.example".to_string(), 0),
+            ("https://a.example".to_string(), 5),
+        ]);
+        
+        assert_eq!(client.pool.read().unwrap().weights, vec![1, 1]);
+        let picks: Vec<usize> = (0..4).map(|_| client.select_endpoint()).collect();
+        assert_eq!(picks, vec![0, 1, 0, 1]);
+    }
+    
+    #[test]
+    fn test_benchmark_percentiles_use_nearest_rank() {
+        let benchmark = EndpointBenchmark {
+            url: "https://a.example".to_string(),
+            weight: 1,
+            iterations: 22,
+            errors: 2,
+            latencies: (1..=20).map(Duration::from_millis).collect(),
+        };
+        
+        assert_eq!(benchmark.min(), Some(Duration::from_millis(1)));
+        assert_eq!(benchmark.median(), Some(Duration::from_millis(10)));
+        assert_eq!(benchmark.p95(), Some(Duration::from_millis(19)));
+        assert!((benchmark.error_rate() - 2.0 / 22.0).abs() < 1e-9);
+        
+        let failed = EndpointBenchmark { latencies: Vec::new(), ..benchmark };
+        assert_eq!(failed.p95(), None);
+    }
+    
+    #[test]
+    fn test_slot_notification_reports_root() {
+        let notification = r#"{"jsonrpc":"2.0","method":"slotNotification","params":{"result":{"parent":75,"root":44,"slot":76},"subscription":0}}"#;
+        assert_eq!(parse_slot_notification(notification), Some(44));
+        assert_eq!(parse_slot_notification(r#"{"jsonrpc":"2.0","result":0,"id":1}"#), None);
+        assert_eq!(parse_slot_notification("not json"), None);
+    }
+}
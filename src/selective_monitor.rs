@@ -118,6 +118,11 @@ impl SelectiveMonitor {
                                 required_programs.insert(program_id.to_string());
                             }
                         }
+                        if condition.get("type").and_then(|v| v.as_str()) == Some("ProgramInvokedAny") {
+                            if let Some(program_ids) = condition.get("program_ids").and_then(|v| v.as_array()) {
+                                required_programs.extend(program_ids.iter().filter_map(|v| v.as_str()).map(String::from));
+                            }
+                        }
                     }
                 }
                 
@@ -137,6 +142,11 @@ impl SelectiveMonitor {
                                 required_programs.insert(program_id.to_string());
                             }
                         }
+                        if condition.get("type").and_then(|v| v.as_str()) == Some("ProgramInvokedAny") {
+                            if let Some(program_ids) = condition.get("program_ids").and_then(|v| v.as_array()) {
+                                required_programs.extend(program_ids.iter().filter_map(|v| v.as_str()).map(String::from));
+                            }
+                        }
                     }
                 }
             }
@@ -3,6 +3,7 @@ use reqwest;
 use serde::{Deserialize, Serialize};
 use std::env;
 use tracing::{info, error};
+use crate::notifier_retry::post_json_with_retry;
 
 #[derive(Debug, Clone)]
 pub struct SlackNotifier {
@@ -95,12 +96,9 @@ impl SlackNotifier {
         }
     }
     
+    #[tracing::instrument(name = "notify", skip_all, fields(channel = "slack"))]
     pub async fn send_message(&self, message: SlackMessage) -> Result<()> {
-        let response = self.client
-            .post(&self.webhook_url)
-            .json(&message)
-            .send()
-            .await?;
+        let response = post_json_with_retry(&self.client, &self.webhook_url, &message, "Slack").await?;
         
         if response.status().is_success() {
             info!("Slack notification sent successfully");
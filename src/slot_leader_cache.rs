@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::rpc_client_with_failover::RpcClientWithFailover;
+
+/// Leaders fetched per `getSlotLeaders` call; the monitor mostly walks forward, so one call
+/// usually covers the next matched slots too
+const LEADER_BATCH_SIZE: u64 = 500;
+/// Oldest slots are dropped beyond this many cached leaders (~100 bytes each)
+const MAX_CACHED_LEADERS: usize = 20_000;
+
+/// Slot → leader identity, fetched in batches and cached per slot
+pub struct SlotLeaderCache {
+    rpc_client: RpcClientWithFailover,
+    leaders: Mutex<BTreeMap<u64, String>>,
+}
+
+impl SlotLeaderCache {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_client: RpcClientWithFailover::new(rpc_url),
+            leaders: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Leader of `slot`, or None when the RPC node can't resolve it (e.g. outside the
+    /// leader schedule it keeps). Failures are logged and never fail the slot.
+    pub async fn leader_for(&self, slot: u64) -> Option<String> {
+        let mut leaders = self.leaders.lock().await;
+        if let Some(leader) = leaders.get(&slot) {
+            return Some(leader.clone());
+        }
+
+        debug!("Fetching {} slot leaders starting at {}", LEADER_BATCH_SIZE, slot);
+        match self.rpc_client.get_slot_leaders(slot, LEADER_BATCH_SIZE).await {
+            Ok(batch) => {
+                for (offset, leader) in batch.iter().enumerate() {
+                    leaders.insert(slot + offset as u64, leader.to_string());
+                }
+                while leaders.len() > MAX_CACHED_LEADERS {
+                    leaders.pop_first();
+                }
+                leaders.get(&slot).cloned()
+            },
+            Err(e) => {
+                warn!("Could not fetch the leader of slot {}: {:#}", slot, e);
+                None
+            }
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::{Result, Context};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use solana_client::rpc_config::RpcBlockConfig;
-use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedTransaction, TransactionDetails, UiConfirmedBlock, UiMessage, UiTransactionEncoding};
 use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::block_cache;
 use crate::rpc_client_with_failover::RpcClientWithFailover;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -153,117 +156,36 @@ impl SlotPreFilter {
         let mut total_txs_scanned = 0u64;
         let mut total_blocks_with_txs = 0u64;
         
-        // Process in smaller batches to avoid overwhelming the RPC
-        for (chunk_idx, chunk) in slots.chunks(20).enumerate() {
-            let mut handles = vec![];
-            let is_first_chunk = chunk_idx == 0;
-            let first_slot_in_chunk = chunk.first().copied().unwrap_or(0);
-            
-            for &slot in chunk {
-                let rpc_client = self.rpc_client.clone();
-                let monitored = self.monitored_addresses.clone();
-                
-                let handle = tokio::spawn(async move {
-                    let config = RpcBlockConfig {
-                        encoding: Some(UiTransactionEncoding::JsonParsed),
-                        transaction_details: Some(TransactionDetails::Full),
-                        rewards: Some(false),
-                        commitment: None,
-                        max_supported_transaction_version: Some(0),
-                    };
-                    
-                    match rpc_client.get_block_with_config(slot, config).await {
-                        Ok(block) => {
-                            if let Some(transactions) = block.transactions {
-                                let tx_count = transactions.len();
-                                if tx_count > 0 {
-                                    // Log first slot with transactions for debugging
-                                    if is_first_chunk && slot == first_slot_in_chunk {
-                                        info!("Slot {} has {} transactions, checking for monitored addresses...", slot, tx_count);
-                                    }
-                                }
-                                
-                                for tx in transactions {
-                                    // Check transaction metadata for token accounts
-                                    if let Some(meta) = &tx.meta {
-                                        // Check pre/post token balances for monitored tokens
-                                        match &meta.pre_token_balances {
-                                            solana_transaction_status::option_serializer::OptionSerializer::Some(balances) => {
-                                                for balance in balances {
-                                                    // Log token mints seen in first few slots
-                                                    if is_first_chunk && balance.ui_token_amount.ui_amount.unwrap_or(0.0) > 0.0 {
-                                                        debug!("Token mint in slot {}: {} (amount: {})", 
-                                                            slot, balance.mint, balance.ui_token_amount.ui_amount.unwrap_or(0.0));
-                                                    }
-                                                    if monitored.contains(&balance.mint) {
-                                                        info!("✅ Found monitored token {} in slot {} (pre-balance)", balance.mint, slot);
-                                                        return (slot, true, tx_count);
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                        match &meta.post_token_balances {
-                                            solana_transaction_status::option_serializer::OptionSerializer::Some(balances) => {
-                                                for balance in balances {
-                                                    if monitored.contains(&balance.mint) {
-                                                        info!("✅ Found monitored token {} in slot {} (post-balance)", balance.mint, slot);
-                                                        return (slot, true, tx_count);
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                    
-                                    // Check account keys in transaction
-                                    match &tx.transaction {
-                                        solana_transaction_status::EncodedTransaction::Json(json_tx) => {
-                                            match &json_tx.message {
-                                                solana_transaction_status::UiMessage::Parsed(parsed) => {
-                                                    for account in &parsed.account_keys {
-                                                        if monitored.contains(&account.pubkey) {
-                                                            info!("✅ Found monitored program {} in slot {} (parsed)", account.pubkey, slot);
-                                                            return (slot, true, tx_count);
-                                                        }
-                                                    }
-                                                }
-                                                solana_transaction_status::UiMessage::Raw(raw) => {
-                                                    for key in &raw.account_keys {
-                                                        if monitored.contains(key) {
-                                                            info!("✅ Found monitored program {} in slot {} (raw)", key, slot);
-                                                            return (slot, true, tx_count);
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        _ => continue,
-                                    }
-                                }
-                            }
-                            (slot, false, 0)
+        let config = RpcBlockConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            transaction_details: Some(TransactionDetails::Full),
+            rewards: Some(false),
+            commitment: None,
+            max_supported_transaction_version: Some(0),
+        };
+        
+        // Blocks come in JSON-RPC batches, many getBlock calls per request; each batch is
+        // scanned and dropped before the next one is fetched
+        let mut batches = std::pin::pin!(self.rpc_client.get_blocks_batched(&slots, config));
+        let mut scanned = 0;
+        while let Some(batch) = batches.next().await {
+            for (slot, block) in batch {
+                scanned += 1;
+                match block {
+                    Ok(block) => {
+                        let tx_count = block.transactions.as_ref().map_or(0, Vec::len);
+                        if tx_count > 0 {
+                            total_blocks_with_txs += 1;
+                            total_txs_scanned += tx_count as u64;
+                        }
+                        // Log details for the first few slots for debugging
+                        if self.block_is_relevant(slot, &block, scanned <= 20) {
+                            relevant_slots.push(slot);
+                            // Extraction fetches with the same config and takes the block from here
+                            block_cache::shared().insert(slot, config, block);
                         }
-                        Err(e) => {
-                            debug!("Failed to fetch slot {}: {}", slot, e);
-                            (slot, false, 0)
-                        },
-                    }
-                });
-                
-                handles.push(handle);
-            }
-            
-            // Collect results
-            for handle in handles {
-                if let Ok((slot, is_relevant, tx_count)) = handle.await {
-                    if tx_count > 0 {
-                        total_blocks_with_txs += 1;
-                        total_txs_scanned += tx_count as u64;
-                    }
-                    if is_relevant {
-                        relevant_slots.push(slot);
                     }
+                    Err(e) => debug!("Failed to fetch slot {}: {}", slot, e),
                 }
             }
         }
@@ -293,4 +215,65 @@ impl SlotPreFilter {
         
         Ok(relevant_slots)
     }
+    
+    /// Whether any transaction in `block` touches a monitored mint or account
+    fn block_is_relevant(&self, slot: u64, block: &UiConfirmedBlock, log_mints: bool) -> bool {
+        let Some(transactions) = &block.transactions else {
+            return false;
+        };
+        if log_mints && !transactions.is_empty() {
+            debug!("Slot {} has {} transactions, checking for monitored addresses...", slot, transactions.len());
+        }
+        
+        for tx in transactions {
+            // Check transaction metadata for token accounts
+            if let Some(meta) = &tx.meta {
+                // Check pre/post token balances for monitored tokens
+                if let OptionSerializer::Some(balances) = &meta.pre_token_balances {
+                    for balance in balances {
+                        // Log token mints seen in first few slots
+                        if log_mints && balance.ui_token_amount.ui_amount.unwrap_or(0.0) > 0.0 {
+                            debug!("Token mint in slot {}: {} (amount: {})",
+                                slot, balance.mint, balance.ui_token_amount.ui_amount.unwrap_or(0.0));
+                        }
+                        if self.monitored_addresses.contains(&balance.mint) {
+                            debug!("✅ Found monitored token {} in slot {} (pre-balance)", balance.mint, slot);
+                            return true;
+                        }
+                    }
+                }
+                if let OptionSerializer::Some(balances) = &meta.post_token_balances {
+                    for balance in balances {
+                        if self.monitored_addresses.contains(&balance.mint) {
+                            debug!("✅ Found monitored token {} in slot {} (post-balance)", balance.mint, slot);
+                            return true;
+                        }
+                    }
+                }
+            }
+            
+            // Check account keys in transaction
+            if let EncodedTransaction::Json(json_tx) = &tx.transaction {
+                match &json_tx.message {
+                    UiMessage::Parsed(parsed) => {
+                        for account in &parsed.account_keys {
+                            if self.monitored_addresses.contains(&account.pubkey) {
+                                debug!("✅ Found monitored program {} in slot {} (parsed)", account.pubkey, slot);
+                                return true;
+                            }
+                        }
+                    }
+                    UiMessage::Raw(raw) => {
+                        for key in &raw.account_keys {
+                            if self.monitored_addresses.contains(key) {
+                                debug!("✅ Found monitored program {} in slot {} (raw)", key, slot);
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        false
+    }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use anyhow::{Result, Context};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::{Read, Write};
+
+/// First two bytes of every gzip stream; plain JSON blobs start with `{` or `[`
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Serializes stored transactions to JSON blobs for persistent backends, optionally gzip
+/// compressed. Reading detects the format, so blobs written before compression was turned on
+/// (or with a different level) stay readable.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StorageCodec {
+    level: Option<u32>,
+}
+
+impl StorageCodec {
+    /// `None` stores plain JSON; `Some(1..=9)` is the gzip level
+    pub fn new(level: Option<u32>) -> Self {
+        Self { level }
+    }
+
+    /// Reads `STORAGE_COMPRESSION_LEVEL` (1-9). Unset, empty or 0 keeps compression off.
+    pub fn from_env() -> Result<Self> {
+        let Some(value) = std::env::var("STORAGE_COMPRESSION_LEVEL").ok().filter(|v| !v.trim().is_empty()) else {
+            return Ok(Self::default());
+        };
+
+        let level = value.trim().parse::<u32>()
+            .with_context(|| format!("Invalid STORAGE_COMPRESSION_LEVEL '{}': expected 0-9", value))?;
+        match level {
+            0 => Ok(Self::default()),
+            1..=9 => Ok(Self::new(Some(level))),
+            _ => Err(anyhow::anyhow!("Invalid STORAGE_COMPRESSION_LEVEL {}: expected 0-9", level)),
+        }
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.level.is_some()
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(value).context("Failed to serialize stored blob")?;
+        let Some(level) = self.level else {
+            return Ok(json);
+        };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
+        encoder.write_all(&json).context("Failed to compress stored blob")?;
+        encoder.finish().context("Failed to compress stored blob")
+    }
+
+    pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            let mut json = Vec::new();
+            GzDecoder::new(bytes).read_to_end(&mut json).context("Failed to decompress stored blob")?;
+            serde_json::from_slice(&json).context("Failed to parse stored blob")
+        } else {
+            serde_json::from_slice(bytes).context("Failed to parse stored blob")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_reads_plain_and_compressed_blobs() {
+        let value = serde_json::json!({ "signature": "abc", "logs": vec!["Program log: hello"; 50] });
+
+        let plain = StorageCodec::new(None).encode(&value).unwrap();
+        let compressed = StorageCodec::new(Some(6)).encode(&value).unwrap();
+
+        assert!(compressed.starts_with(&GZIP_MAGIC));
+        assert!(compressed.len() < plain.len());
+        assert_eq!(StorageCodec::decode::<serde_json::Value>(&plain).unwrap(), value);
+        assert_eq!(StorageCodec::decode::<serde_json::Value>(&compressed).unwrap(), value);
+    }
+}
@@ -1,9 +1,13 @@
 use anyhow::{Result, Context};
 use reqwest;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use tracing::{info, warn, error};
 use crate::transaction_extractor::ExtractedTransaction;
 use crate::filter_engine::{AlertSeverity, MatchedFilter};
+use crate::notifier_retry::post_json_with_retry;
 
 #[derive(Debug, Clone)]
 pub struct TelegramNotifier {
@@ -11,11 +15,30 @@ pub struct TelegramNotifier {
     chat_id: String,
     client: reqwest::Client,
     base_url: String,
+    /// Forum topic for messages without a severity-specific thread
+    message_thread_id: Option<i64>,
+    /// Per-severity topic overrides, e.g. Critical alerts into a dedicated thread
+    severity_thread_ids: HashMap<AlertSeverity, i64>,
+    /// Shared by clones, so every copy of the notifier stops once the channel is disabled
+    health: Arc<ChannelHealth>,
+}
+
+/// Consecutive failures that resending can't fix (wrong chat id, revoked token, bot removed
+/// from the chat). After `max_hard_failures` of them the channel is disabled for the rest of
+/// the run instead of failing every alert.
+#[derive(Debug)]
+struct ChannelHealth {
+    consecutive_hard_failures: AtomicU32,
+    disabled: AtomicBool,
+    max_hard_failures: u32,
+    console_fallback: bool,
 }
 
 #[derive(Debug, Serialize)]
 struct SendMessageRequest {
     chat_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_thread_id: Option<i64>,
     text: String,
     parse_mode: String,
     disable_web_page_preview: bool,
@@ -24,6 +47,8 @@ struct SendMessageRequest {
 #[derive(Debug, Deserialize)]
 struct TelegramResponse {
     ok: bool,
+    #[serde(default)]
+    error_code: Option<u16>,
     description: Option<String>,
 }
 
@@ -37,9 +62,62 @@ impl TelegramNotifier {
             chat_id,
             client,
             base_url,
+            message_thread_id: None,
+            severity_thread_ids: HashMap::new(),
+            health: Arc::new(ChannelHealth {
+                consecutive_hard_failures: AtomicU32::new(0),
+                disabled: AtomicBool::new(false),
+                max_hard_failures: std::env::var("TELEGRAM_MAX_HARD_FAILURES")
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .filter(|max| *max > 0)
+                    .unwrap_or(3),
+                console_fallback: std::env::var("TELEGRAM_CONSOLE_FALLBACK")
+                    .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false" | "no"))
+                    .unwrap_or(true),
+            }),
         }
     }
     
+    /// True once repeated chat/auth errors have switched the channel off
+    pub fn is_disabled(&self) -> bool {
+        self.health.disabled.load(Ordering::Relaxed)
+    }
+    
+    /// Read topic routing from `TELEGRAM_THREAD_ID` and `TELEGRAM_THREAD_ID_<SEVERITY>`
+    /// (LOW, MEDIUM, HIGH, CRITICAL). Unparseable values are ignored with a warning.
+    pub fn with_threads_from_env(mut self) -> Self {
+        self.message_thread_id = parse_thread_env("TELEGRAM_THREAD_ID");
+        
+        for (suffix, severity) in [
+            ("LOW", AlertSeverity::Low),
+            ("MEDIUM", AlertSeverity::Medium),
+            ("HIGH", AlertSeverity::High),
+            ("CRITICAL", AlertSeverity::Critical),
+        ] {
+            if let Some(thread_id) = parse_thread_env(&format!("TELEGRAM_THREAD_ID_{}", suffix)) {
+                self.severity_thread_ids.insert(severity, thread_id);
+            }
+        }
+        
+        self
+    }
+    
+    pub fn with_thread_id(mut self, thread_id: Option<i64>) -> Self {
+        self.message_thread_id = thread_id;
+        self
+    }
+    
+    pub fn with_severity_thread(mut self, severity: AlertSeverity, thread_id: i64) -> Self {
+        self.severity_thread_ids.insert(severity, thread_id);
+        self
+    }
+    
+    /// Topic for a severity, falling back to the default thread
+    fn thread_for(&self, severity: &AlertSeverity) -> Option<i64> {
+        self.severity_thread_ids.get(severity).copied().or(self.message_thread_id)
+    }
+    
     pub async fn send_alert(
         &self,
         transaction: &ExtractedTransaction,
@@ -47,7 +125,7 @@ impl TelegramNotifier {
         severity: &AlertSeverity,
     ) -> Result<()> {
         let message = self.format_alert_message(transaction, matched_filter, severity);
-        self.send_message(&message).await
+        self.send_to_thread(&message, self.thread_for(severity)).await
     }
     
     pub async fn send_custom_message(&self, title: &str, body: &str) -> Result<()> {
@@ -55,22 +133,40 @@ impl TelegramNotifier {
         self.send_message(&full_message).await
     }
     
+    /// Templated alert, routed to the severity's thread like `send_alert`
+    pub async fn send_custom_alert(&self, title: &str, body: &str, severity: &AlertSeverity) -> Result<()> {
+        let full_message = format!("<b>{}</b>\n\n{}", title, body);
+        self.send_to_thread(&full_message, self.thread_for(severity)).await
+    }
+    
     pub async fn send_message(&self, text: &str) -> Result<()> {
+        self.send_to_thread(text, self.message_thread_id).await
+    }
+    
+    #[tracing::instrument(name = "notify", skip_all, fields(channel = "telegram"))]
+    async fn send_to_thread(&self, text: &str, message_thread_id: Option<i64>) -> Result<()> {
+        if self.is_disabled() {
+            if self.health.console_fallback {
+                println!("[telegram disabled] {}", text);
+                return Ok(());
+            }
+            return Err(anyhow::anyhow!("Telegram channel disabled after repeated chat/auth errors"));
+        }
+        
         let url = format!("{}/sendMessage", self.base_url);
         
         let request = SendMessageRequest {
             chat_id: self.chat_id.clone(),
+            message_thread_id,
             text: text.to_string(),
             parse_mode: "HTML".to_string(),
             disable_web_page_preview: true,
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
+        let response = post_json_with_retry(&self.client, &url, &request, "Telegram")
             .await
             .context("Failed to send Telegram message")?;
+        let status = response.status().as_u16();
         
         let telegram_response: TelegramResponse = response
             .json()
@@ -80,13 +176,34 @@ impl TelegramNotifier {
         if !telegram_response.ok {
             let error_msg = telegram_response.description
                 .unwrap_or_else(|| "Unknown error".to_string());
+            if is_hard_failure(telegram_response.error_code.unwrap_or(status), &error_msg) {
+                self.record_hard_failure(&error_msg);
+            }
             return Err(anyhow::anyhow!("Telegram API error: {}", error_msg));
         }
         
+        self.health.consecutive_hard_failures.store(0, Ordering::Relaxed);
         info!("Successfully sent Telegram notification");
         Ok(())
     }
     
+    fn record_hard_failure(&self, error_msg: &str) {
+        let failures = self.health.consecutive_hard_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures < self.health.max_hard_failures {
+            warn!("Telegram rejected the message ({}/{} before disabling): {}", failures, self.health.max_hard_failures, error_msg);
+            return;
+        }
+        
+        if !self.health.disabled.swap(true, Ordering::Relaxed) {
+            error!(
+                "🚫 TELEGRAM DISABLED after {} consecutive failures ({}). Check TELEGRAM_BOT_TOKEN and TELEGRAM_CHAT_ID; {}",
+                failures,
+                error_msg,
+                if self.health.console_fallback { "alerts are printed to the console instead" } else { "alerts are dropped" }
+            );
+        }
+    }
+    
     fn format_alert_message(
         &self,
         transaction: &ExtractedTransaction,
@@ -118,14 +235,21 @@ impl TelegramNotifier {
             "• Slot: {}\n",
             transaction.slot
         ));
+        if let Some(leader) = &transaction.slot_leader {
+            message.push_str(&format!("• Leader: <code>{}</code>\n", leader));
+        }
         message.push_str(&format!(
             "• Status: {}\n",
             if transaction.success { "✅ Success" } else { "❌ Failed" }
         ));
         message.push_str(&format!(
-            "• Fee: {} SOL\n\n",
+            "• Fee: {} SOL\n",
             transaction.fee as f64 / 1_000_000_000.0
         ));
+        for (key, value) in &matched_filter.context {
+            message.push_str(&format!("• {}: <code>{}</code>\n", html_escape(key), html_escape(value)));
+        }
+        message.push('\n');
         
         // Token balance changes
         if !transaction.token_balance_changes.is_empty() {
@@ -133,9 +257,11 @@ impl TelegramNotifier {
             for change in &transaction.token_balance_changes {
                 if change.change.abs() > 0.0 {
                     let direction = if change.change > 0.0 { "+" } else { "" };
+                    // Whole-number tokens (0 decimals) print without a fraction
+                    let precision = change.after.decimals.min(2) as usize;
                     message.push_str(&format!(
-                        "• {}{:.2} tokens\n",
-                        direction, change.change
+                        "• {}{:.*} tokens\n",
+                        direction, precision, change.change
                     ));
                     
                     // Add mint address for context
@@ -179,6 +305,32 @@ impl TelegramNotifier {
     }
 }
 
+fn parse_thread_env(name: &str) -> Option<i64> {
+    let value = std::env::var(name).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    match value.parse() {
+        Ok(thread_id) => Some(thread_id),
+        Err(_) => {
+            warn!("Ignoring {}: '{}' is not a valid thread id", name, value);
+            None
+        }
+    }
+}
+
+/// Errors that resending can't fix: bad token (401), bot blocked or removed (403) or a chat
+/// that doesn't exist (400 "chat not found"). Rate limits, 5xx and other 400s (e.g. malformed
+/// HTML in one message) don't count.
+fn is_hard_failure(error_code: u16, description: &str) -> bool {
+    match error_code {
+        401 | 403 => true,
+        400 => description.to_lowercase().contains("chat not found"),
+        _ => false,
+    }
+}
+
 // HTML escape function to prevent injection
 fn html_escape(text: &str) -> String {
     text.replace('&', "&amp;")
@@ -208,6 +360,8 @@ pub fn print_telegram_setup_instructions() {
 3. Set environment variables:
    TELEGRAM_BOT_TOKEN=your_bot_token_here
    TELEGRAM_CHAT_ID=your_chat_id_here
+   TELEGRAM_THREAD_ID=topic_id            (optional, for groups with topics)
+   TELEGRAM_THREAD_ID_CRITICAL=topic_id   (optional, per severity: LOW/MEDIUM/HIGH/CRITICAL)
 
 4. Test the connection:
    The bot will send a test message on startup
@@ -218,6 +372,15 @@ pub fn print_telegram_setup_instructions() {
 mod tests {
     use super::*;
     
+    #[test]
+    fn test_hard_failures() {
+        assert!(is_hard_failure(400, "Bad Request: chat not found"));
+        assert!(is_hard_failure(401, "Unauthorized"));
+        assert!(is_hard_failure(403, "Forbidden: bot was kicked from the group chat"));
+        assert!(!is_hard_failure(400, "Bad Request: can't parse entities"));
+        assert!(!is_hard_failure(429, "Too Many Requests: retry after 5"));
+    }
+    
     #[test]
     fn test_html_escape() {
         assert_eq!(html_escape("Test <script>"), "Test &lt;script&gt;");
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
+
+/// Keeps the OTLP exporter alive; dropping it flushes spans that are still batched
+#[must_use = "spans are only flushed when the guard is dropped"]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush OpenTelemetry spans: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global tracing subscriber: formatted logs, plus span export over OTLP/gRPC when
+/// built with the `otel` feature and `OTEL_EXPORTER_OTLP_ENDPOINT` is set. `env_filter` applies
+/// to both, so spans below the log level (e.g. the debug-level `filter_evaluate`) aren't exported.
+pub fn init_tracing(env_filter: EnvFilter) -> Result<TelemetryGuard> {
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .ok()
+        .filter(|endpoint| !endpoint.trim().is_empty());
+
+    #[cfg(feature = "otel")]
+    {
+        let (otel_layer, provider) = match endpoint {
+            Some(endpoint) => {
+                let (layer, provider) = otel_layer(&endpoint)?;
+                (Some(layer), Some(provider))
+            },
+            None => (None, None),
+        };
+
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(tracing_subscriber::fmt::layer())
+            .with(otel_layer)
+            .init();
+
+        if provider.is_some() {
+            tracing::info!("Exporting traces over OTLP");
+        }
+        Ok(TelemetryGuard { provider })
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(tracing_subscriber::fmt::layer())
+            .init();
+
+        if endpoint.is_some() {
+            tracing::warn!("OTEL_EXPORTER_OTLP_ENDPOINT is set, but this build lacks the `otel` feature; traces are not exported");
+        }
+        Ok(TelemetryGuard {})
+    }
+}
+
+#[cfg(feature = "otel")]
+fn otel_layer<S>(endpoint: &str) -> Result<(
+    tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>,
+    opentelemetry_sdk::trace::SdkTracerProvider,
+)>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use anyhow::Context;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .with_context(|| format!("Failed to create OTLP exporter for {}", endpoint))?;
+
+    let service_name = std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "yala-svm-monitor".to_string());
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(opentelemetry_sdk::Resource::builder().with_service_name(service_name).build())
+        .build();
+
+    let tracer = provider.tracer("index_cli");
+    Ok((tracing_opentelemetry::layer().with_tracer(tracer), provider))
+}
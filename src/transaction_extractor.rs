@@ -3,6 +3,7 @@ use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     pubkey::Pubkey,
     signature::Signature,
+    transaction::TransactionVersion,
 };
 use solana_transaction_status::{
     UiTransactionEncoding,
@@ -11,17 +12,21 @@ use solana_transaction_status::{
     UiMessage,
     UiParsedMessage,
     UiCompiledInstruction,
+    UiAddressTableLookup,
     UiInstruction,
     UiParsedInstruction,
     UiPartiallyDecodedInstruction,
     UiAccountsList,
     UiTransactionTokenBalance,
+    UiConfirmedBlock,
+    Reward,
     option_serializer::OptionSerializer,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{info, warn, error, debug};
 use crate::rpc_client_with_failover::RpcClientWithFailover;
+use solana_commitment_config::CommitmentConfig;
 use std::sync::Arc;
 
 /// Comprehensive transaction data structure capturing all available information
@@ -51,8 +56,10 @@ pub struct ExtractedTransaction {
     pub post_balances: Vec<u64>,
     pub balance_changes: HashMap<String, BalanceChange>,
     
-    // Token Balances
+    // Token Balances (left empty in lean extraction mode; token_balance_changes is always kept)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub pre_token_balances: Vec<TokenBalance>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub post_token_balances: Vec<TokenBalance>,
     pub token_balance_changes: Vec<TokenBalanceChange>,
     
@@ -71,6 +78,55 @@ pub struct ExtractedTransaction {
     pub version: String,
     pub recent_blockhash: String,
     pub loaded_addresses: LoadedAddresses,
+    
+    /// Heuristic serialized size in bytes (see `estimate_transaction_size`)
+    #[serde(default)]
+    pub approx_size_bytes: usize,
+    
+    /// Validator that produced the slot; only filled in for matched transactions when leader
+    /// attribution is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot_leader: Option<String>,
+    
+    /// Owner program of each writable account; only resolved when a filter uses `AccountOwnedBy`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub account_owners: HashMap<String, String>,
+    
+    /// Current total supply of the mints it touches; only resolved when a filter uses
+    /// `SupplyPercentage`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mint_supplies: HashMap<String, f64>,
+    
+    /// Rewards credited in the transaction's block (fees to the leader, rent, staking and
+    /// voting), shared by every transaction of the block. Only captured in rewards mode.
+    #[serde(default, skip_serializing_if = "<[BlockReward]>::is_empty")]
+    pub block_rewards: Arc<[BlockReward]>,
+}
+
+/// One reward of a block, as reported by `getBlock` with `rewards: true`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockReward {
+    pub pubkey: String,
+    /// Negative for rent collected from the account
+    pub lamports: i64,
+    pub post_balance: u64,
+    /// `fee`, `rent`, `staking` or `voting`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commission: Option<u8>,
+}
+
+impl From<Reward> for BlockReward {
+    fn from(reward: Reward) -> Self {
+        Self {
+            pubkey: reward.pubkey,
+            lamports: reward.lamports,
+            post_balance: reward.post_balance,
+            reward_type: reward.reward_type.map(|reward_type| reward_type.to_string()),
+            commission: reward.commission,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +163,9 @@ pub struct TokenBalance {
 pub struct TokenBalanceChange {
     pub account: String,
     pub mint: String,
+    /// Owner of the token account as reported by the RPC (post balance, falling back to pre)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
     pub before: TokenAmount,
     pub after: TokenAmount,
     pub change: f64,
@@ -161,17 +220,67 @@ pub struct LoadedAddresses {
     pub readonly: Vec<String>,
 }
 
+/// Result of extracting a whole block
+#[derive(Debug, Default)]
+pub struct SlotExtraction {
+    /// Transactions in the block as returned by the RPC
+    pub raw_transaction_count: usize,
+    /// Block rewards; empty unless rewards mode is on
+    pub rewards: Vec<BlockReward>,
+    pub transactions: Vec<ExtractedTransaction>,
+    /// Block index and error for each transaction that could not be extracted
+    pub failures: Vec<(usize, String)>,
+}
+
+#[derive(Clone)]
 pub struct TransactionExtractor {
     rpc_client: Arc<RpcClientWithFailover>,
+    /// Block commitment; None leaves it to the RPC node's default
+    commitment: Option<CommitmentConfig>,
+    /// Drop raw pre/post token balances after computing the changes
+    lean: bool,
+    /// Fetch block rewards (off by default; they add a list of rewards to every block)
+    rewards: bool,
 }
 
 impl TransactionExtractor {
     pub fn new(rpc_url: String) -> Self {
         Self {
             rpc_client: Arc::new(RpcClientWithFailover::new(rpc_url)),
+            commitment: None,
+            lean: false,
+            rewards: false,
         }
     }
 
+    /// Request block rewards and attach them to every extracted transaction (`block_rewards`)
+    pub fn with_rewards(mut self, rewards: bool) -> Self {
+        self.rewards = rewards;
+        self
+    }
+
+    /// Whether blocks are fetched with their rewards
+    pub fn captures_rewards(&self) -> bool {
+        self.rewards
+    }
+
+    /// Lean mode keeps only `token_balance_changes`, which is what filters use, and discards the
+    /// raw pre/post token balance vectors to shrink stored and exported records
+    pub fn with_lean(mut self, lean: bool) -> Self {
+        self.lean = lean;
+        self
+    }
+
+    /// Per-URL traffic observed by this extractor's RPC client
+    pub fn rpc_stats(&self) -> Vec<crate::rpc_client_with_failover::EndpointStats> {
+        self.rpc_client.endpoint_stats()
+    }
+
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
     pub async fn extract_all_from_slots(&self, slots: Vec<u64>) -> Result<Vec<ExtractedTransaction>> {
         let mut all_transactions = Vec::new();
         
@@ -192,34 +301,64 @@ impl TransactionExtractor {
     }
 
     pub async fn extract_from_slot(&self, slot: u64) -> Result<Vec<ExtractedTransaction>> {
-        let block = self.rpc_client
+        Ok(self.extract_from_slot_detailed(slot).await?.transactions)
+    }
+
+    /// Like `extract_from_slot`, but also reports the raw transaction count and which
+    /// transactions failed to extract (used by the debug command)
+    #[tracing::instrument(name = "extract_from_slot", skip(self))]
+    pub async fn extract_from_slot_detailed(&self, slot: u64) -> Result<SlotExtraction> {
+        let block = self.fetch_block(slot).await?;
+        Ok(self.extract_from_block(block, slot))
+    }
+
+    /// The `getBlock` call of `extract_from_slot` on its own, for callers timing fetch and
+    /// extraction separately
+    #[tracing::instrument(name = "fetch_block", skip(self))]
+    pub async fn fetch_block(&self, slot: u64) -> Result<UiConfirmedBlock> {
+        self.rpc_client
             .get_block_with_config(
                 slot,
                 solana_client::rpc_config::RpcBlockConfig {
                     encoding: Some(UiTransactionEncoding::JsonParsed),
                     transaction_details: Some(solana_transaction_status::TransactionDetails::Full),
-                    rewards: Some(false),
-                    commitment: None,
+                    rewards: Some(self.rewards),
+                    commitment: self.commitment,
                     max_supported_transaction_version: Some(0),
                 },
             )
             .await
-            .context(format!("Failed to fetch block for slot {}", slot))?;
+            .context(format!("Failed to fetch block for slot {}", slot))
+    }
 
-        let mut extracted_transactions = Vec::new();
+    /// Extract every transaction of an already fetched block, without any RPC call. The block
+    /// should be fetched the way `extract_from_slot` does (full details, `JsonParsed`), otherwise
+    /// parsed instructions are missing and conditions relying on them won't match.
+    pub fn extract_from_block(&self, block: UiConfirmedBlock, slot: u64) -> SlotExtraction {
+        let mut extraction = SlotExtraction::default();
+        extraction.rewards = block.rewards.unwrap_or_default().into_iter().map(BlockReward::from).collect();
+        let block_rewards: Arc<[BlockReward]> = extraction.rewards.clone().into();
         
         if let Some(transactions) = block.transactions {
+            extraction.raw_transaction_count = transactions.len();
             for (idx, tx_with_meta) in transactions.into_iter().enumerate() {
-                match self.extract_transaction(tx_with_meta, slot, block.block_time, block.block_height) {
-                    Ok(extracted) => extracted_transactions.push(extracted),
+                match self.extract_transaction(tx_with_meta, slot, block.block_time, block.block_height, &block_rewards) {
+                    Ok(extracted) => extraction.transactions.push(extracted),
                     Err(e) => {
                         warn!("Failed to extract transaction at index {}: {}", idx, e);
+                        extraction.failures.push((idx, format!("{:#}", e)));
                     }
                 }
             }
         }
         
-        Ok(extracted_transactions)
+        extraction
+    }
+
+    /// Extract one transaction that arrived on its own (e.g. from a Geyser stream) rather than
+    /// in a block, so block time, height and rewards are unknown
+    pub fn extract_encoded(&self, tx_with_meta: EncodedTransactionWithStatusMeta, slot: u64) -> Result<ExtractedTransaction> {
+        self.extract_transaction(tx_with_meta, slot, None, None, &Arc::from(Vec::new()))
     }
 
     fn extract_transaction(
@@ -228,12 +367,36 @@ impl TransactionExtractor {
         slot: u64,
         block_time: Option<i64>,
         block_height: Option<u64>,
+        block_rewards: &Arc<[BlockReward]>,
     ) -> Result<ExtractedTransaction> {
         let meta = tx_with_meta.meta.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Transaction meta is missing"))?;
 
-        let (signature, recent_blockhash, account_keys, instructions, address_table_lookups, version) = 
+        let (signature, recent_blockhash, mut account_keys, instructions, address_table_lookups) =
             self.extract_transaction_details(&tx_with_meta.transaction)?;
+        let version = version_label(tx_with_meta.version.as_ref());
+
+        // Extract loaded addresses
+        let loaded_addresses_opt = match &meta.loaded_addresses {
+            OptionSerializer::Some(loaded) => Some(loaded.clone()),
+            _ => None,
+        };
+        let loaded_addresses = self.extract_loaded_addresses(&loaded_addresses_opt);
+
+        // A raw v0 message only lists its static keys; instruction and balance indices continue
+        // into the lookup-table addresses (writable first), which the parsed message already
+        // appends itself
+        let is_versioned = matches!(tx_with_meta.version, Some(TransactionVersion::Number(_)));
+        let num_loaded = loaded_addresses.writable.len() + loaded_addresses.readonly.len();
+        if is_versioned && is_raw_message(&tx_with_meta.transaction) {
+            account_keys.extend(loaded_addresses.writable.iter().cloned());
+            account_keys.extend(loaded_addresses.readonly.iter().cloned());
+        }
+        let static_account_keys = if is_versioned {
+            account_keys[..account_keys.len().saturating_sub(num_loaded)].to_vec()
+        } else {
+            account_keys.clone()
+        };
 
         // Extract account information with balance changes
         let accounts = self.extract_account_info(
@@ -241,6 +404,7 @@ impl TransactionExtractor {
             &meta.pre_balances,
             &meta.post_balances,
             &tx_with_meta.transaction,
+            &loaded_addresses,
         )?;
 
         // Extract balance changes
@@ -292,12 +456,31 @@ impl TransactionExtractor {
             _ => None,
         };
 
-        // Extract loaded addresses
-        let loaded_addresses_opt = match &meta.loaded_addresses {
-            OptionSerializer::Some(loaded) => Some(loaded.clone()),
-            _ => None,
+        let num_signatures = match &tx_with_meta.transaction {
+            EncodedTransaction::Json(ui_tx) => ui_tx.signatures.len(),
+            _ => 1,
         };
-        let loaded_addresses = self.extract_loaded_addresses(&loaded_addresses_opt);
+        let (pre_token_balances, post_token_balances) = if self.lean {
+            (Vec::new(), Vec::new())
+        } else {
+            (pre_token_balances, post_token_balances)
+        };
+
+        let approx_size_bytes = estimate_transaction_size(
+            num_signatures,
+            &account_keys,
+            &loaded_addresses,
+            &extracted_instructions,
+        );
+
+        let writable_account_indices: Vec<u8> = accounts.iter().enumerate()
+            .filter(|(_, account)| account.is_writable)
+            .map(|(idx, _)| idx as u8)
+            .collect();
+        let readonly_account_indices: Vec<u8> = accounts.iter().enumerate()
+            .filter(|(_, account)| !account.is_writable)
+            .map(|(idx, _)| idx as u8)
+            .collect();
 
         Ok(ExtractedTransaction {
             signature,
@@ -313,9 +496,9 @@ impl TransactionExtractor {
             },
             accounts,
             account_keys: account_keys.clone(),
-            static_account_keys: account_keys.clone(), // TODO: Differentiate static vs dynamic
-            writable_account_indices: vec![], // TODO: Extract from message header
-            readonly_account_indices: vec![], // TODO: Extract from message header
+            static_account_keys,
+            writable_account_indices,
+            readonly_account_indices,
             pre_balances: meta.pre_balances.clone(),
             post_balances: meta.post_balances.clone(),
             balance_changes,
@@ -330,13 +513,18 @@ impl TransactionExtractor {
             version,
             recent_blockhash,
             loaded_addresses,
+            approx_size_bytes,
+            slot_leader: None,
+            account_owners: HashMap::new(),
+            mint_supplies: HashMap::new(),
+            block_rewards: block_rewards.clone(),
         })
     }
 
     fn extract_transaction_details(
         &self,
         transaction: &EncodedTransaction,
-    ) -> Result<(String, String, Vec<String>, Vec<UiInstruction>, Vec<AddressTableLookup>, String)> {
+    ) -> Result<(String, String, Vec<String>, Vec<UiInstruction>, Vec<AddressTableLookup>)> {
         match transaction {
             EncodedTransaction::Json(ui_tx) => {
                 let signature = ui_tx.signatures.first()
@@ -353,7 +541,7 @@ impl TransactionExtractor {
                             parsed_msg.recent_blockhash.clone(),
                             account_keys,
                             parsed_msg.instructions.clone(),
-                            vec![], // Address table lookups not in parsed message
+                            convert_address_table_lookups(parsed_msg.address_table_lookups.as_deref()),
                         )
                     },
                     UiMessage::Raw(raw_msg) => {
@@ -361,14 +549,12 @@ impl TransactionExtractor {
                             raw_msg.recent_blockhash.clone(),
                             raw_msg.account_keys.clone(),
                             self.convert_compiled_instructions(&raw_msg.instructions),
-                            vec![], // TODO: Extract address table lookups
+                            convert_address_table_lookups(raw_msg.address_table_lookups.as_deref()),
                         )
                     },
                 };
                 
-                let version = "legacy".to_string(); // TODO: Extract actual version
-                
-                Ok((signature, recent_blockhash, account_keys, instructions, address_table_lookups, version))
+                Ok((signature, recent_blockhash, account_keys, instructions, address_table_lookups))
             },
             _ => Err(anyhow::anyhow!("Unsupported transaction encoding")),
         }
@@ -386,19 +572,22 @@ impl TransactionExtractor {
         pre_balances: &[u64],
         post_balances: &[u64],
         transaction: &EncodedTransaction,
+        loaded_addresses: &LoadedAddresses,
     ) -> Result<Vec<AccountInfo>> {
         let mut accounts = Vec::new();
+        let flags = account_flags(transaction, loaded_addresses);
         
         for (idx, account_key) in account_keys.iter().enumerate() {
             let pre_balance = pre_balances.get(idx).copied().unwrap_or(0);
             let post_balance = post_balances.get(idx).copied().unwrap_or(0);
             let balance_change = post_balance as i64 - pre_balance as i64;
+            let (is_signer, is_writable) = flags.get(idx).copied().unwrap_or((false, false));
             
-            // TODO: Extract signer/writable/program info from transaction
+            // TODO: Extract program info from transaction
             let account_info = AccountInfo {
                 pubkey: account_key.clone(),
-                is_signer: false, // TODO: Extract from message
-                is_writable: false, // TODO: Extract from message
+                is_signer,
+                is_writable,
                 is_program: false, // TODO: Detect program accounts
                 pre_balance,
                 post_balance,
@@ -509,9 +698,13 @@ impl TransactionExtractor {
                 let change = after.ui_amount.unwrap_or(0.0) - before.ui_amount.unwrap_or(0.0);
                 
                 if change.abs() > 0.0 {
+                    let owner = post_balance.and_then(|tb| tb.owner.clone())
+                        .or_else(|| pre_balance.and_then(|tb| tb.owner.clone()));
+                    
                     changes.push(TokenBalanceChange {
                         account: account.clone(),
                         mint: mint.to_string(),
+                        owner,
                         before,
                         after,
                         change,
@@ -550,13 +743,15 @@ impl TransactionExtractor {
                         .unwrap_or_else(|| format!("Unknown({})", idx)))
                     .collect();
                 
+                let parsed = parse_compute_budget_instruction(&program_id, &compiled.data);
+                
                 Ok(ExtractedInstruction {
                     program_id,
                     program_name: None,
-                    instruction_type: None,
+                    instruction_type: parsed.as_ref().map(|p| p.instruction_type.clone()),
                     accounts,
                     data: compiled.data.clone(),
-                    parsed: None,
+                    parsed,
                     stack_height: compiled.stack_height,
                 })
             },
@@ -570,7 +765,7 @@ impl TransactionExtractor {
                                 .and_then(|t| t.as_str())
                                 .unwrap_or("unknown")
                                 .to_string()),
-                            accounts: vec![], // TODO: Extract from parsed info
+                            accounts: parsed_instruction_accounts(parsed_inst.parsed.get("info")),
                             data: serde_json::to_string(&parsed_inst.parsed).unwrap_or_default(),
                             parsed: Some(ParsedInstructionData {
                                 instruction_type: parsed_inst.parsed.get("type")
@@ -588,14 +783,16 @@ impl TransactionExtractor {
                         let accounts = partial.accounts.iter()
                             .map(|acc| acc.to_string())
                             .collect();
+                        // The RPC's jsonParsed encoding doesn't decode ComputeBudget, so do it here
+                        let parsed = parse_compute_budget_instruction(&partial.program_id, &partial.data);
                         
                         Ok(ExtractedInstruction {
                             program_id: partial.program_id.clone(),
                             program_name: None,
-                            instruction_type: None,
+                            instruction_type: parsed.as_ref().map(|p| p.instruction_type.clone()),
                             accounts,
                             data: partial.data.clone(),
-                            parsed: None,
+                            parsed,
                             stack_height: partial.stack_height,
                         })
                     },
@@ -639,25 +836,250 @@ impl TransactionExtractor {
     }
 }
 
-/// Create a JSON export of all extracted transactions
+/// Assumed data length for parsed instructions, whose raw data the RPC doesn't return
+const PARSED_INSTRUCTION_DATA_ESTIMATE: usize = 8;
+
+/// Approximate wire size of a transaction in bytes.
+///
+/// Parsed instructions are counted with a small fixed data length and address lookups
+/// as a single table, so treat the result as a complexity signal rather than an exact size.
+pub fn estimate_transaction_size(
+    num_signatures: usize,
+    account_keys: &[String],
+    loaded_addresses: &LoadedAddresses,
+    instructions: &[ExtractedInstruction],
+) -> usize {
+    let loaded_count = loaded_addresses.writable.len() + loaded_addresses.readonly.len();
+    let static_keys = account_keys.len().saturating_sub(loaded_count);
+    
+    let mut size = compact_u16_len(num_signatures) + 64 * num_signatures
+        + 3 // message header
+        + compact_u16_len(static_keys) + 32 * static_keys
+        + 32; // recent blockhash
+    
+    size += compact_u16_len(instructions.len());
+    for inst in instructions {
+        let data_len = if inst.parsed.is_some() {
+            PARSED_INSTRUCTION_DATA_ESTIMATE
+        } else {
+            bs58::decode(&inst.data).into_vec().map(|d| d.len()).unwrap_or(0)
+        };
+        size += 1 // program id index
+            + compact_u16_len(inst.accounts.len()) + inst.accounts.len()
+            + compact_u16_len(data_len) + data_len;
+    }
+    
+    if loaded_count > 0 {
+        // Version prefix plus one lookup table: key and writable/readonly index lists
+        size += 1 + compact_u16_len(1) + 32
+            + compact_u16_len(loaded_addresses.writable.len()) + loaded_addresses.writable.len()
+            + compact_u16_len(loaded_addresses.readonly.len()) + loaded_addresses.readonly.len();
+    }
+    
+    size
+}
+
+/// Bytes used by Solana's compact-u16 length prefix
+fn compact_u16_len(value: usize) -> usize {
+    if value < 0x80 {
+        1
+    } else if value < 0x4000 {
+        2
+    } else {
+        3
+    }
+}
+
+/// `"legacy"` or the version number (`"0"`); the RPC only omits it for legacy transactions
+pub fn version_label(version: Option<&TransactionVersion>) -> String {
+    match version {
+        Some(TransactionVersion::Number(number)) => number.to_string(),
+        Some(TransactionVersion::Legacy(_)) | None => "legacy".to_string(),
+    }
+}
+
+fn is_raw_message(transaction: &EncodedTransaction) -> bool {
+    matches!(transaction, EncodedTransaction::Json(ui_tx) if matches!(ui_tx.message, UiMessage::Raw(_)))
+}
+
+fn convert_address_table_lookups(lookups: Option<&[UiAddressTableLookup]>) -> Vec<AddressTableLookup> {
+    lookups.unwrap_or_default().iter()
+        .map(|lookup| AddressTableLookup {
+            account_key: lookup.account_key.clone(),
+            writable_indexes: lookup.writable_indexes.clone(),
+            readonly_indexes: lookup.readonly_indexes.clone(),
+        })
+        .collect()
+}
+
+/// (signer, writable) per account key. For raw messages, lookup-table addresses follow the
+/// static keys: loaded writable ones are writable, loaded read-only ones aren't, neither signs.
+fn account_flags(transaction: &EncodedTransaction, loaded_addresses: &LoadedAddresses) -> Vec<(bool, bool)> {
+    let EncodedTransaction::Json(ui_tx) = transaction else {
+        return Vec::new();
+    };
+    
+    match &ui_tx.message {
+        UiMessage::Parsed(parsed_msg) => parsed_msg.account_keys.iter()
+            .map(|key| (key.signer, key.writable))
+            .collect(),
+        UiMessage::Raw(raw_msg) => {
+            let header = &raw_msg.header;
+            let num_keys = raw_msg.account_keys.len();
+            let num_signers = header.num_required_signatures as usize;
+            let writable_signers = num_signers.saturating_sub(header.num_readonly_signed_accounts as usize);
+            let writable_unsigned_end = num_keys.saturating_sub(header.num_readonly_unsigned_accounts as usize);
+            
+            (0..num_keys)
+                .map(|idx| {
+                    let is_signer = idx < num_signers;
+                    let is_writable = if is_signer { idx < writable_signers } else { idx < writable_unsigned_end };
+                    (is_signer, is_writable)
+                })
+                .chain(loaded_addresses.writable.iter().map(|_| (false, true)))
+                .chain(loaded_addresses.readonly.iter().map(|_| (false, false)))
+                .collect()
+        },
+    }
+}
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+/// Accounts of an RPC-parsed instruction, which only come as named `info` fields (`source`,
+/// `destination`, `authority`, `mint`, `multisigAuthority`, `signers`, ...). Every string in
+/// `info` that is a valid pubkey counts, without duplicates; amounts and other values never
+/// decode to 32 bytes. The order is by field name (`serde_json::Map` sorts its keys), not the
+/// instruction's own account order.
+fn parsed_instruction_accounts(info: Option<&serde_json::Value>) -> Vec<String> {
+    fn collect(value: &serde_json::Value, accounts: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(s) => {
+                if s.parse::<Pubkey>().is_ok() && !accounts.contains(s) {
+                    accounts.push(s.clone());
+                }
+            },
+            serde_json::Value::Array(items) => items.iter().for_each(|item| collect(item, accounts)),
+            serde_json::Value::Object(fields) => fields.values().for_each(|field| collect(field, accounts)),
+            _ => {},
+        }
+    }
+    
+    let mut accounts = Vec::new();
+    if let Some(info) = info {
+        collect(info, &mut accounts);
+    }
+    accounts
+}
+
+/// Decode a ComputeBudget instruction (base58 `data`) into the same shape the RPC uses for
+/// parsed instructions, e.g. `setComputeUnitLimit` with `{"units": 1400000}`. Returns None for
+/// other programs and unknown or malformed instructions.
+pub fn parse_compute_budget_instruction(program_id: &str, data: &str) -> Option<ParsedInstructionData> {
+    if program_id != COMPUTE_BUDGET_PROGRAM_ID {
+        return None;
+    }
+    
+    let bytes = bs58::decode(data).into_vec().ok()?;
+    let (&tag, rest) = bytes.split_first()?;
+    let u32_arg = || rest.get(..4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    let u64_arg = || rest.get(..8).map(|b| u64::from_le_bytes(b.try_into().unwrap()));
+    
+    let (instruction_type, info) = match tag {
+        1 => ("requestHeapFrame", serde_json::json!({ "bytes": u32_arg()? })),
+        2 => ("setComputeUnitLimit", serde_json::json!({ "units": u32_arg()? })),
+        3 => ("setComputeUnitPrice", serde_json::json!({ "microLamports": u64_arg()? })),
+        4 => ("setLoadedAccountsDataSizeLimit", serde_json::json!({ "bytes": u32_arg()? })),
+        _ => return None,
+    };
+    
+    Some(ParsedInstructionData {
+        instruction_type: instruction_type.to_string(),
+        info,
+    })
+}
+
+/// Serialize an export either indented (readable) or compact (roughly half the size for
+/// transaction dumps)
+pub fn to_export_json<T: Serialize + ?Sized>(value: &T, pretty: bool) -> Result<String> {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    };
+    Ok(json)
+}
+
+/// Buffered writer for export files, gzip-compressed when the path ends in `.gz`. Call
+/// `finish` so the gzip trailer and any write error aren't lost on drop.
+pub enum ExportWriter {
+    Plain(std::io::BufWriter<std::fs::File>),
+    Gzip(flate2::write::GzEncoder<std::io::BufWriter<std::fs::File>>),
+}
+
+impl ExportWriter {
+    pub fn create(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::io::BufWriter::new(
+            std::fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?
+        );
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gz")) {
+            Ok(Self::Gzip(flate2::write::GzEncoder::new(file, flate2::Compression::default())))
+        } else {
+            Ok(Self::Plain(file))
+        }
+    }
+    
+    pub fn finish(self) -> Result<()> {
+        use std::io::Write;
+        
+        let mut file = match self {
+            Self::Plain(file) => file,
+            Self::Gzip(encoder) => encoder.finish().context("Failed to finish gzip stream")?,
+        };
+        file.flush().context("Failed to flush export file")
+    }
+}
+
+impl std::io::Write for ExportWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(file) => file.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+    
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(file) => file.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Create a JSON export of all extracted transactions (gzip-compressed for `.gz` paths)
 pub fn export_transactions_to_json(
     transactions: &[ExtractedTransaction],
     output_path: &str,
+    pretty: bool,
 ) -> Result<()> {
-    let json = serde_json::to_string_pretty(transactions)?;
-    std::fs::write(output_path, json)?;
+    use std::io::Write;
+    
+    let json = to_export_json(transactions, pretty)?;
+    let mut file = ExportWriter::create(output_path)?;
+    file.write_all(json.as_bytes())?;
+    file.finish()?;
     info!("Exported {} transactions to {}", transactions.len(), output_path);
     Ok(())
 }
 
-/// Create a CSV export of transaction summaries
+/// Create a CSV export of transaction summaries (gzip-compressed for `.gz` paths)
 pub fn export_transaction_summary_csv(
     transactions: &[ExtractedTransaction],
     output_path: &str,
 ) -> Result<()> {
     use std::io::Write;
     
-    let mut file = std::fs::File::create(output_path)?;
+    let mut file = ExportWriter::create(output_path)?;
     writeln!(file, "signature,slot,timestamp,success,fee,compute_units,num_instructions,num_logs,total_balance_change")?;
     
     for tx in transactions {
@@ -680,6 +1102,174 @@ pub fn export_transaction_summary_csv(
         )?;
     }
     
+    file.finish()?;
     info!("Exported transaction summary to {}", output_path);
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_compute_budget_instruction() {
+        let mut limit = vec![2u8];
+        limit.extend_from_slice(&1_400_000u32.to_le_bytes());
+        let parsed = parse_compute_budget_instruction(
+            COMPUTE_BUDGET_PROGRAM_ID,
+            &bs58::encode(&limit).into_string(),
+        ).unwrap();
+        assert_eq!(parsed.instruction_type, "setComputeUnitLimit");
+        assert_eq!(parsed.info["units"], 1_400_000);
+        
+        let mut price = vec![3u8];
+        price.extend_from_slice(&50_000u64.to_le_bytes());
+        let parsed = parse_compute_budget_instruction(
+            COMPUTE_BUDGET_PROGRAM_ID,
+            &bs58::encode(&price).into_string(),
+        ).unwrap();
+        assert_eq!(parsed.info["microLamports"], 50_000);
+        
+        // Truncated argument, unknown tag and other programs
+        assert!(parse_compute_budget_instruction(COMPUTE_BUDGET_PROGRAM_ID, &bs58::encode([2u8, 1]).into_string()).is_none());
+        assert!(parse_compute_budget_instruction(COMPUTE_BUDGET_PROGRAM_ID, &bs58::encode([9u8]).into_string()).is_none());
+        assert!(parse_compute_budget_instruction("11111111111111111111111111111111", &bs58::encode(&limit).into_string()).is_none());
+    }
+    
+    #[test]
+    fn test_extract_from_block_without_rpc() {
+        let block: UiConfirmedBlock = serde_json::from_value(serde_json::json!({
+            "previousBlockhash": "11111111111111111111111111111111",
+            "blockhash": "11111111111111111111111111111111",
+            "parentSlot": 99,
+            "blockTime": 1_700_000_000,
+            "blockHeight": 90,
+            "transactions": [{
+                "transaction": {
+                    "signatures": ["sig1"],
+                    "message": {
+                        "header": { "numRequiredSignatures": 1, "numReadonlySignedAccounts": 0, "numReadonlyUnsignedAccounts": 1 },
+                        "accountKeys": ["payer", "11111111111111111111111111111111"],
+                        "recentBlockhash": "11111111111111111111111111111111",
+                        "instructions": [{ "programIdIndex": 1, "accounts": [0], "data": "", "stackHeight": null }]
+                    }
+                },
+                "meta": {
+                    "err": null,
+                    "status": { "Ok": null },
+                    "fee": 5000,
+                    "preBalances": [1_000_000, 1],
+                    "postBalances": [995_000, 1]
+                }
+            }]
+        })).unwrap();
+        
+        // Nothing listens on this URL; extraction must not need it
+        let extractor = TransactionExtractor::new("http://127.0.0.1:1".to_string());
+        let extraction = extractor.extract_from_block(block, 100);
+        
+        assert_eq!(extraction.raw_transaction_count, 1);
+        assert!(extraction.failures.is_empty(), "{:?}", extraction.failures);
+        let transaction = &extraction.transactions[0];
+        assert_eq!(transaction.signature, "sig1");
+        assert_eq!((transaction.slot, transaction.block_time, transaction.fee), (100, Some(1_700_000_000), 5000));
+        assert!(transaction.accounts[0].is_signer && transaction.accounts[0].is_writable);
+    }
+    
+    #[test]
+    fn test_v0_transaction_includes_lookup_table_accounts() {
+        let block: UiConfirmedBlock = serde_json::from_value(serde_json::json!({
+            "previousBlockhash": "11111111111111111111111111111111",
+            "blockhash": "11111111111111111111111111111111",
+            "parentSlot": 99,
+            "transactions": [{
+                "transaction": {
+                    "signatures": ["sig1"],
+                    "message": {
+                        "header": { "numRequiredSignatures": 1, "numReadonlySignedAccounts": 0, "numReadonlyUnsignedAccounts": 1 },
+                        "accountKeys": ["payer", "11111111111111111111111111111111"],
+                        "recentBlockhash": "11111111111111111111111111111111",
+                        "instructions": [{ "programIdIndex": 1, "accounts": [0, 2], "data": "", "stackHeight": null }],
+                        "addressTableLookups": [{ "accountKey": "table", "writableIndexes": [4], "readonlyIndexes": [] }]
+                    }
+                },
+                "meta": {
+                    "err": null,
+                    "status": { "Ok": null },
+                    "fee": 5000,
+                    "preBalances": [1_000_000, 1, 0],
+                    "postBalances": [995_000, 1, 0],
+                    "loadedAddresses": { "writable": ["loaded"], "readonly": [] }
+                },
+                "version": 0
+            }]
+        })).unwrap();
+        
+        let extractor = TransactionExtractor::new("http://127.0.0.1:1".to_string());
+        let extraction = extractor.extract_from_block(block, 100);
+        assert!(extraction.failures.is_empty(), "{:?}", extraction.failures);
+        let transaction = &extraction.transactions[0];
+        assert_eq!(transaction.version, "0");
+        assert_eq!(transaction.account_keys, vec!["payer", "11111111111111111111111111111111", "loaded"]);
+        assert_eq!(transaction.static_account_keys.len(), 2);
+        assert!(transaction.accounts[2].is_writable && !transaction.accounts[2].is_signer);
+        assert_eq!(transaction.address_table_lookups[0].writable_indexes, vec![4]);
+    }
+    
+    #[test]
+    fn test_parsed_transfer_checked_lists_its_accounts() {
+        let source = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+        let mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        let destination = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+        let authority = "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1";
+        let instruction: UiInstruction = serde_json::from_value(serde_json::json!({
+            "program": "spl-token",
+            "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "parsed": {
+                "type": "transferChecked",
+                "info": {
+                    "source": source,
+                    "mint": mint,
+                    "destination": destination,
+                    "authority": authority,
+                    "tokenAmount": { "amount": "1000000", "decimals": 6, "uiAmount": 1.0, "uiAmountString": "1" }
+                }
+            },
+            "stackHeight": null
+        })).unwrap();
+        
+        let extractor = TransactionExtractor::new("http://127.0.0.1:1".to_string());
+        let extracted = extractor.extract_single_instruction(&instruction, &[]).unwrap();
+        
+        assert_eq!(extracted.instruction_type.as_deref(), Some("transferChecked"));
+        let mut accounts = extracted.accounts.clone();
+        accounts.sort();
+        let mut expected = vec![source, mint, destination, authority];
+        expected.sort();
+        assert_eq!(accounts, expected);
+    }
+    
+    #[test]
+    fn test_export_writer_compresses_gz_paths() {
+        use std::io::{Read, Write};
+        
+        let dir = std::env::temp_dir().join(format!("export-writer-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let line = b"{\"signature\":\"abc\"}\n";
+        
+        for name in ["out.jsonl", "out.jsonl.gz"] {
+            let mut writer = ExportWriter::create(dir.join(name)).unwrap();
+            writer.write_all(line).unwrap();
+            writer.finish().unwrap();
+        }
+        
+        assert_eq!(std::fs::read(dir.join("out.jsonl")).unwrap(), line);
+        let compressed = std::fs::read(dir.join("out.jsonl.gz")).unwrap();
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(&compressed[..]).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, line);
+        
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{anyhow, bail, Context, Result};
+use hmac::{Hmac, Mac};
+use reqwest::Method;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use crate::config_manager::replace_json_placeholders;
+use crate::filter_engine::MatchedFilter;
+use crate::notifier_retry::send_with_retry;
+use crate::transaction_extractor::ExtractedTransaction;
+
+/// Header carrying `sha256=<hex HMAC of the body>` when the webhook has a secret
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Sends the `Webhook` actions that don't go to Discord: any method, custom headers, a
+/// templated JSON body, an optional HMAC signature, and retries with exponential backoff
+/// (`WEBHOOK_MAX_ATTEMPTS`, default 5) on connection errors, 5xx and 429.
+pub struct WebhookSender {
+    client: reqwest::Client,
+    max_attempts: u32,
+}
+
+impl WebhookSender {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self { client, max_attempts: max_attempts_from_env() }
+    }
+
+    pub async fn send(
+        &self,
+        url: &str,
+        method: &str,
+        headers: &BTreeMap<String, String>,
+        body: &str,
+        secret: Option<&str>,
+    ) -> Result<()> {
+        let method = Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+            .with_context(|| format!("Invalid webhook method '{}'", method))?;
+        let signature = secret.map(resolve_secret).transpose()?.map(|key| sign_payload(key.as_bytes(), body.as_bytes()));
+
+        let service = format!("Webhook {}", crate::filter_summary::url_host(url));
+        let response = send_with_retry(&service, self.max_attempts, || {
+            let mut request = self.client.request(method.clone(), url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.to_string());
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            request
+        }).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            bail!("{} returned {}: {}", service, status, text.chars().take(200).collect::<String>());
+        }
+        Ok(())
+    }
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn max_attempts_from_env() -> u32 {
+    std::env::var("WEBHOOK_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|attempts| *attempts > 0)
+        .unwrap_or(DEFAULT_MAX_ATTEMPTS)
+}
+
+/// `env:NAME` reads the secret from the environment, anything else is the secret itself
+fn resolve_secret(secret: &str) -> Result<String> {
+    match secret.strip_prefix("env:") {
+        Some(name) => std::env::var(name.trim())
+            .ok()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| anyhow!("Webhook secret variable {} is not set", name.trim())),
+        None => Ok(secret.to_string()),
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 of `body`, so receivers can check the request came from us
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let digest: String = mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256={}", digest)
+}
+
+/// The request body for a match: `template` with its `${...}` placeholders filled from the
+/// transaction (plus `filter_id` and `filter_name`, strings JSON-escaped), or by default the filter, its context and
+/// the whole transaction. A template that doesn't render to valid JSON is an error.
+pub fn render_payload(template: Option<&str>, transaction: &ExtractedTransaction, matched_filter: &MatchedFilter) -> Result<String> {
+    let transaction_json = serde_json::to_value(transaction)?;
+    let Some(template) = template else {
+        return Ok(serde_json::json!({
+            "filter_id": matched_filter.filter_id,
+            "filter_name": matched_filter.filter_name,
+            "context": matched_filter.context,
+            "transaction": transaction_json,
+        }).to_string());
+    };
+
+    let mut data = transaction_json;
+    if let Some(fields) = data.as_object_mut() {
+        fields.insert("filter_id".to_string(), matched_filter.filter_id.clone().into());
+        fields.insert("filter_name".to_string(), matched_filter.filter_name.clone().into());
+    }
+    let body = replace_json_placeholders(template, &data);
+    serde_json::from_str::<serde_json::Value>(&body)
+        .with_context(|| format!("Webhook payload template of filter {} is not valid JSON after substitution", matched_filter.filter_id))?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_engine::tests::test_transaction;
+
+    #[test]
+    fn test_sign_payload_matches_known_hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_template_values_are_json_escaped() {
+        let mut transaction = test_transaction();
+        transaction.slot = 7;
+        transaction.log_messages = vec!["Program log: \"quoted\"\nnext line".to_string()];
+        let matched = MatchedFilter {
+            filter_id: "big_mint".to_string(),
+            filter_name: "Say \"hi\" \\ bye".to_string(),
+            actions: vec![],
+            cooldown_secs: None,
+            context: Default::default(),
+        };
+
+        let template = r#"{"rule": "${filter_name}", "log": "${log_messages.0}", "slot": ${slot}}"#;
+        let body = render_payload(Some(template), &transaction, &matched).unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["rule"], "Say \"hi\" \\ bye");
+        assert_eq!(payload["log"], "Program log: \"quoted\"\nnext line");
+        assert_eq!(payload["slot"], 7);
+    }
+}
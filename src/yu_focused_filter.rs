@@ -1,9 +1,13 @@
 use anyhow::{Result, Context};
+use futures::StreamExt;
 use solana_client::rpc_config::RpcBlockConfig;
-use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{TransactionDetails, UiConfirmedBlock, UiTransactionEncoding};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::block_cache;
+use crate::filter_engine::LAYERZERO_BRIDGE_PROGRAMS;
 use crate::rpc_client_with_failover::RpcClientWithFailover;
 
 /// YU-focused pre-filter that ONLY looks for YU token transactions
@@ -28,8 +32,8 @@ impl YuFocusedFilter {
             "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(), // Jupiter V6
             "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB".to_string(), // Jupiter V4
             "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(), // Orca
-            "6doghB248px58JSSwG4qejQ46kFMW4AMj7vzJnWZHNZn".to_string(), // LayerZero Bridge [OLD]
-            "3fCoNdCEoEcERakCPM17NjLE9AocA86LMwRRWDpzjLVh".to_string(), // LayerZero Bridge [NEW]
+            LAYERZERO_BRIDGE_PROGRAMS[0].to_string(), // LayerZero Bridge [OLD]
+            LAYERZERO_BRIDGE_PROGRAMS[1].to_string(), // LayerZero Bridge [NEW]
         ];
         
         info!(
@@ -106,69 +110,24 @@ impl YuFocusedFilter {
         
         info!("YU-focused filtering {} slots", slots.len());
         
-        // Process in smaller batches
-        for chunk in slots.chunks(20) {
-            let mut handles = vec![];
-            
-            for &slot in chunk {
-                let rpc_client = self.rpc_client.clone();
-                let yu_mint = self.yu_token_mint.clone();
-                
-                let handle = tokio::spawn(async move {
-                    let config = RpcBlockConfig {
-                        encoding: Some(UiTransactionEncoding::JsonParsed),
-                        transaction_details: Some(TransactionDetails::Full),
-                        rewards: Some(false),
-                        commitment: None,
-                        max_supported_transaction_version: Some(0),
-                    };
-                    
-                    match rpc_client.get_block_with_config(slot, config).await {
-                        Ok(block) => {
-                            if let Some(transactions) = block.transactions {
-                                for tx in transactions {
-                                    if let Some(meta) = &tx.meta {
-                                        // Check pre-token balances
-                                        match &meta.pre_token_balances {
-                                            solana_transaction_status::option_serializer::OptionSerializer::Some(balances) => {
-                                                for balance in balances {
-                                                    if balance.mint == yu_mint {
-                                                        return (slot, true);
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                        
-                                        // Check post-token balances
-                                        match &meta.post_token_balances {
-                                            solana_transaction_status::option_serializer::OptionSerializer::Some(balances) => {
-                                                for balance in balances {
-                                                    if balance.mint == yu_mint {
-                                                        return (slot, true);
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                            }
-                            (slot, false)
-                        }
-                        Err(_) => (slot, false),
-                    }
-                });
-                
-                handles.push(handle);
-            }
-            
-            // Collect results
-            for handle in handles {
-                if let Ok((slot, has_yu)) = handle.await {
-                    if has_yu {
-                        yu_slots.push(slot);
-                    }
+        let config = RpcBlockConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            transaction_details: Some(TransactionDetails::Full),
+            rewards: Some(false),
+            commitment: None,
+            max_supported_transaction_version: Some(0),
+        };
+        
+        // Blocks come in JSON-RPC batches, many getBlock calls per request; each batch is
+        // checked and dropped before the next one is fetched
+        let mut batches = std::pin::pin!(self.rpc_client.get_blocks_batched(&slots, config));
+        while let Some(batch) = batches.next().await {
+            for (slot, block) in batch {
+                let Ok(block) = block else { continue };
+                if block_has_mint(&block, &self.yu_token_mint) {
+                    yu_slots.push(slot);
+                    // Extraction fetches with the same config and takes the block from here
+                    block_cache::shared().insert(slot, config, block);
                 }
             }
         }
@@ -213,6 +172,15 @@ impl YuFocusedFilter {
     }
 }
 
+/// Whether any transaction in `block` has a pre or post token balance of `mint`
+fn block_has_mint(block: &UiConfirmedBlock, mint: &str) -> bool {
+    block.transactions.iter().flatten()
+        .filter_map(|tx| tx.meta.as_ref())
+        .any(|meta| [&meta.pre_token_balances, &meta.post_token_balances].into_iter().any(|balances| {
+            matches!(balances, OptionSerializer::Some(balances) if balances.iter().any(|balance| balance.mint == mint))
+        }))
+}
+
 /// Optimized configuration for YU token monitoring
 pub struct YuMonitorConfig {
     /// Only monitor slots with YU token activity